@@ -128,6 +128,7 @@ pub fn derive_translate(_input: TokenStream) -> TokenStream {
                     Rule::loc_fn => en::Rule::loc_fn,
                     Rule::loc_na => en::Rule::loc_na,
                     Rule::loc_null => en::Rule::loc_null,
+                    Rule::loc_nan => en::Rule::loc_nan,
                     Rule::loc_inf => en::Rule::loc_inf,
                     Rule::loc_true => en::Rule::loc_true,
                     Rule::loc_false => en::Rule::loc_false,
@@ -152,6 +153,15 @@ pub fn derive_translate(_input: TokenStream) -> TokenStream {
                     Rule::hl_sym => en::Rule::hl_sym,
                     Rule::hl_symbol_backticked => en::Rule::hl_symbol_backticked,
                     Rule::hl_str => en::Rule::hl_str,
+                    Rule::hl_raw_str => en::Rule::hl_raw_str,
+                    Rule::hl_raw_str_delimited => en::Rule::hl_raw_str_delimited,
+                    Rule::hl_fstr => en::Rule::hl_fstr,
+                    Rule::hl_fstr_open => en::Rule::hl_fstr_open,
+                    Rule::hl_fstr_close => en::Rule::hl_fstr_close,
+                    Rule::hl_fstr_text => en::Rule::hl_fstr_text,
+                    Rule::hl_fstr_field => en::Rule::hl_fstr_field,
+                    Rule::hl_fstr_brace => en::Rule::hl_fstr_brace,
+                    Rule::hl_fstr_spec => en::Rule::hl_fstr_spec,
                     Rule::hl_num => en::Rule::hl_num,
                     Rule::hl_infix => en::Rule::hl_infix,
                     Rule::hl_open => en::Rule::hl_open,
@@ -171,6 +181,7 @@ pub fn derive_translate(_input: TokenStream) -> TokenStream {
                     Rule::multiply => en::Rule::multiply,
                     Rule::divide => en::Rule::divide,
                     Rule::modulo => en::Rule::modulo,
+                    Rule::matmul => en::Rule::matmul,
                     Rule::power => en::Rule::power,
                     Rule::gt => en::Rule::gt,
                     Rule::gte => en::Rule::gte,
@@ -211,21 +222,41 @@ pub fn derive_translate(_input: TokenStream) -> TokenStream {
                     Rule::kw_continue => en::Rule::kw_continue,
                     Rule::val_null => en::Rule::val_null,
                     Rule::val_na => en::Rule::val_na,
+                    Rule::val_nan => en::Rule::val_nan,
                     Rule::val_inf => en::Rule::val_inf,
                     Rule::val_true => en::Rule::val_true,
                     Rule::val_false => en::Rule::val_false,
                     Rule::number => en::Rule::number,
                     Rule::number_leading => en::Rule::number_leading,
                     Rule::number_trailing => en::Rule::number_trailing,
+                    Rule::number_exponent => en::Rule::number_exponent,
+                    Rule::number_hex => en::Rule::number_hex,
+                    Rule::number_hex_digits => en::Rule::number_hex_digits,
+                    Rule::number_hex_fraction => en::Rule::number_hex_fraction,
+                    Rule::number_hex_exponent => en::Rule::number_hex_exponent,
                     Rule::more => en::Rule::more,
                     Rule::integer_expr => en::Rule::integer_expr,
                     Rule::integer => en::Rule::integer,
+                    Rule::imaginary_expr => en::Rule::imaginary_expr,
+                    Rule::imaginary => en::Rule::imaginary,
                     Rule::string_expr => en::Rule::string_expr,
                     Rule::single_quoted_string => en::Rule::single_quoted_string,
                     Rule::double_quoted_string => en::Rule::double_quoted_string,
                     Rule::single_quoted_string_char => en::Rule::single_quoted_string_char,
                     Rule::double_quoted_string_char => en::Rule::double_quoted_string_char,
                     Rule::escaped_char => en::Rule::escaped_char,
+                    Rule::raw_string_expr => en::Rule::raw_string_expr,
+                    Rule::raw_string_delimited => en::Rule::raw_string_delimited,
+                    Rule::raw_string_paren => en::Rule::raw_string_paren,
+                    Rule::raw_string_bracket => en::Rule::raw_string_bracket,
+                    Rule::raw_string_brace => en::Rule::raw_string_brace,
+                    Rule::fstring_expr => en::Rule::fstring_expr,
+                    Rule::fstring => en::Rule::fstring,
+                    Rule::fstring_text => en::Rule::fstring_text,
+                    Rule::fstring_field => en::Rule::fstring_field,
+                    Rule::fstring_code => en::Rule::fstring_code,
+                    Rule::fstring_code_char => en::Rule::fstring_code_char,
+                    Rule::namespaced => en::Rule::namespaced,
                     Rule::symbol => en::Rule::symbol,
                     Rule::symbol_with_backticks => en::Rule::symbol_with_backticks,
                     Rule::symbol_backticked => en::Rule::symbol_backticked,
@@ -235,6 +266,9 @@ pub fn derive_translate(_input: TokenStream) -> TokenStream {
                     Rule::elem => en::Rule::elem,
                     Rule::named => en::Rule::named,
                     Rule::vec => en::Rule::vec,
+                    Rule::index_pairs => en::Rule::index_pairs,
+                    Rule::index_elem => en::Rule::index_elem,
+                    Rule::index_missing => en::Rule::index_missing,
                 }
             }
         }
@@ -272,7 +306,7 @@ pub fn derive_localized_parser(input: TokenStream) -> TokenStream {
                         | Op::infix(neq, Left))
                     .op(Op::infix(add, Left) | Op::infix(subtract, Left))
                     .op(Op::infix(multiply, Left) | Op::infix(divide, Left))
-                    .op(Op::infix(modulo, Left) | Op::infix(special, Left) | Op::infix(pipe, Left))
+                    .op(Op::infix(modulo, Left) | Op::infix(matmul, Left) | Op::infix(special, Left) | Op::infix(pipe, Left))
                     .op(Op::infix(power, Left))
                     .op(Op::infix(colon, Left))
                     .op(Op::infix(dollar, Left))
@@ -280,6 +314,7 @@ pub fn derive_localized_parser(input: TokenStream) -> TokenStream {
         }
 
         extern crate self as __localized_parser_r__;
+        use __localized_parser_r__::cli::Experiment;
         use __localized_parser_r__::error::Error;
         use __localized_parser_r__::lang:: Signal;
         use __localized_parser_r__::session:: SessionParserConfig;
@@ -293,7 +328,14 @@ pub fn derive_localized_parser(input: TokenStream) -> TokenStream {
                     Ok(pairs) if pairs.len() == 0 => Err(Signal::Thunk),
 
                     // for any expressions
-                    Ok(pairs) => parse_expr(config, self, pratt_parser(), pairs),
+                    Ok(pairs) => {
+                        let expr = parse_expr(config, self, pratt_parser(), pairs)?;
+                        if config.experiments.contains(&Experiment::Optimize) {
+                            Ok(optimize(expr))
+                        } else {
+                            Ok(expr)
+                        }
+                    }
                     Err(e) => Err(Signal::Error(Error::from_parse_error(input, e))),
                 }
             }
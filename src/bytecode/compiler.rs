@@ -0,0 +1,351 @@
+use std::rc::Rc;
+
+use super::{BinaryOp, Code, Instr};
+use crate::callable::core::Builtin;
+use crate::callable::keywords::*;
+use crate::callable::operators::InfixAssign;
+use crate::object::*;
+use crate::parser::{combined, value};
+
+/// Builtins which inspect or modify environments at runtime, such that the
+/// variables of a closure calling them can't be resolved ahead of time
+const DYNAMIC: [&str; 3] = ["eval", "environment", "parent"];
+
+/// Compile a closure body
+///
+/// Variables are resolved lexically, given the closure's formals and the
+/// environment in which it was defined. Local variables are assigned slots
+/// in the closure's call environment, while free variables are resolved to
+/// the slots of enclosing closure calls where possible.
+///
+/// Returns `None` when the body compiles to a single instruction, as there
+/// is nothing to gain over evaluating it directly.
+///
+pub fn compile(formals: &ExprList, body: &Expr, env: &Rc<Environment>) -> Option<Code> {
+    let mut compiler = Compiler::default();
+
+    if !calls_dynamic(body) {
+        let mut locals = vec![];
+        for (key, value) in formals.keys.iter().zip(formals.values.iter()) {
+            match (key, value) {
+                (Some(name), _) | (None, Expr::Ellipsis(Some(name))) => locals.push(*name),
+                _ => (),
+            }
+        }
+
+        // arguments not matched to a formal are bound to `...`
+        locals.push(Symbol::new("..."));
+        assigned(body, &mut locals);
+
+        compiler.scope = Some(Scope { locals, env: env.clone() });
+    }
+
+    compiler.expr(body, true);
+
+    if compiler.instrs.len() <= 1 {
+        None
+    } else {
+        let layout = compiler.scope.map(|scope| scope.locals);
+        Some(Code { instrs: compiler.instrs, layout })
+    }
+}
+
+/// Whether an expression calls a builtin that manipulates environments
+fn calls_dynamic(expr: &Expr) -> bool {
+    match expr {
+        Expr::Call(what, args) => {
+            let dynamic = what.as_name().is_some_and(|name| DYNAMIC.contains(&name));
+            dynamic || calls_dynamic(what) || args.values.iter().any(calls_dynamic)
+        }
+        Expr::List(args) => args.values.iter().any(calls_dynamic),
+        Expr::Function(_, body) => calls_dynamic(body),
+        _ => false,
+    }
+}
+
+/// Collect the names that are assigned in a closure body
+///
+/// The bodies of nested functions are skipped, as their assignments are
+/// local to their own calls.
+///
+fn assigned(expr: &Expr, names: &mut Vec<Symbol>) {
+    let Expr::Call(what, args) = expr else {
+        return;
+    };
+
+    if let Expr::Primitive(f) = what.as_ref() {
+        let target = if is::<InfixAssign>(f.as_ref()) {
+            args.values.first().and_then(Expr::as_name).map(Symbol::new)
+        } else if is::<KeywordFor>(f.as_ref()) {
+            args.keys.first().copied().flatten()
+        } else {
+            None
+        };
+
+        if let Some(name) = target {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+    }
+
+    assigned(what, names);
+    for arg in args.values.iter() {
+        assigned(arg, names);
+    }
+}
+
+/// The variables in scope of a closure body
+struct Scope {
+    /// Local variables, in the order of their slots
+    locals: Vec<Symbol>,
+    /// The environment in which the closure was defined
+    env: Rc<Environment>,
+}
+
+impl Scope {
+    /// Resolve a variable to the depth of the environment binding it and its
+    /// slot in that environment
+    ///
+    /// Enclosing environments can only be skipped over while they are
+    /// sealed, as otherwise they may bind any name.
+    ///
+    fn resolve(&self, name: Symbol) -> Option<(usize, usize)> {
+        if let Some(slot) = self.locals.iter().position(|local| *local == name) {
+            return Some((0, slot));
+        }
+
+        let mut env = self.env.clone();
+        let mut depth = 1;
+        loop {
+            let bindings = env.values.borrow();
+            if !bindings.is_sealed() {
+                return None;
+            }
+
+            if let Some(slot) = bindings.slot(&name) {
+                return Some((depth, slot));
+            }
+
+            let parent = env.parent.clone()?;
+            drop(bindings);
+            env = parent;
+            depth += 1;
+        }
+    }
+}
+
+#[derive(Default)]
+struct Compiler {
+    instrs: Vec<Instr>,
+    scope: Option<Scope>,
+}
+
+fn is<T: 'static>(f: &dyn Builtin) -> bool {
+    f.as_any().is::<T>()
+}
+
+impl Compiler {
+    fn emit(&mut self, instr: Instr) -> usize {
+        self.instrs.push(instr);
+        self.instrs.len() - 1
+    }
+
+    fn next(&self) -> usize {
+        self.instrs.len()
+    }
+
+    /// Point the jump at instruction `at` to the next instruction
+    fn patch(&mut self, at: usize) {
+        let to = self.next();
+        match &mut self.instrs[at] {
+            Instr::Jump(target)
+            | Instr::JumpIfFalse(target)
+            | Instr::Next(_, target)
+            | Instr::Body(target, _)
+            | Instr::Frame(_, target) => *target = to,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Compile an expression, leaving its value on the stack
+    ///
+    /// `tail` indicates that the expression is in the tail position of the
+    /// closure body, where the tree-walking evaluator hands expressions back
+    /// to the caller as tail calls.
+    ///
+    fn expr(&mut self, expr: &Expr, tail: bool) {
+        // literals and constant expressions are computed once, when compiled
+        if let Some(value) = value(expr) {
+            self.emit(Instr::Const(value));
+            return;
+        }
+
+        match expr {
+            Expr::Symbol(name) => {
+                let resolved = self.scope.as_ref().and_then(|s| s.resolve(*name));
+                self.emit(match resolved {
+                    Some((depth, slot)) => Instr::LoadSlot(*name, depth, slot),
+                    None => Instr::Load(*name),
+                });
+            }
+            Expr::Break => {
+                self.emit(Instr::Break);
+            }
+            Expr::Continue => {
+                self.emit(Instr::Continue);
+            }
+            Expr::Call(what, args) => {
+                let compiled = match what.as_ref() {
+                    Expr::Primitive(f) => self.primitive_call(expr, f.as_ref(), args, tail),
+                    // vectors of constants are built once, unless `c` is shadowed
+                    Expr::Symbol(name) if name == "c" => match combined(args) {
+                        Some(value) => {
+                            self.emit(Instr::BuiltinConst(*name, value, expr.clone()));
+                            true
+                        }
+                        None => false,
+                    },
+                    _ => false,
+                };
+
+                if !compiled {
+                    self.emit(if tail {
+                        Instr::Tail(expr.clone())
+                    } else {
+                        Instr::Eval(expr.clone())
+                    });
+                }
+            }
+            _ => {
+                self.emit(Instr::Eval(expr.clone()));
+            }
+        }
+    }
+
+    /// Compile a call to a primitive, returning `false` if it is unsupported
+    fn primitive_call(
+        &mut self,
+        call: &Expr,
+        f: &dyn Builtin,
+        args: &ExprList,
+        tail: bool,
+    ) -> bool {
+        let values = &args.values;
+
+        if is::<KeywordBlock>(f) {
+            self.block(values, tail);
+        } else if is::<KeywordParen>(f) && values.len() == 1 {
+            self.expr(&values[0], false);
+        } else if is::<KeywordIf>(f) && values.len() == 3 {
+            self.if_else(&values[0], &values[1], &values[2], tail);
+        } else if is::<KeywordFor>(f) && values.len() == 2 {
+            let Some(var) = &args.keys[0] else {
+                return false;
+            };
+            self.for_loop(*var, &values[0], &values[1]);
+        } else if is::<KeywordWhile>(f) && values.len() == 2 {
+            self.while_loop(&values[0], &values[1]);
+        } else if is::<KeywordRepeat>(f) && !values.is_empty() {
+            self.repeat_loop(&values[0]);
+        } else if is::<InfixAssign>(f) && values.len() == 2 {
+            let name = match &values[0] {
+                Expr::Symbol(name) => *name,
+                Expr::String(name) => Symbol::from(name),
+                _ => return false,
+            };
+
+            let frame = self.emit(Instr::Frame(call.clone(), 0));
+            self.expr(&values[1], false);
+            let resolved = self.scope.as_ref().and_then(|s| s.resolve(name));
+            self.emit(match resolved {
+                Some((0, slot)) => Instr::StoreSlot(name, slot),
+                _ => Instr::Store(name),
+            });
+            self.patch(frame);
+            self.emit(Instr::ExitFrame);
+        } else if let (Some(op), 2) = (BinaryOp::from_builtin(f), values.len()) {
+            let frame = self.emit(Instr::Frame(call.clone(), 0));
+            self.operand(&values[0]);
+            self.operand(&values[1]);
+            let exprs = ExprList::from(vec![values[0].clone(), values[1].clone()]);
+            self.emit(Instr::Binary(op, exprs));
+            self.patch(frame);
+            self.emit(Instr::ExitFrame);
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    fn operand(&mut self, expr: &Expr) {
+        self.expr(expr, false);
+        if !matches!(
+            self.instrs.last(),
+            Some(Instr::Load(_) | Instr::LoadSlot(..) | Instr::Const(_))
+        ) {
+            self.emit(Instr::Force);
+        }
+    }
+
+    fn block(&mut self, exprs: &[Expr], tail: bool) {
+        let Some((last, exprs)) = exprs.split_last() else {
+            self.emit(Instr::Const(Obj::Null));
+            return;
+        };
+
+        for expr in exprs {
+            self.expr(expr, false);
+            self.emit(Instr::Pop);
+        }
+
+        self.expr(last, tail);
+    }
+
+    fn if_else(&mut self, cond: &Expr, then: &Expr, otherwise: &Expr, tail: bool) {
+        self.expr(cond, false);
+        let jump_else = self.emit(Instr::JumpIfFalse(0));
+        self.expr(then, tail);
+        let jump_end = self.emit(Instr::Jump(0));
+        self.patch(jump_else);
+        self.expr(otherwise, tail);
+        self.patch(jump_end);
+    }
+
+    fn for_loop(&mut self, var: Symbol, iter: &Expr, body: &Expr) {
+        self.expr(iter, false);
+        self.emit(Instr::For);
+        let next = self.emit(Instr::Next(var, 0));
+        let enter = self.emit(Instr::Body(0, next));
+        self.expr(body, false);
+        self.emit(Instr::ExitBody);
+        self.emit(Instr::Jump(next));
+        self.patch(next);
+        self.patch(enter);
+        self.emit(Instr::EndFor);
+    }
+
+    fn while_loop(&mut self, cond: &Expr, body: &Expr) {
+        self.emit(Instr::Const(Obj::Null));
+        let start = self.next();
+        self.expr(cond, false);
+        let exit = self.emit(Instr::JumpIfFalse(0));
+        let enter = self.emit(Instr::Body(0, start));
+        self.expr(body, false);
+        self.emit(Instr::ExitBody);
+        self.emit(Instr::Jump(start));
+        self.patch(exit);
+        self.patch(enter);
+    }
+
+    fn repeat_loop(&mut self, body: &Expr) {
+        self.emit(Instr::Const(Obj::Null));
+        let start = self.next();
+        let enter = self.emit(Instr::Body(0, start));
+        self.expr(body, false);
+        self.emit(Instr::ExitBody);
+        self.emit(Instr::Jump(start));
+        self.patch(enter);
+    }
+}
@@ -0,0 +1,453 @@
+//! Bytecode Compilation of Closures
+//!
+//! Closure bodies are compiled into a flat sequence of instructions, which
+//! are executed by a small stack-based virtual machine. Control flow
+//! (blocks, conditionals and loops), assignment to symbols and arithmetic
+//! operators are compiled, while any other expression is handed back to the
+//! tree-walking evaluator, such that compiled closures produce the same
+//! results as their evaluated bodies.
+//!
+//! Variables are resolved when a closure is compiled. Local variables are
+//! given slots in the environment of each call, and variables of enclosing
+//! closure calls are addressed by the depth of their environment and their
+//! slot within it, such that they are found without hashing their names.
+//! Whenever an environment binds names beyond those it reserved, for example
+//! through `eval()`, resolved variables fall back to a lookup by name.
+//!
+//! Calls to primitives introduce a call frame when they are evaluated. The
+//! virtual machine defers pushing these frames onto the call stack until
+//! evaluation might observe them, such as when it falls back to the
+//! tree-walking evaluator or when a signal propagates out of the frame.
+//!
+
+mod compiler;
+mod vm;
+
+use std::cell::OnceCell;
+use std::fmt::Debug;
+use std::rc::Rc;
+
+use crate::callable::core::{Builtin, Sym};
+use crate::callable::operators::*;
+use crate::lang::EvalResult;
+use crate::object::*;
+
+pub use compiler::compile;
+
+/// A single instruction of compiled code
+///
+/// Jump targets are instruction indices into the enclosing [`Code`].
+///
+#[derive(Debug, Clone)]
+pub enum Instr {
+    /// Push a constant value
+    Const(Obj),
+    /// Push the value of a call to a builtin computed when compiled, or
+    /// evaluate the call if the builtin is shadowed by a function
+    BuiltinConst(Symbol, Obj, Expr),
+    /// Push the value of a symbol, forcing promises
+    Load(Symbol),
+    /// Push the value of a symbol resolved to the slot of an environment at
+    /// the given depth, forcing promises
+    LoadSlot(Symbol, usize, usize),
+    /// Bind the value on top of the stack to a symbol, leaving it in place
+    Store(Symbol),
+    /// Bind the value on top of the stack to the slot of a local variable,
+    /// leaving it in place
+    StoreSlot(Symbol, usize),
+    /// Discard the value on top of the stack
+    Pop,
+    /// Force the value on top of the stack if it is a promise
+    Force,
+    /// Continue at an instruction
+    Jump(usize),
+    /// Pop a condition, continuing at an instruction if it is false
+    JumpIfFalse(usize),
+    /// Enter the frame of a primitive call, exited at the given instruction
+    Frame(Expr, usize),
+    /// Exit the innermost frame
+    ExitFrame,
+    /// Apply a binary operator to the top two values, given the operand
+    /// expressions for dispatch
+    Binary(BinaryOp, ExprList),
+    /// Pop an iterable value, starting a `for` loop over its elements
+    For,
+    /// Bind the next element of the innermost `for` loop to a symbol, or
+    /// continue at an instruction once the elements are exhausted
+    Next(Symbol, usize),
+    /// Finish the innermost `for` loop
+    EndFor,
+    /// Enter a loop body, which continues at the first instruction on
+    /// `break` and at the second on `next`
+    Body(usize, usize),
+    /// Exit a loop body, replacing the loop's result with the body's value
+    ExitBody,
+    /// Signal a `break`
+    Break,
+    /// Signal a `next`
+    Continue,
+    /// Evaluate an expression using the tree-walking evaluator
+    Eval(Expr),
+    /// Return an expression to be evaluated as a tail call
+    Tail(Expr),
+}
+
+/// Binary operators applied without evaluating a call
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Pow,
+    Mod,
+    MatMul,
+    Or,
+    And,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Equal,
+    NotEqual,
+}
+
+impl BinaryOp {
+    pub fn from_builtin(f: &dyn Builtin) -> Option<BinaryOp> {
+        use BinaryOp::*;
+        let f = f.as_any();
+        let op = if f.is::<InfixAdd>() {
+            Add
+        } else if f.is::<InfixSub>() {
+            Sub
+        } else if f.is::<InfixMul>() {
+            Mul
+        } else if f.is::<InfixDiv>() {
+            Div
+        } else if f.is::<InfixPow>() {
+            Pow
+        } else if f.is::<InfixMod>() {
+            Mod
+        } else if f.is::<InfixMatMul>() {
+            MatMul
+        } else if f.is::<InfixVectorOr>() {
+            Or
+        } else if f.is::<InfixVectorAnd>() {
+            And
+        } else if f.is::<InfixGreater>() {
+            Greater
+        } else if f.is::<InfixGreaterEqual>() {
+            GreaterEqual
+        } else if f.is::<InfixLess>() {
+            Less
+        } else if f.is::<InfixLessEqual>() {
+            LessEqual
+        } else if f.is::<InfixEqual>() {
+            Equal
+        } else if f.is::<InfixNotEqual>() {
+            NotEqual
+        } else {
+            return None;
+        };
+
+        Some(op)
+    }
+
+    /// The symbol used to dispatch the operator on classed operands
+    pub fn sym(&self) -> &'static str {
+        use BinaryOp::*;
+        match self {
+            Add => InfixAdd::SYM,
+            Sub => InfixSub::SYM,
+            Mul => InfixMul::SYM,
+            Div => InfixDiv::SYM,
+            Pow => InfixPow::SYM,
+            Mod => InfixMod::SYM,
+            MatMul => InfixMatMul::SYM,
+            Or => InfixVectorOr::SYM,
+            And => InfixVectorAnd::SYM,
+            Greater => InfixGreater::SYM,
+            GreaterEqual => InfixGreaterEqual::SYM,
+            Less => InfixLess::SYM,
+            LessEqual => InfixLessEqual::SYM,
+            Equal => InfixEqual::SYM,
+            NotEqual => InfixNotEqual::SYM,
+        }
+    }
+
+    pub fn apply(&self, lhs: Obj, rhs: Obj) -> EvalResult {
+        use BinaryOp::*;
+        match self {
+            Add => lhs + rhs,
+            Sub => lhs - rhs,
+            Mul => lhs * rhs,
+            Div => lhs / rhs,
+            Pow => lhs.power(rhs),
+            Mod => lhs % rhs,
+            MatMul => lhs.matmul(&rhs),
+            Or => lhs | rhs,
+            And => lhs & rhs,
+            Greater => lhs.vec_gt(rhs),
+            GreaterEqual => lhs.vec_gte(rhs),
+            Less => lhs.vec_lt(rhs),
+            LessEqual => lhs.vec_lte(rhs),
+            Equal => lhs.vec_eq(rhs),
+            NotEqual => lhs.vec_neq(rhs),
+        }
+    }
+}
+
+/// Compiled code for a closure body
+#[derive(Debug, Clone, Default)]
+pub struct Code {
+    pub instrs: Vec<Instr>,
+    /// The names for which slots are reserved in the environment of each
+    /// call, or `None` if variables were not resolved
+    pub layout: Option<Vec<Symbol>>,
+}
+
+/// The compiled body of a closure
+///
+/// Compilation happens the first time the closure is called and is shared
+/// by all copies of the function object. Bodies that would gain nothing from
+/// compilation are left to the tree-walking evaluator.
+///
+#[derive(Clone, Default)]
+pub struct Compiled(Rc<OnceCell<Option<Rc<Code>>>>);
+
+impl Compiled {
+    pub fn code(&self, formals: &ExprList, body: &Expr, env: &Rc<Environment>) -> Option<Rc<Code>> {
+        self.0
+            .get_or_init(|| compile(formals, body, env).map(Rc::new))
+            .clone()
+    }
+}
+
+impl Debug for Compiled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0.get() {
+            Some(Some(code)) => write!(f, "<bytecode ({} instructions)>", code.instrs.len()),
+            _ => write!(f, "<not compiled>"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::compile;
+    use crate::context::Context;
+    use crate::lang::CallStack;
+    use crate::object::ExprList;
+    use crate::{r, r_expect};
+
+    #[test]
+    fn closure_bodies_are_compiled() {
+        let stack = CallStack::default();
+        let formals = ExprList::new();
+        let body = stack
+            .parse("{ s <- 0; for (i in x) s <- s + i; s }")
+            .unwrap();
+        assert!(compile(&formals, &body, &stack.env()).is_some());
+
+        // a body that is only a call is left to the evaluator
+        let body = stack.parse("f(x)").unwrap();
+        assert!(compile(&formals, &body, &stack.env()).is_none());
+    }
+
+    #[test]
+    fn compiled_loops() {
+        assert_eq!(
+            r! {{"
+                f <- function(n) {
+                    s <- 0
+                    for (i in 1:n) {
+                        if (i %% 2 == 0) continue
+                        if (i > 9) break
+                        s <- s + i
+                    }
+                    s
+                }
+                f(100)
+            "}},
+            r! { 25 }
+        );
+
+        assert_eq!(
+            r! {{"
+                f <- function(n) {
+                    i <- 0
+                    while (TRUE) {
+                        i <- i + 1
+                        if (i >= n) break
+                    }
+                    i
+                }
+                f(5)
+            "}},
+            r! { 5 }
+        );
+
+        assert_eq!(
+            r! {{"
+                f <- function() {
+                    x <- for (i in 1:3) i * 10
+                    x
+                }
+                f()
+            "}},
+            r! { 30 }
+        );
+    }
+
+    #[test]
+    fn compiled_arguments_are_lazy() {
+        r_expect! {{"
+            f <- function(a, b) {
+                x <- 1
+                if (a) x == 1 else b
+            }
+            f(TRUE, stop('never evaluated'))
+        "}}
+    }
+
+    #[test]
+    fn compiled_closures_pass_on_ellipsis() {
+        assert_eq!(
+            r! {{"
+                f <- function(x, ...) {
+                    y <- x + 1
+                    list(y, ...)
+                }
+                f(1, 2, 3)
+            "}},
+            r! { list(2, 2, 3) }
+        );
+    }
+
+    #[test]
+    fn compiled_operators_dispatch_on_class() {
+        assert_eq!(
+            r! {{"
+                `+.money` <- function(e1, e2) 'added money'
+                f <- function(a, b) {
+                    z <- a + b
+                    z
+                }
+                f(structure(1, class = 'money'), 2)
+            "}},
+            r! { "added money" }
+        );
+    }
+
+    #[test]
+    fn compiled_errors_are_signaled() {
+        assert_eq!(
+            r! {{"
+                f <- function() {
+                    x <- 1
+                    y <- x + stop('boom')
+                    y
+                }
+                tryCatch(f(), error = function(e) conditionMessage(e))
+            "}},
+            r! { "boom" }
+        );
+    }
+
+    #[test]
+    fn resolved_variables() {
+        assert_eq!(
+            r! {{"
+                outer <- function(a) {
+                    b <- 10
+                    inner <- function(x) {
+                        y <- x + a + b
+                        y
+                    }
+                    inner(1) + inner(2)
+                }
+                outer(5)
+            "}},
+            r! { 33 }
+        );
+
+        // locals that are not yet bound are found in enclosing environments
+        assert_eq!(
+            r! {{"
+                k <- 42
+                f <- function(n) {
+                    if (n > 0) k <- n
+                    k
+                }
+                c(f(0), f(3))
+            "}},
+            r! { c(42, 3) }
+        );
+    }
+
+    #[test]
+    fn compiled_calls_respect_shadowed_builtins() {
+        assert_eq!(
+            r! {{"
+                c <- function(...) 'mine'
+                k <- function() {
+                    x <- 1
+                    c(1, 2)
+                }
+                k()
+            "}},
+            r! { "mine" }
+        );
+    }
+
+    #[test]
+    fn vectors_of_constants_are_built_unless_shadowed() {
+        assert_eq!(
+            r! {{"
+                k <- function() {
+                    x <- 1
+                    c(a = 1, b = 2)
+                }
+                before <- k()
+                c <- function(...) 'mine'
+                list(before, k())
+            "}},
+            r! {{"list(c(a = 1, b = 2), 'mine')"}}
+        );
+    }
+
+    #[test]
+    fn resolved_variables_respect_dynamic_bindings() {
+        assert_eq!(
+            r! {{"
+                x <- 1
+                outer <- function() {
+                    h <- function() eval(quote(x <- 100), parent(environment()))
+                    f <- function() {
+                        y <- 0
+                        x + y
+                    }
+                    a <- f()
+                    h()
+                    b <- f()
+                    c(a, b)
+                }
+                outer()
+            "}},
+            r! { c(1, 100) }
+        );
+    }
+
+    #[test]
+    fn compiled_recursion() {
+        assert_eq!(
+            r! {{"
+                fib <- function(n) {
+                    if (n < 2) n else fib(n - 1) + fib(n - 2)
+                }
+                fib(15)
+            "}},
+            r! { 610 }
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
+use super::{Code, Instr};
+use crate::callable::operators::dispatch_op;
+use crate::context::Context;
+use crate::lang::{CallStack, Cond, EvalResult, Signal};
+use crate::object::{Environment, Obj, Symbol};
+
+/// State that is unwound when a signal propagates through the machine
+enum Block {
+    /// The frame of a primitive call, entered at an instruction. Frames are
+    /// only pushed onto the call stack once evaluation might observe them.
+    Frame {
+        at: usize,
+        pushed: bool,
+        height: usize,
+    },
+    /// A loop body, which intercepts `break` and `next`
+    Loop {
+        height: usize,
+        brk: usize,
+        cont: usize,
+    },
+    /// The progress through the elements of a `for` loop
+    Iter { iter: Obj, index: usize },
+}
+
+struct Machine<'a> {
+    instrs: &'a [Instr],
+    env: Rc<Environment>,
+    values: Vec<Obj>,
+    blocks: Vec<Block>,
+    // the number of frames that have not yet been pushed onto the call stack
+    pending: usize,
+}
+
+impl Code {
+    /// Execute compiled code in the most recent frame of the call stack
+    pub fn run(&self, stack: &mut CallStack) -> EvalResult {
+        let mut vm = Machine {
+            instrs: &self.instrs,
+            env: stack.env(),
+            values: vec![],
+            blocks: vec![],
+            pending: 0,
+        };
+
+        let mut pc = 0;
+        while let Some(instr) = vm.instrs.get(pc) {
+            pc += 1;
+            if let Err(signal) = vm.step(instr, &mut pc, stack) {
+                pc = vm.unwind(signal, stack)?;
+            }
+        }
+
+        Ok(vm.values.pop().unwrap_or_default())
+    }
+}
+
+/// Find the value of a variable resolved to a slot
+///
+/// Returns `None` if the variable needs to be looked up by name, either
+/// because an environment that was skipped over is no longer sealed, the
+/// variable is not bound or its value is a promise that needs to be forced.
+///
+fn find_slot(env: &Environment, name: Symbol, depth: usize, slot: usize) -> Option<Obj> {
+    let mut env = env;
+    for _ in 0..depth {
+        if !env.values.borrow().is_sealed() {
+            return None;
+        }
+        env = env.parent.as_ref()?;
+    }
+
+    match env.values.borrow().get_slot(slot, name)? {
+        Obj::Promise(None, ..) => None,
+        Obj::Promise(Some(value), ..) => Some(value.as_ref().clone()),
+        value => Some(value.clone()),
+    }
+}
+
+impl Machine<'_> {
+    fn push(&mut self, value: Obj) {
+        self.values.push(value);
+    }
+
+    fn pop(&mut self) -> Obj {
+        self.values.pop().unwrap_or_default()
+    }
+
+    /// The value on top of the stack, as it is bound to a name
+    fn top(&mut self) -> EvalResult {
+        let Some(value) = self.values.last_mut() else {
+            unreachable!()
+        };
+
+        // lazy results are computed once they are bound to a name
+        if let Obj::Vector(v) = value {
+            if v.is_lazy() {
+                v.materialize_computed()?;
+            }
+        }
+
+        Ok(value.clone())
+    }
+
+    fn step(&mut self, instr: &Instr, pc: &mut usize, stack: &mut CallStack) -> Result<(), Signal> {
+        match instr {
+            Instr::Const(value) => self.push(value.clone()),
+            Instr::BuiltinConst(name, value, expr) => {
+                if self.env.shadows_builtin(*name) {
+                    self.flush(stack);
+                    let value = stack.eval_and_finalize(expr.clone())?;
+                    self.push(value);
+                } else {
+                    self.push(value.clone());
+                }
+            }
+            Instr::Load(name) => {
+                let value = self.load(*name, stack)?;
+                self.push(value);
+            }
+            Instr::LoadSlot(name, depth, slot) => {
+                let value = match find_slot(&self.env, *name, *depth, *slot) {
+                    Some(value) => value,
+                    None => self.load(*name, stack)?,
+                };
+                self.push(value);
+            }
+            Instr::Store(name) => {
+                let value = self.top()?;
+                self.env.insert(*name, value);
+            }
+            Instr::StoreSlot(name, slot) => {
+                let value = self.top()?;
+                let mut bindings = self.env.values.borrow_mut();
+                if let Err(value) = bindings.set_slot(*slot, *name, value) {
+                    bindings.insert(*name, value);
+                }
+            }
+            Instr::Pop => {
+                self.pop();
+            }
+            Instr::Force => {
+                if let Some(Obj::Promise(value, ..)) = self.values.last() {
+                    if value.is_none() {
+                        self.flush(stack);
+                    }
+                    let value = self.pop().force(stack)?;
+                    self.push(value);
+                }
+            }
+            Instr::Jump(to) => *pc = *to,
+            Instr::JumpIfFalse(to) => {
+                let cond: bool = self.pop().try_into()?;
+                if !cond {
+                    *pc = *to;
+                }
+            }
+            Instr::Frame(..) => {
+                let height = self.values.len();
+                self.blocks
+                    .push(Block::Frame { at: *pc - 1, pushed: false, height });
+                self.pending += 1;
+            }
+            Instr::ExitFrame => {
+                let Some(Block::Frame { pushed, .. }) = self.blocks.pop() else {
+                    unreachable!()
+                };
+
+                if pushed {
+                    let value = self.pop();
+                    let value = stack.pop_frame_and_return(Ok(value))?;
+                    self.push(value);
+                } else {
+                    self.pending -= 1;
+                }
+            }
+            Instr::Binary(op, exprs) => {
+                let rhs = self.pop();
+                let lhs = self.pop();
+                let mut values = vec![lhs, rhs];
+
+                if values.iter().any(Obj::is_object) {
+                    self.flush(stack);
+                    match dispatch_op(op.sym(), exprs.clone(), values, 2, stack)? {
+                        ControlFlow::Break(result) => {
+                            self.push(result);
+                            return Ok(());
+                        }
+                        ControlFlow::Continue(v) => values = v,
+                    }
+                }
+
+                let rhs = values.pop().unwrap_or_default();
+                let lhs = values.pop().unwrap_or_default();
+                let value = op.apply(lhs, rhs)?;
+                self.push(value);
+            }
+            Instr::For => {
+                let iter = self.pop();
+                self.blocks.push(Block::Iter { iter, index: 0 });
+                self.push(Obj::Null);
+            }
+            Instr::Next(var, end) => {
+                let Some(Block::Iter { iter, index }) = self.blocks.last_mut() else {
+                    unreachable!()
+                };
+
+                match Obj::get(iter, *index) {
+                    Some(value) => {
+                        *index += 1;
+                        self.env.insert(*var, value);
+                    }
+                    None => *pc = *end,
+                }
+            }
+            Instr::EndFor => {
+                self.blocks.pop();
+            }
+            Instr::Body(brk, cont) => {
+                let height = self.values.len();
+                self.blocks
+                    .push(Block::Loop { height, brk: *brk, cont: *cont });
+            }
+            Instr::ExitBody => {
+                self.blocks.pop();
+                let value = self.pop();
+                if let Some(result) = self.values.last_mut() {
+                    *result = value;
+                }
+            }
+            Instr::Break => return Err(Signal::Condition(Cond::Break)),
+            Instr::Continue => return Err(Signal::Condition(Cond::Continue)),
+            Instr::Eval(expr) => {
+                self.flush(stack);
+                let value = stack.eval_and_finalize(expr.clone())?;
+                self.push(value);
+            }
+            Instr::Tail(expr) => return Err(Signal::Tail(expr.clone(), true)),
+        }
+
+        Ok(())
+    }
+
+    /// Get the value of a symbol
+    ///
+    /// Values are looked up directly, only deferring to the call stack when
+    /// a promise needs to be forced or the symbol is not found, as either
+    /// may evaluate code or signal an error.
+    fn load(&mut self, name: Symbol, stack: &mut CallStack) -> EvalResult {
+        let mut env = self.env.clone();
+        loop {
+            match env.values.borrow().get(&name) {
+                Some(Obj::Promise(None, ..)) => break,
+                Some(Obj::Promise(Some(value), ..)) => return Ok(value.as_ref().clone()),
+                Some(value) => return Ok(value.clone()),
+                None => (),
+            }
+
+            let Some(parent) = env.parent.clone() else {
+                break;
+            };
+            env = parent;
+        }
+
+        self.flush(stack);
+        stack.get(name)
+    }
+
+    /// Push any pending frames onto the call stack
+    fn flush(&mut self, stack: &mut CallStack) {
+        if self.pending == 0 {
+            return;
+        }
+
+        for block in self.blocks.iter_mut() {
+            if let Block::Frame { at, pushed: pushed @ false, .. } = block {
+                let Instr::Frame(call, _) = &self.instrs[*at] else {
+                    unreachable!()
+                };
+
+                stack.add_frame(call.clone(), self.env.clone());
+                *pushed = true;
+            }
+        }
+
+        self.pending = 0;
+    }
+
+    /// Propagate a signal through frames and loops
+    ///
+    /// Returns the instruction at which to continue if the signal is handled
+    /// by a loop or a frame, or the signal if it propagates out of the code.
+    fn unwind(&mut self, mut signal: Signal, stack: &mut CallStack) -> Result<usize, Signal> {
+        // frames are exited on the call stack to give handlers a chance to act
+        self.flush(stack);
+
+        while let Some(block) = self.blocks.pop() {
+            match block {
+                Block::Frame { at, height, .. } => match stack.pop_frame_and_return(Err(signal)) {
+                    Err(s) => signal = s,
+                    Ok(value) => {
+                        let Instr::Frame(_, exit) = self.instrs[at] else {
+                            unreachable!()
+                        };
+
+                        self.values.truncate(height);
+                        self.push(value);
+                        return Ok(exit + 1);
+                    }
+                },
+                Block::Loop { height, brk, cont } => match signal {
+                    Signal::Condition(Cond::Break) => {
+                        self.values.truncate(height);
+                        return Ok(brk);
+                    }
+                    Signal::Condition(Cond::Continue) => {
+                        self.values.truncate(height);
+                        return Ok(cont);
+                    }
+                    _ => (),
+                },
+                Block::Iter { .. } => (),
+            }
+        }
+
+        Err(signal)
+    }
+}
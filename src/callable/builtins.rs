@@ -22,6 +22,7 @@ pub static BUILTIN: LazyLock<HashMap<&'static str, Box<dyn Builtin>>> = LazyLock
         ("/", Box::new(InfixDiv) as Box<dyn Builtin>),
         ("^", Box::new(InfixPow) as Box<dyn Builtin>),
         ("%", Box::new(InfixMod) as Box<dyn Builtin>),
+        ("%*%", Box::new(InfixMatMul) as Box<dyn Builtin>),
         ("||", Box::new(InfixOr) as Box<dyn Builtin>),
         ("&&", Box::new(InfixAnd) as Box<dyn Builtin>),
         ("|", Box::new(InfixVectorOr) as Box<dyn Builtin>),
@@ -35,27 +36,90 @@ pub static BUILTIN: LazyLock<HashMap<&'static str, Box<dyn Builtin>>> = LazyLock
         ("|>", Box::new(InfixPipe) as Box<dyn Builtin>),
         (":", Box::new(InfixColon) as Box<dyn Builtin>),
         ("$", Box::new(InfixDollar) as Box<dyn Builtin>),
+        ("::", Box::new(InfixDoubleColon) as Box<dyn Builtin>),
+        (":::", Box::new(InfixTripleColon) as Box<dyn Builtin>),
         ("..", Box::new(PostfixPack) as Box<dyn Builtin>),
         ("[[", Box::new(PostfixIndex) as Box<dyn Builtin>),
         ("[", Box::new(PostfixVecIndex) as Box<dyn Builtin>),
+        ("attr", Box::new(PrimitiveAttr) as Box<dyn Builtin>),
+        ("attr<-", Box::new(PrimitiveAttrAssign) as Box<dyn Builtin>),
+        ("attributes", Box::new(PrimitiveAttributes) as Box<dyn Builtin>),
         ("c", Box::new(PrimitiveC) as Box<dyn Builtin>),
         ("callstack", Box::new(PrimitiveCallstack) as Box<dyn Builtin>),
+        ("class", Box::new(PrimitiveClass) as Box<dyn Builtin>),
+        ("class<-", Box::new(PrimitiveClassAssign) as Box<dyn Builtin>),
+        ("unclass", Box::new(PrimitiveUnclass) as Box<dyn Builtin>),
+        ("inherits", Box::new(PrimitiveInherits) as Box<dyn Builtin>),
+        ("as.integer", Box::new(PrimitiveAsInteger) as Box<dyn Builtin>),
+        ("as.double", Box::new(PrimitiveAsDouble) as Box<dyn Builtin>),
+        ("as.logical", Box::new(PrimitiveAsLogical) as Box<dyn Builtin>),
+        ("as.complex", Box::new(PrimitiveAsComplex) as Box<dyn Builtin>),
+        ("as.character", Box::new(PrimitiveAsCharacter) as Box<dyn Builtin>),
+        ("Re", Box::new(PrimitiveRe) as Box<dyn Builtin>),
+        ("Im", Box::new(PrimitiveIm) as Box<dyn Builtin>),
+        ("Mod", Box::new(PrimitiveMod) as Box<dyn Builtin>),
+        ("Arg", Box::new(PrimitiveArg) as Box<dyn Builtin>),
+        ("Conj", Box::new(PrimitiveConj) as Box<dyn Builtin>),
+        ("sqrt", Box::new(PrimitiveSqrt) as Box<dyn Builtin>),
+        ("conditionMessage", Box::new(PrimitiveConditionMessage) as Box<dyn Builtin>),
+        ("conditionCall", Box::new(PrimitiveConditionCall) as Box<dyn Builtin>),
+        ("data.frame", Box::new(PrimitiveDataFrame) as Box<dyn Builtin>),
+        ("head", Box::new(PrimitiveHead) as Box<dyn Builtin>),
+        ("tail", Box::new(PrimitiveTail) as Box<dyn Builtin>),
+        ("dim", Box::new(PrimitiveDim) as Box<dyn Builtin>),
+        ("dim<-", Box::new(PrimitiveDimAssign) as Box<dyn Builtin>),
+        ("nrow", Box::new(PrimitiveNrow) as Box<dyn Builtin>),
+        ("ncol", Box::new(PrimitiveNcol) as Box<dyn Builtin>),
         ("environment", Box::new(PrimitiveEnvironment) as Box<dyn Builtin>),
         ("eval", Box::new(PrimitiveEval) as Box<dyn Builtin>),
+        ("factor", Box::new(PrimitiveFactor) as Box<dyn Builtin>),
+        ("levels", Box::new(PrimitiveLevels) as Box<dyn Builtin>),
+        ("nlevels", Box::new(PrimitiveNlevels) as Box<dyn Builtin>),
+        ("droplevels", Box::new(PrimitiveDroplevels) as Box<dyn Builtin>),
+        ("format", Box::new(PrimitiveFormat) as Box<dyn Builtin>),
+        ("invokeRestart", Box::new(PrimitiveInvokeRestart) as Box<dyn Builtin>),
         ("is_null", Box::new(PrimitiveIsNull) as Box<dyn Builtin>),
         ("length", Box::new(PrimitiveLength) as Box<dyn Builtin>),
         ("list", Box::new(PrimitiveList) as Box<dyn Builtin>),
+        ("matrix", Box::new(PrimitiveMatrix) as Box<dyn Builtin>),
+        ("array", Box::new(PrimitiveArray) as Box<dyn Builtin>),
+        ("t", Box::new(PrimitiveT) as Box<dyn Builtin>),
+        ("cbind", Box::new(PrimitiveCbind) as Box<dyn Builtin>),
+        ("rbind", Box::new(PrimitiveRbind) as Box<dyn Builtin>),
+        ("gc", Box::new(PrimitiveGc) as Box<dyn Builtin>),
+        ("object.size", Box::new(PrimitiveObjectSize) as Box<dyn Builtin>),
+        ("message", Box::new(PrimitiveMessage) as Box<dyn Builtin>),
         ("names", Box::new(PrimitiveNames) as Box<dyn Builtin>),
+        ("on.exit", Box::new(PrimitiveOnExit) as Box<dyn Builtin>),
         ("parent", Box::new(PrimitiveParent) as Box<dyn Builtin>),
         ("paste", Box::new(PrimitivePaste) as Box<dyn Builtin>),
         ("print", Box::new(PrimitivePrint) as Box<dyn Builtin>),
         ("q", Box::new(PrimitiveQ) as Box<dyn Builtin>),
         ("quote", Box::new(PrimitiveQuote) as Box<dyn Builtin>),
+        ("as.raw", Box::new(PrimitiveAsRaw) as Box<dyn Builtin>),
+        ("rawToChar", Box::new(PrimitiveRawToChar) as Box<dyn Builtin>),
+        ("charToRaw", Box::new(PrimitiveCharToRaw) as Box<dyn Builtin>),
+        ("xor", Box::new(PrimitiveXor) as Box<dyn Builtin>),
+        ("rawShift", Box::new(PrimitiveRawShift) as Box<dyn Builtin>),
+        ("readBin", Box::new(PrimitiveReadBin) as Box<dyn Builtin>),
+        ("writeBin", Box::new(PrimitiveWriteBin) as Box<dyn Builtin>),
         ("rnorm", Box::new(PrimitiveRnorm) as Box<dyn Builtin>),
         ("runif", Box::new(PrimitiveRunif) as Box<dyn Builtin>),
+        ("signalCondition", Box::new(PrimitiveSignalCondition) as Box<dyn Builtin>),
+        ("stop", Box::new(PrimitiveStop) as Box<dyn Builtin>),
+        ("structure", Box::new(PrimitiveStructure) as Box<dyn Builtin>),
         ("substitute", Box::new(PrimitiveSubstitute) as Box<dyn Builtin>),
         ("sum", Box::new(PrimitiveSum) as Box<dyn Builtin>),
+        ("table", Box::new(PrimitiveTable) as Box<dyn Builtin>),
+        ("tracemem", Box::new(PrimitiveTracemem) as Box<dyn Builtin>),
+        ("untracemem", Box::new(PrimitiveUntracemem) as Box<dyn Builtin>),
+        ("tryCatch", Box::new(PrimitiveTryCatch) as Box<dyn Builtin>),
         ("typeof", Box::new(PrimitiveTypeOf) as Box<dyn Builtin>),
+        ("UseMethod", Box::new(PrimitiveUseMethod) as Box<dyn Builtin>),
+        ("NextMethod", Box::new(PrimitiveNextMethod) as Box<dyn Builtin>),
+        ("warning", Box::new(PrimitiveWarning) as Box<dyn Builtin>),
+        ("withCallingHandlers", Box::new(PrimitiveWithCallingHandlers) as Box<dyn Builtin>),
+        ("withRestarts", Box::new(PrimitiveWithRestarts) as Box<dyn Builtin>),
         // builtins end
     ])
 });
@@ -1,10 +1,13 @@
 extern crate r_derive;
 
+use std::ops::ControlFlow;
+use std::rc::Rc;
+
 use crate::callable::dyncompare::*;
 use crate::cli::Experiment;
 use crate::context::Context;
 use crate::object::types::{Character, Integer};
-use crate::object::{Expr, ExprList, List, Obj, Subset};
+use crate::object::{Expr, ExprList, List, Obj, Subset, Symbol};
 use crate::{internal_err, lang::*};
 
 impl std::fmt::Debug for Box<dyn Callable> {
@@ -75,7 +78,7 @@ pub trait Callable: CallableFormals {
                 Character::NA => {
                     let next_unassigned_formal = formals.remove(0);
                     if let Some((Some(param), _)) = next_unassigned_formal {
-                        matched_args.push_named(Character::Some(param), value);
+                        matched_args.push_named(Character::Some(param.into()), value);
                     } else {
                         ellipsis.push_named(Character::NA, value);
                     }
@@ -87,7 +90,7 @@ pub trait Callable: CallableFormals {
 
         for (k, v) in ellipsis.iter_pairs() {
             if let Obj::Promise(_, e, _) = v {
-                ellipsis_expr.push_named(k.as_option(), e)
+                ellipsis_expr.push_named(k.as_option().map(Symbol::from), e)
             } else {
                 // all arguments must be boxed in promises to allow for NSE
                 unreachable!()
@@ -103,7 +106,7 @@ pub trait Callable: CallableFormals {
 
         let ellipsis_promise = Obj::Promise(
             None,
-            Expr::Call(Box::new(Expr::Primitive(list)), ellipsis_expr),
+            Expr::Call(Rc::new(Expr::Primitive(list)), Rc::new(ellipsis_expr)),
             stack.last_frame().env().clone(),
         );
 
@@ -116,12 +119,9 @@ pub trait Callable: CallableFormals {
         }
 
         if let Some(Expr::Ellipsis(Some(name))) = remainder.get(0) {
-            matched_args.push_named(Character::Some(name), ellipsis_promise);
+            matched_args.push_named(Character::Some(name.into()), ellipsis_promise);
         } else if !remainder.is_empty() {
-            matched_args.push_named(
-                Character::Some("...".to_string()),
-                Obj::List(ellipsis.clone()),
-            );
+            matched_args.push_named(Character::Some("...".into()), Obj::List(ellipsis.clone()));
         }
 
         Ok((matched_args, ellipsis))
@@ -253,6 +253,9 @@ where
         let sym = Self::SYM;
         match Self::KIND {
             Function => format!("{sym}({})", args),
+            Infix if matches!(sym, "::" | ":::") => {
+                format!("{}{sym}{}", args.values[0], args.values[1])
+            }
             Infix => format!("{} {sym} {}", args.values[0], args.values[1]),
             Prefix => format!("{sym}{}", args.values[0]),
             Postfix => format!("{}{sym}", args.values[0]),
@@ -296,6 +299,41 @@ pub fn force_promises(vals: List, stack: &mut CallStack) -> Result<Vec<(Characte
         .collect()
 }
 
+/// Dispatch a builtin generic on its first argument
+///
+/// The first argument is forced and, if its class has a method for the
+/// generic, the method is called with the builtin's arguments and its result
+/// is returned as a `Break`. Otherwise the forced value is returned as a
+/// `Continue` for the builtin to use.
+pub fn dispatch_first(
+    generic: &str,
+    mut x: Obj,
+    ellipsis: &List,
+    stack: &mut CallStack,
+) -> Result<ControlFlow<Obj, Obj>, Signal> {
+    let value = force_in_place(&mut x, stack)?;
+
+    let args: Vec<(Character, Obj)> = std::iter::once((Character::NA, x))
+        .chain(ellipsis.iter_pairs())
+        .collect();
+
+    match stack.dispatch_builtin(generic, &value, List::from(args))? {
+        Some(result) => Ok(ControlFlow::Break(result)),
+        None => Ok(ControlFlow::Continue(value)),
+    }
+}
+
+/// Force a promise, retaining its value so it is not evaluated again
+///
+/// Used when forced arguments are passed on to methods by dispatch.
+pub fn force_in_place(arg: &mut Obj, stack: &mut CallStack) -> EvalResult {
+    let value = arg.clone().force(stack)?;
+    if let Obj::Promise(None, expr, env) = arg.clone() {
+        *arg = Obj::Promise(Some(Box::new(value.clone())), expr, env);
+    }
+    Ok(value)
+}
+
 impl Format for String {
     fn rfmt_call_with(&self, _state: FormatState, args: &ExprList) -> String {
         format!("{}({})", self, args)
@@ -304,7 +342,7 @@ impl Format for String {
 
 impl Callable for String {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        (stack.last_frame().env().clone().get(self.clone())?).call(args, stack)
+        (stack.last_frame().env().clone().get(Symbol::from(self))?).call(args, stack)
     }
 }
 
@@ -313,7 +351,7 @@ impl Format for Obj {}
 impl CallableFormals for Obj {
     fn formals(&self) -> ExprList {
         match self {
-            Obj::Function(formals, _, _) => formals.clone(),
+            Obj::Function(formals, ..) => formals.clone(),
             _ => ExprList::new(),
         }
     }
@@ -321,7 +359,7 @@ impl CallableFormals for Obj {
 
 impl Callable for Obj {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let Obj::Function(_, body, _) = self else {
+        let Obj::Function(_, body, ..) = self else {
             return internal_err!();
         };
 
@@ -336,16 +374,26 @@ impl Callable for Obj {
     }
 
     fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
-        let Obj::Function(_, body, _) = self else {
+        let Obj::Function(formals, body, env, _, compiled) = self else {
             return internal_err!();
         };
 
+        // closure bodies are compiled on their first call
+        let code = compiled.code(formals, body, env);
+        if let Some(layout) = code.as_ref().and_then(|code| code.layout.as_ref()) {
+            stack.env().reserve(layout);
+        }
+
         if !stack.session.experiments.contains(&Experiment::RestArgs) {
-            stack.env().insert("...".to_string(), Obj::List(ellipsis));
+            stack.env().insert(Symbol::new("..."), Obj::List(ellipsis));
         }
 
         stack.env().append(args);
-        stack.eval(body.clone())
+
+        match code {
+            Some(code) => code.run(stack),
+            None => stack.eval(body.clone()),
+        }
     }
 }
 
@@ -5,7 +5,9 @@ use crate::context::Context;
 use crate::internal_err;
 use crate::lang::Signal::*;
 use crate::lang::*;
-use crate::object::{ExprList, Obj};
+use crate::object::types::Character;
+use crate::object::{Expr, ExprList, FormatSpec, List, Obj};
+use crate::parser::escape;
 
 #[derive(Debug, Clone, PartialEq)]
 #[builtin]
@@ -108,7 +110,7 @@ impl Callable for KeywordFor {
         while let Some(value) = iter.get(index) {
             index += 1;
 
-            stack.last_frame().env().insert(var.clone(), value);
+            stack.last_frame().env().insert(var, value);
             eval_result = stack.eval_and_finalize(body.clone());
 
             use Cond::*;
@@ -116,8 +118,7 @@ impl Callable for KeywordFor {
             match eval_result {
                 Err(Condition(Break)) => break,
                 Err(Condition(Continue)) => continue,
-                Err(Return(..)) => return eval_result,
-                Err(Error(_)) => return eval_result,
+                Err(_) => return eval_result,
                 _ => (),
             }
 
@@ -319,10 +320,120 @@ impl Callable for KeywordList {
     }
 }
 
+/// Interpolated Strings
+///
+/// Parsed from `f"value is {x + 1:.2f}"`. Text between fields is passed as
+/// string literals, while the code of each field is passed as an argument
+/// named by its format spec, if it has one. Each argument is evaluated and
+/// formatted, and the results are recycled and concatenated elementwise.
+/// Objects with a class are first formatted by their `format` method, if
+/// they have one, while factors are formatted by their labels.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[builtin]
+pub struct KeywordFString;
+
+impl Format for KeywordFString {
+    fn rfmt_call_with(&self, _state: FormatState, args: &ExprList) -> String {
+        let parts: String = args
+            .keys
+            .iter()
+            .zip(args.values.iter())
+            .map(|(spec, value)| match (spec, value) {
+                (None, Expr::String(s)) => escape(s).replace('{', "{{").replace('}', "}}"),
+                // code that would otherwise be read as having a spec, like `1:3`
+                (None, value) if FormatSpec::split(&value.to_string()).1.is_some() => {
+                    format!("{{({value})}}")
+                }
+                (None, value) => format!("{{{value}}}"),
+                (Some(spec), value) => format!("{{{value}:{spec}}}"),
+            })
+            .collect();
+
+        format!("f\"{parts}\"")
+    }
+
+    fn rfmt_with(&self, _: FormatState) -> String {
+        "f\"\"".to_string()
+    }
+}
+
+impl CallableFormals for KeywordFString {}
+
+impl Callable for KeywordFString {
+    fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
+        let mut parts = Vec::with_capacity(args.len());
+        for (spec, expr) in args {
+            let spec = match spec {
+                Some(spec) => FormatSpec::parse(&spec)?,
+                None => FormatSpec::default(),
+            };
+
+            let value = stack.eval_and_finalize(expr)?;
+            let args = List::from(vec![(Character::NA, value.clone())]);
+            let value = match stack.dispatch_builtin("format", &value, args)? {
+                Some(formatted) => formatted,
+                None => value,
+            };
+
+            parts.push(match value {
+                Obj::Vector(v) if v.factor_levels().is_some() => spec.format(&v.as_character())?,
+                Obj::Vector(v) => spec.format(&v)?,
+                Obj::Null => vec![],
+                obj => vec![spec.format_str(&format!("{obj}"))?],
+            });
+        }
+
+        // as with vectorized operations, a zero-length part produces no strings
+        let n = match parts.iter().any(|part| part.is_empty()) {
+            true => 0,
+            false => parts.iter().map(|part| part.len()).max().unwrap_or(1),
+        };
+
+        let strings: Vec<String> = (0..n)
+            .map(|i| {
+                parts
+                    .iter()
+                    .map(|part| part[i % part.len()].as_str())
+                    .collect()
+            })
+            .collect();
+
+        Ok(Obj::Vector(strings.into()))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::r;
 
+    #[test]
+    fn fstrings_format_classed_objects() {
+        assert_eq!(r! {{r#"f"{factor(['b', 'a']):>2}""#}}, r! { [" b", " a"] });
+        assert_eq!(
+            r! {{r#"
+                format.money <- function(x, ...) f"${unclass(x):.2f}"
+                f"costs {structure(5, class = 'money')}"
+            "#}},
+            r! { "costs $5.00" }
+        );
+    }
+
+    #[test]
+    fn for_over_unbounded_sequence() {
+        assert_eq!(
+            r! {{"
+               sum <- 0
+               for (i in 1:Inf) {
+                   if (i > 100) break
+                   sum <- sum + i
+               }
+               sum
+            "}},
+            r! { 5050 }
+        );
+    }
+
     #[test]
     fn repeat_with_break() {
         assert_eq!(
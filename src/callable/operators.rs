@@ -1,12 +1,156 @@
 use r_derive::*;
+use std::ops::ControlFlow;
+use std::rc::Rc;
 
 use super::core::*;
+use super::primitive::warn;
 use crate::context::Context;
 use crate::error::Error;
-use crate::lang::{CallStack, EvalResult};
+use crate::internal_err;
+use crate::lang::{CallStack, EvalResult, Signal};
+use crate::object::coercion::factor_codes;
+use crate::object::rep::Rep;
 use crate::object::types::*;
 use crate::object::*;
 
+/// Dispatch an operator on the class of its operands
+///
+/// The first of the leading `n` operands whose class has a method for the
+/// operator determines the method to call, which receives all operands.
+/// Omitted operands are passed on as missing arguments. If no method
+/// applies, the evaluated operands are returned.
+pub(crate) fn dispatch_op(
+    op: &str,
+    exprs: ExprList,
+    values: Vec<Obj>,
+    n: usize,
+    stack: &mut CallStack,
+) -> Result<ControlFlow<Obj, Vec<Obj>>, Signal> {
+    if !values.iter().take(n).any(Obj::is_object) {
+        return Ok(ControlFlow::Continue(values));
+    }
+
+    let env = stack.env();
+    let args: Vec<(Character, Obj)> = exprs
+        .into_iter()
+        .zip(values.iter())
+        .map(|((key, expr), value)| {
+            let promise = match expr {
+                Expr::Missing => Obj::Promise(None, expr, env.clone()),
+                _ => Obj::Promise(Some(Box::new(value.clone())), expr, env.clone()),
+            };
+            (Character::from(key), promise)
+        })
+        .collect();
+
+    for x in values.iter().take(n) {
+        if let Some(result) = stack.dispatch_builtin(op, x, List::from(args.clone()))? {
+            return Ok(ControlFlow::Break(result));
+        }
+    }
+
+    Ok(ControlFlow::Continue(values))
+}
+
+fn dispatch_binary(
+    op: &str,
+    args: ExprList,
+    stack: &mut CallStack,
+) -> Result<ControlFlow<Obj, (Obj, Obj)>, Signal> {
+    let (lhs, rhs) = args.unnamed_binary_args();
+    let values = stack.eval_binary((lhs.clone(), rhs.clone()))?;
+    let values = vec![values.0, values.1];
+
+    Ok(
+        match dispatch_op(op, vec![lhs, rhs].into(), values, 2, stack)? {
+            ControlFlow::Continue(mut v) => {
+                ControlFlow::Continue((v.swap_remove(0), v.swap_remove(0)))
+            }
+            ControlFlow::Break(result) => ControlFlow::Break(result),
+        },
+    )
+}
+
+fn dispatch_index(
+    op: &str,
+    args: ExprList,
+    stack: &mut CallStack,
+) -> Result<ControlFlow<Obj, (Obj, Obj)>, Signal> {
+    let (what, index) = args.unnamed_binary_args();
+    let values = stack.eval_binary((what.clone(), index.clone()))?;
+    let values = vec![values.0, values.1];
+
+    Ok(
+        match dispatch_op(op, vec![what, index].into(), values, 1, stack)? {
+            ControlFlow::Continue(mut v) => {
+                ControlFlow::Continue((v.swap_remove(0), v.swap_remove(0)))
+            }
+            ControlFlow::Break(result) => ControlFlow::Break(result),
+        },
+    )
+}
+
+/// An object, its index per dimension and whether to drop dimensions
+type DimsIndex = (Obj, Vec<Option<Obj>>, bool);
+
+/// Dispatch `[` when indexing by more than one dimension
+///
+/// Omitted indices, as in `x[, 1]`, are `None`. A `drop` argument is
+/// separated from the indices, defaulting to `true`.
+fn dispatch_dims_index(
+    op: &str,
+    args: ExprList,
+    stack: &mut CallStack,
+) -> Result<ControlFlow<Obj, DimsIndex>, Signal> {
+    let values = args
+        .values
+        .iter()
+        .map(|expr| match expr {
+            Expr::Missing => Ok(Obj::Null),
+            expr => stack.eval(expr.clone()),
+        })
+        .collect::<Result<Vec<Obj>, Signal>>()?;
+
+    let args: Vec<(Option<Symbol>, Expr)> = args.into_iter().collect();
+    let exprs = args.iter().cloned().collect();
+    let mut values = match dispatch_op(op, exprs, values, 1, stack)? {
+        ControlFlow::Break(result) => return Ok(ControlFlow::Break(result)),
+        ControlFlow::Continue(values) => values.into_iter(),
+    };
+
+    let Some(what) = values.next() else {
+        unreachable!();
+    };
+
+    let mut indices = vec![];
+    let mut drop = true;
+    for ((key, expr), value) in args.into_iter().skip(1).zip(values) {
+        match (key.as_deref(), expr) {
+            (Some("drop"), _) => drop = value.try_into()?,
+            (_, Expr::Missing) => indices.push(None),
+            _ => indices.push(Some(value)),
+        }
+    }
+
+    Ok(ControlFlow::Continue((what, indices, drop)))
+}
+
+fn dispatch_unary(
+    op: &str,
+    args: ExprList,
+    stack: &mut CallStack,
+) -> Result<ControlFlow<Obj, Obj>, Signal> {
+    let what = args.unnamed_unary_arg();
+    let value = stack.eval(what.clone())?;
+
+    Ok(
+        match dispatch_op(op, vec![what].into(), vec![value], 1, stack)? {
+            ControlFlow::Continue(mut v) => ControlFlow::Continue(v.swap_remove(0)),
+            ControlFlow::Break(result) => ControlFlow::Break(result),
+        },
+    )
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[builtin(sym = "<-", kind = Infix)]
 pub struct InfixAssign;
@@ -24,7 +168,10 @@ pub struct InfixAdd;
 impl CallableFormals for InfixAdd {}
 impl Callable for InfixAdd {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs + rhs
     }
 }
@@ -35,7 +182,10 @@ pub struct InfixSub;
 impl CallableFormals for InfixSub {}
 impl Callable for InfixSub {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs - rhs
     }
 }
@@ -46,7 +196,10 @@ pub struct PrefixSub;
 impl CallableFormals for PrefixSub {}
 impl Callable for PrefixSub {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let what = stack.eval(args.unnamed_unary_arg())?;
+        let what = match dispatch_unary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(what) => what,
+        };
         -what
     }
 }
@@ -57,7 +210,10 @@ pub struct PrefixNot;
 impl CallableFormals for PrefixNot {}
 impl Callable for PrefixNot {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let what = stack.eval(args.unnamed_unary_arg())?;
+        let what = match dispatch_unary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(what) => what,
+        };
         !what
     }
 }
@@ -78,7 +234,10 @@ pub struct InfixMul;
 impl CallableFormals for InfixMul {}
 impl Callable for InfixMul {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs * rhs
     }
 }
@@ -89,7 +248,10 @@ pub struct InfixDiv;
 impl CallableFormals for InfixDiv {}
 impl Callable for InfixDiv {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs / rhs
     }
 }
@@ -100,7 +262,10 @@ pub struct InfixPow;
 impl CallableFormals for InfixPow {}
 impl Callable for InfixPow {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs.power(rhs)
     }
 }
@@ -111,11 +276,28 @@ pub struct InfixMod;
 impl CallableFormals for InfixMod {}
 impl Callable for InfixMod {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs % rhs
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[builtin(sym = "%*%", kind = Infix)]
+pub struct InfixMatMul;
+impl CallableFormals for InfixMatMul {}
+impl Callable for InfixMatMul {
+    fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
+        lhs.matmul(&rhs)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[builtin(sym = "||", kind = Infix)]
 pub struct InfixOr;
@@ -162,7 +344,10 @@ pub struct InfixVectorOr;
 impl CallableFormals for InfixVectorOr {}
 impl Callable for InfixVectorOr {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs | rhs
     }
 }
@@ -173,7 +358,10 @@ pub struct InfixVectorAnd;
 impl CallableFormals for InfixVectorAnd {}
 impl Callable for InfixVectorAnd {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs & rhs
     }
 }
@@ -184,7 +372,10 @@ pub struct InfixGreater;
 impl CallableFormals for InfixGreater {}
 impl Callable for InfixGreater {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs.vec_gt(rhs)
     }
 }
@@ -195,7 +386,10 @@ pub struct InfixGreaterEqual;
 impl CallableFormals for InfixGreaterEqual {}
 impl Callable for InfixGreaterEqual {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs.vec_gte(rhs)
     }
 }
@@ -206,7 +400,10 @@ pub struct InfixLess;
 impl CallableFormals for InfixLess {}
 impl Callable for InfixLess {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs.vec_lt(rhs)
     }
 }
@@ -217,7 +414,10 @@ pub struct InfixLessEqual;
 impl CallableFormals for InfixLessEqual {}
 impl Callable for InfixLessEqual {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs.vec_lte(rhs)
     }
 }
@@ -228,7 +428,10 @@ pub struct InfixEqual;
 impl CallableFormals for InfixEqual {}
 impl Callable for InfixEqual {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs.vec_eq(rhs)
     }
 }
@@ -239,7 +442,10 @@ pub struct InfixNotEqual;
 impl CallableFormals for InfixNotEqual {}
 impl Callable for InfixNotEqual {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (lhs, rhs) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (lhs, rhs) = match dispatch_binary(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         lhs.vec_neq(rhs)
     }
 }
@@ -255,14 +461,15 @@ impl Callable for InfixPipe {
 
         use Expr::*;
         match rhs {
-            Call(what, mut args) => {
+            Call(what, args) => {
+                let mut args = Rc::unwrap_or_clone(args);
                 args.insert(0, lhs);
-                let new_expr = Call(what, args);
+                let new_expr = Call(what, Rc::new(args));
                 stack.eval(new_expr)
             }
             s @ Symbol(..) | s @ String(..) => {
                 let args = ExprList::from(vec![(None, lhs)]);
-                let new_expr = Call(Box::new(s), args);
+                let new_expr = Call(Rc::new(s), Rc::new(args));
                 stack.eval(new_expr)
             }
             _ => unreachable!(),
@@ -281,10 +488,10 @@ impl Callable for InfixColon {
         let arg2 = argstream.next().map(|(_, v)| v).unwrap_or(Expr::Null);
 
         fn colon_args(arg: &Expr) -> Option<(Expr, Expr)> {
-            if let Expr::Call(what, largs) = arg.clone() {
-                if let Expr::Primitive(p) = *what {
-                    if p == (Box::new(InfixColon) as Box<dyn Builtin>) {
-                        return Some(largs.clone().unnamed_binary_args());
+            if let Expr::Call(what, largs) = arg {
+                if let Expr::Primitive(p) = &**what {
+                    if *p == (Box::new(InfixColon) as Box<dyn Builtin>) {
+                        return Some((**largs).clone().unnamed_binary_args());
                     }
                 }
             }
@@ -315,30 +522,32 @@ impl Callable for InfixColon {
                 return Ok(Obj::Vector(Vector::from(Vec::<Double>::new())));
             }
 
-            let mut v = start;
-            return Ok(Obj::Vector(Vector::from(
-                vec![start]
-                    .into_iter()
-                    .chain(std::iter::repeat_with(|| {
-                        v += by;
-                        v
-                    }))
-                    .take_while(|x| if start <= end { x <= &end } else { x >= &end })
-                    .collect::<Vec<f64>>(),
-            )));
+            // ranges towards infinity never end, and can only be consumed lazily
+            let len = match range / by {
+                n if n.is_infinite() => None,
+                n => Some((n + 1e-10).floor() as usize + 1),
+            };
+
+            let seq = Sequence::new(start, by, len);
+            Ok(Obj::Vector(Vector::from(Rep::from(seq))))
 
         // binary case
         } else {
             let start: i32 = stack.eval(arg1)?.as_integer()?.try_into()?;
-            let end: i32 = stack.eval(arg2)?.as_integer()?.try_into()?;
-            if start > end {
+            let end: f64 = stack.eval(arg2)?.try_into()?;
+            let start = start as f64;
+
+            if start > end || end.is_nan() {
                 return Error::InvalidRange.into();
             }
-            return Ok(Obj::Vector(Vector::from(if start <= end {
-                (start..=end).map(|i| i as f64).collect::<Vec<f64>>()
-            } else {
-                (end..=start).map(|i| i as f64).rev().collect::<Vec<f64>>()
-            })));
+
+            let len = match end {
+                f64::INFINITY => None,
+                end => Some((end.trunc() - start) as usize + 1),
+            };
+
+            let seq = Sequence::new(start, 1.0, len);
+            Ok(Obj::Vector(Vector::from(Rep::from(seq))))
         }
     }
 }
@@ -359,11 +568,15 @@ impl Callable for InfixDollar {
             unreachable!();
         };
 
-        let mut what = stack.eval(what)?;
+        let Some(name) = index.as_name().map(String::from) else {
+            return Ok(Obj::Null);
+        };
 
-        match index {
-            Expr::String(s) | Expr::Symbol(s) => what.try_get_named(&s),
-            _ => Ok(Obj::Null),
+        let exprs = vec![what.clone(), Expr::String(name.clone())];
+        let values = vec![stack.eval(what)?, Obj::Vector(vec![name.clone()].into())];
+        match dispatch_op(Self::SYM, exprs.into(), values, 1, stack)? {
+            ControlFlow::Break(result) => Ok(result),
+            ControlFlow::Continue(mut values) => values.swap_remove(0).try_get_named(&name),
         }
     }
 
@@ -380,8 +593,8 @@ impl Callable for InfixDollar {
 
         let mut what = stack.eval_mut(what)?;
 
-        match index {
-            Expr::String(s) | Expr::Symbol(s) => what.try_get_named_mut(s.as_str()),
+        match index.as_name() {
+            Some(s) => what.try_get_named_mut(s),
             _ => Ok(Obj::Null),
         }
     }
@@ -398,15 +611,62 @@ impl Callable for InfixDollar {
         };
 
         let value = stack.eval(value)?;
+        let target = what.clone();
         let mut what = stack.eval_mut(what)?;
 
-        match name {
-            Expr::String(s) | Expr::Symbol(s) => what.try_set_named(&s, value),
+        match name.as_name() {
+            // data frame columns are replaced as a whole, checking their length
+            Some(s) if what.is_data_frame() => {
+                let frame = what.try_set_column(s, value.clone())?;
+                stack.assign_target(target, frame)?;
+                Ok(value)
+            }
+            Some(s) => what.try_set_named(s, value),
             _ => unimplemented!(),
         }
     }
 }
 
+/// The namespace and binding names of `pkg::name` and `pkg:::name`
+fn namespaced_names(args: ExprList) -> Result<(Symbol, Symbol), Signal> {
+    let mut argstream = args.into_iter();
+
+    let Some((_, namespace)) = argstream.next() else {
+        unreachable!();
+    };
+
+    let Some((_, name)) = argstream.next() else {
+        unreachable!();
+    };
+
+    match (namespace.as_name(), name.as_name()) {
+        (Some(namespace), Some(name)) => Ok((Symbol::new(namespace), Symbol::new(name))),
+        _ => internal_err!(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[builtin(sym = "::", kind = Infix)]
+pub struct InfixDoubleColon;
+impl CallableFormals for InfixDoubleColon {}
+impl Callable for InfixDoubleColon {
+    fn call(&self, args: ExprList, _stack: &mut CallStack) -> EvalResult {
+        let (namespace, name) = namespaced_names(args)?;
+        Namespace::find(namespace)?.get_exported(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[builtin(sym = ":::", kind = Infix)]
+pub struct InfixTripleColon;
+impl CallableFormals for InfixTripleColon {}
+impl Callable for InfixTripleColon {
+    fn call(&self, args: ExprList, _stack: &mut CallStack) -> EvalResult {
+        let (namespace, name) = namespaced_names(args)?;
+        Namespace::find(namespace)?.get_internal(name)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[builtin(sym = "..", kind = Postfix)]
 pub struct PostfixPack;
@@ -423,7 +683,10 @@ pub struct PostfixIndex;
 impl CallableFormals for PostfixIndex {}
 impl Callable for PostfixIndex {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (what, index) = stack.eval_binary(args.unnamed_binary_args())?;
+        let (what, index) = match dispatch_index(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
         what.try_get_inner(index)
     }
 
@@ -446,9 +709,17 @@ impl Callable for PostfixIndex {
         };
 
         let value = stack.eval(value)?;
+        let target = what.clone();
         let what = stack.eval_mut(what)?;
         let index = stack.eval(index)?;
 
+        if what.is_data_frame() {
+            let name = what.frame_column_name(&index)?;
+            let frame = what.try_set_column(&name, value.clone())?;
+            stack.assign_target(target, frame)?;
+            return Ok(value);
+        }
+
         let subset = index.try_into()?;
 
         Ok(match what {
@@ -463,17 +734,99 @@ impl Callable for PostfixIndex {
 #[builtin(sym = "[", kind = PostfixCall("[", "]"))]
 pub struct PostfixVecIndex;
 impl CallableFormals for PostfixVecIndex {}
+impl PostfixVecIndex {
+    /// Whether arguments index a single dimension, as in `x[i]`
+    fn is_vector_index(args: &ExprList) -> bool {
+        args.len() == 2
+            && args.keys.iter().all(Option::is_none)
+            && !matches!(args.values[1], Expr::Missing)
+    }
+}
+
 impl Callable for PostfixVecIndex {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let (what, index) = stack.eval_binary(args.unnamed_binary_args())?;
-        what.try_get(index)
+        if Self::is_vector_index(&args) {
+            let (what, index) = match dispatch_index(Self::SYM, args, stack)? {
+                ControlFlow::Break(result) => return Ok(result),
+                ControlFlow::Continue(operands) => operands,
+            };
+            return what.try_get(index);
+        }
+
+        let (what, mut indices, drop) = match dispatch_dims_index(Self::SYM, args, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(operands) => operands,
+        };
+
+        match indices.len() {
+            0 => Ok(what),
+            1 => match indices.pop().flatten() {
+                Some(index) => what.try_get(index),
+                None => Ok(what),
+            },
+            _ => what.try_get_dims(indices, drop),
+        }
     }
 
     fn call_mut(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        let x = args.unnamed_binary_args();
-        let what = stack.eval_mut(x.0)?;
-        let index = stack.eval(x.1)?;
-        what.try_get(index)
+        if Self::is_vector_index(&args) {
+            let x = args.unnamed_binary_args();
+            let what = stack.eval_mut(x.0)?;
+            let index = stack.eval(x.1)?;
+            return what.try_get(index);
+        }
+
+        let mut args = args.into_iter();
+        let Some((_, what)) = args.next() else {
+            unreachable!();
+        };
+
+        let what = stack.eval_mut(what)?;
+        let indices = args
+            .filter(|(key, _)| key.as_deref() != Some("drop"))
+            .map(|(_, expr)| match expr {
+                Expr::Missing => Ok(None),
+                expr => stack.eval(expr).map(Some),
+            })
+            .collect::<Result<Vec<Option<Obj>>, Signal>>()?;
+
+        what.try_get_dims_mut(indices)
+    }
+
+    fn call_assign(&self, value: Expr, args: ExprList, stack: &mut CallStack) -> EvalResult {
+        let what = self.call_mut(args, stack)?;
+        let value = stack.eval(value)?;
+
+        // values assigned into a factor are encoded as codes of its levels
+        let levels = match &what {
+            Obj::Vector(v) => v.factor_levels(),
+            _ => None,
+        };
+
+        let Some(levels) = levels else {
+            return what.assign(value);
+        };
+
+        let labels: Vec<Character> = match value.clone().as_vector()? {
+            Obj::Vector(v) => match v.as_character() {
+                Vector::Character(v) => v.iter_values().collect(),
+                _ => unreachable!(),
+            },
+            _ => return internal_err!(),
+        };
+
+        let codes = factor_codes(&labels, &levels);
+        let introduced_na = labels
+            .iter()
+            .zip(codes.iter())
+            .any(|(label, code)| !label.is_na() && code.is_na());
+
+        if introduced_na {
+            warn("invalid factor level, NA generated", stack)?;
+        }
+
+        what.assign(Obj::Vector(Vector::from(codes)))?;
+        Ok(value)
     }
 }
 
@@ -488,6 +841,69 @@ mod tests {
         assert_eq!(r!([1, 2]), r!(1:2));
         assert_eq!(r!([1]), r!(1:1));
         assert_eq!(r!(1:-2:-3), r!([1, -1, -3]));
+        assert_eq!(r!(0:0.25:1), r!([0, 0.25, 0.5, 0.75, 1]));
+    }
+
+    #[test]
+    fn colon_sequences_are_lazy() {
+        r_expect! {{"
+            x <- 1:1000000000
+            length(x) == 1000000000 && x[999999999] == 999999999
+        "}}
+        assert_eq!(
+            r! {{"
+                x <- 1:1000000000
+                x[c(1, 1000000000)]
+            "}},
+            r! { [1, 1000000000] }
+        );
+        assert_eq!(r! { (1:10)[3:5] }, r! { [3, 4, 5] });
+        assert_eq!(r! { (1:10)[(1:10) > 8] }, r! { [9, 10] });
+    }
+
+    #[test]
+    fn colon_sequences_materialize_on_assignment() {
+        assert_eq!(
+            r! {{"
+                x <- 1:5
+                y <- x
+                x[2] <- 10
+                c(x, y)
+            "}},
+            r! { [1, 10, 3, 4, 5, 1, 2, 3, 4, 5] }
+        );
+    }
+
+    #[test]
+    fn unbounded_colon_sequences() {
+        assert_eq!(r! { (1:Inf)[[1000000]] }, r! { 1000000 });
+        assert_eq!(r! { length(1:Inf) }, r! { Inf });
+        assert_eq!(
+            r! { sum(1:Inf) },
+            EvalResult::Err(Signal::Error(Error::UnboundedSequence))
+        );
+        assert_eq!(
+            r! {{"
+                x <- 1:Inf
+                x[1] <- 0
+            "}},
+            EvalResult::Err(Signal::Error(Error::UnboundedSequence))
+        );
+    }
+
+    #[test]
+    fn unbounded_sequences_are_not_materialized() {
+        let unbounded = EvalResult::Err(Signal::Error(Error::UnboundedSequence));
+        assert_eq!(r! { factor(1:Inf) }, unbounded);
+        assert_eq!(r! { as.raw(1:Inf) }, unbounded);
+        assert_eq!(r! { data.frame(x = 1:Inf) }, unbounded);
+        assert_eq!(r! { table(1:Inf) }, unbounded);
+        assert_eq!(r! { matrix(1:Inf, 2) }, unbounded);
+        assert_eq!(r! { t(1:Inf) }, unbounded);
+        assert_eq!(r! { cbind(1, 1:Inf) }, unbounded);
+        assert_eq!(r! { tail(1:Inf) }, unbounded);
+        assert_eq!(r! { sum(1, 1:Inf) }, unbounded);
+        assert_eq!(r! { head(1:Inf, 3) }, r! { [1, 2, 3] });
     }
 
     #[test]
@@ -507,6 +923,62 @@ mod tests {
         "}}
     }
 
+    #[test]
+    fn arithmetic_dispatches_on_class() {
+        assert_eq!(
+            r! {{"
+                `+.money` <- function(e1, e2) structure(unclass(e1) + unclass(e2) * 100, class = 'money')
+                unclass(structure(1, class = 'money') + 2)
+            "}},
+            r! { 201 }
+        );
+    }
+
+    #[test]
+    fn operators_dispatch_to_group_generic() {
+        assert_eq!(
+            r! {{"
+                Ops.temp <- function(e1, e2) paste(.Generic, 'on temp')
+                x <- structure(10, class = 'temp')
+                [x > 1, 1 - x, -x]
+            "}},
+            r! { ["> on temp", "- on temp", "- on temp"] }
+        );
+    }
+
+    #[test]
+    fn index_dispatches_on_class() {
+        r_expect! {{"
+            `[.stack` <- function(x, i) structure(unclass(x)[i], class = 'stack')
+            s <- structure([1, 2, 3], class = 'stack')
+            class(s[1:2]) == 'stack' && length(s[1:2]) == 2
+        "}}
+    }
+
+    #[test]
+    fn index_with_na() {
+        // subsets with missing indices are compared by their printed values
+        let x = r! {{"
+            x <- c(1, 2, 3)
+            x[c(1, NA)]
+        "}};
+        assert_eq!(x.unwrap().to_string(), "[1]  1 NA");
+
+        let x = r! { c(a = 1, b = 2)[c(2, NA)] }.unwrap();
+        assert_eq!(x.to_string(), "b NA \n2 NA ");
+    }
+
+    #[test]
+    fn dollar_dispatches_on_class() {
+        assert_eq!(
+            r! {{"
+                `$.record` <- function(x, name) paste('field', name)
+                structure(list(), class = 'record')$anything
+            "}},
+            r! { "field anything" }
+        );
+    }
+
     #[test]
     fn dollar_access() {
         r_expect! {{"
@@ -514,4 +986,76 @@ mod tests {
             l$a == 1
         "}}
     }
+
+    #[test]
+    fn dollar_access_missing_column() {
+        r_expect! {{"
+            df <- data.frame(a = 1:2)
+            is_null(df$b) && length(df$a) == 2
+        "}}
+    }
+
+    #[test]
+    fn matrix_index() {
+        r_expect! {{"
+            m <- matrix(1:6, nrow = 2)
+            m[2, 3] == 6 && m[1, -1][2] == 5
+        "}}
+    }
+
+    #[test]
+    fn matrix_index_omitted() {
+        assert_eq!(r! { matrix(1:6, nrow = 2)[, 2] }, r! { [3, 4] });
+        assert_eq!(r! { matrix(1:6, nrow = 2)[2, ] }, r! { [2, 4, 6] });
+    }
+
+    #[test]
+    fn matrix_index_drop() {
+        assert_eq!(
+            r! { dim(matrix(1:6, nrow = 2)[, 2, drop = false]) },
+            r! { [2L, 1L] }
+        );
+        assert_eq!(
+            r! { matrix(1:6, nrow = 2)[1:2, 2:3] },
+            r! { matrix([3, 4, 5, 6], nrow = 2) }
+        );
+    }
+
+    #[test]
+    fn matrix_index_out_of_bounds() {
+        assert_eq!(
+            r! { matrix(1:6, nrow = 2)[3, 1] },
+            Error::Other("subscript out of bounds".to_string()).into()
+        );
+    }
+
+    #[test]
+    fn matrix_index_assign() {
+        assert_eq!(
+            r! {{"
+                m <- matrix(0, 2, 2)
+                m[1, ] <- [1, 2]
+                m[2, 2] <- 5
+                m
+            "}},
+            r! { matrix([1, 0, 2, 5], nrow = 2) }
+        );
+    }
+
+    #[test]
+    fn matrix_multiplication() {
+        assert_eq!(
+            r! { matrix(1:4, 2) %*% matrix(1:4, 2) },
+            r! { matrix([7, 10, 15, 22], nrow = 2) }
+        );
+        assert_eq!(r! { c(1:3 %*% 1:3) }, r! { 14 });
+    }
+
+    #[test]
+    fn matrix_multiplication_conformable() {
+        assert_eq!(
+            r! { matrix(1:6, 2) %*% matrix(1:6, 2) },
+            Error::Other("non-conformable arguments".to_string()).into()
+        );
+    }
 }
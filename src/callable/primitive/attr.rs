@@ -0,0 +1,166 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Get or Set an Attribute of an Object
+///
+/// Retrieve a single attribute of an object by name, or set it using the
+/// replacement form `attr(x, which) <- value`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// attr(x, which)
+/// attr(x, which) <- value
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object whose attribute to access.
+/// * `which`: The name of the attribute.
+/// * `value`: When setting, the new value of the attribute. A `null` value
+///   removes the attribute.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// x <- [1, 2, 3]
+/// attr(x, "units") <- "cm"
+/// attr(x, "units")
+/// ```
+///
+#[doc(alias = "attr")]
+#[builtin(sym = "attr")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveAttr;
+
+formals!(PrimitiveAttr, "(x, which)");
+
+impl Callable for PrimitiveAttr {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = args.try_get_named("x")?.force(stack)?;
+        let which = attr_name(args.try_get_named("which")?.force(stack)?)?;
+        Ok(x.get_attr(&which).unwrap_or(Obj::Null))
+    }
+}
+
+/// Set an Attribute of an Object
+///
+/// The replacement function used by `attr(x, which) <- value`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// `attr<-`(x, which, value)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object whose attribute to set.
+/// * `which`: The name of the attribute.
+/// * `value`: The new value of the attribute. A `null` value removes the
+///   attribute.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// `attr<-`([1, 2, 3], "units", "cm")
+/// ```
+///
+#[doc(alias = "attr<-")]
+#[builtin(sym = "attr<-")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveAttrAssign;
+
+formals!(PrimitiveAttrAssign, "(x, which, value)");
+
+impl Callable for PrimitiveAttrAssign {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = args.try_get_named("x")?.force(stack)?;
+        let which = attr_name(args.try_get_named("which")?.force(stack)?)?;
+        let value = args.try_get_named("value")?.force(stack)?;
+        x.set_attr(&which, value)
+    }
+}
+
+fn attr_name(which: Obj) -> Result<String, Signal> {
+    match which {
+        Obj::Vector(v @ Vector::Character(_)) if v.len() == 1 => {
+            let mut names: Vec<String> = v.into();
+            Ok(names.remove(0))
+        }
+        _ => Err(Error::Other("exactly one attribute 'which' must be given".to_string()).into()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{r, r_expect};
+
+    #[test]
+    fn attr_round_trip() {
+        r_expect! {{"
+            x <- [1, 2, 3]
+            attr(x, 'units') <- 'cm'
+            attr(x, 'units') == 'cm'
+        "}}
+    }
+
+    #[test]
+    fn attr_missing_is_null() {
+        r_expect! { is_null(attr([1, 2], "units")) }
+    }
+
+    #[test]
+    fn attr_null_removes() {
+        r_expect! {{"
+            x <- [1, 2, 3]
+            attr(x, 'units') <- 'cm'
+            attr(x, 'units') <- null
+            is_null(attr(x, 'units'))
+        "}}
+    }
+
+    #[test]
+    fn attr_names() {
+        assert_eq!(
+            r! {{"
+                x <- [1, 2]
+                attr(x, 'names') <- ['a', 'b']
+                names(x)
+            "}},
+            r! { ["a", "b"] }
+        );
+    }
+
+    #[test]
+    fn attr_on_functions() {
+        r_expect! {{"
+            f <- function(x) x
+            attr(f, 'note') <- 3
+            attr(f, 'note') == 3
+        "}}
+    }
+
+    #[test]
+    fn attr_replacement_nested() {
+        assert_eq!(
+            r! {{"
+                x <- (a = [1, 2], b = 3)
+                attr(x$a, 'units') <- 'cm'
+                attr(x$a, 'units')
+            "}},
+            r! { "cm" }
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Get All Attributes of an Object
+///
+/// Returns a named list of all attributes of an object, including its
+/// `names`, or `null` if the object has no attributes.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// attributes(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object whose attributes to retrieve.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// attributes(structure([1, 2], units = "cm"))
+/// ```
+///
+#[doc(alias = "attributes")]
+#[builtin(sym = "attributes")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveAttributes;
+
+formals!(PrimitiveAttributes, "(x)");
+
+impl Callable for PrimitiveAttributes {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+
+        let mut attrs: Vec<(Option<String>, Obj)> = vec![];
+
+        if let Some(names) = x.get_attr("names") {
+            attrs.push((Some("names".to_string()), names));
+        }
+
+        if let Some(other) = x.attributes() {
+            for (k, v) in other.iter() {
+                attrs.push((Some(k.clone()), v.clone()));
+            }
+        }
+
+        if attrs.is_empty() {
+            Ok(Obj::Null)
+        } else {
+            Ok(Obj::List(List::from(attrs)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{r, r_expect};
+
+    #[test]
+    fn attributes_of_plain_vector() {
+        r_expect! { is_null(attributes([1, 2, 3])) }
+    }
+
+    #[test]
+    fn attributes_include_names() {
+        assert_eq!(
+            r! { attributes(structure([a = 1, b = 2], units = "cm")) },
+            r! { list(names = ["a", "b"], units = "cm") }
+        );
+    }
+}
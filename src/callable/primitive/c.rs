@@ -1,6 +1,7 @@
 use r_derive::*;
 
 use crate::callable::core::*;
+use crate::error::Error;
 use crate::object::types::*;
 use crate::object::*;
 use crate::{formals, lang::*};
@@ -45,13 +46,27 @@ formals!(PrimitiveC, "(...)");
 
 impl Callable for PrimitiveC {
     fn call(&self, args: ExprList, stack: &mut CallStack) -> EvalResult {
-        // this can be cleaned up quite a bit, but I just need it working with
-        // more types for now to test vectorized operators using different types
-
         let Obj::List(vals) = stack.eval_list_eager(args)? else {
             unreachable!()
         };
 
+        PrimitiveC::combine(vals)
+    }
+}
+
+impl PrimitiveC {
+    /// Combine evaluated values into a single vector or list
+    pub fn combine(vals: List) -> EvalResult {
+        // this can be cleaned up quite a bit, but I just need it working with
+        // more types for now to test vectorized operators using different types
+
+        if vals
+            .iter_values()
+            .any(|v| matches!(v, Obj::Vector(v) if v.is_unbounded()))
+        {
+            return Error::UnboundedSequence.into();
+        }
+
         // lets first see what we're aiming to build.
         let ty: u8 = vals
             .pairs_ref()
@@ -91,7 +106,7 @@ impl Callable for PrimitiveC {
                         for (name2, value2) in x.iter_pairs() {
                             let name = match (&name1, name2) {
                                 (OptionNA::Some(x1), OptionNA::Some(x2)) => {
-                                    OptionNA::Some(format!("{x1}.{x2}"))
+                                    OptionNA::Some(format!("{x1}.{x2}").into())
                                 }
                                 (OptionNA::NA, OptionNA::Some(x2)) => OptionNA::Some(x2),
                                 (OptionNA::Some(_), OptionNA::NA) => name1.clone(),
@@ -124,7 +139,7 @@ impl Callable for PrimitiveC {
                         (Some(prefix), Some(names_iter)) => names_iter
                             .map(|maybe_name| {
                                 if let OptionNA::Some(name) = maybe_name {
-                                    Character::Some(format!("{}.{}", prefix, name))
+                                    Character::Some(format!("{}.{}", prefix, name).into())
                                 } else {
                                     Character::Some(prefix.clone())
                                 }
@@ -149,23 +164,29 @@ impl Callable for PrimitiveC {
                 Obj::Vector(Vector::Logical(_)) => Vector::from(Vec::<Logical>::new()),
                 Obj::Vector(Vector::Integer(_)) => Vector::from(Vec::<Integer>::new()),
                 Obj::Vector(Vector::Double(_)) => Vector::from(Vec::<Double>::new()),
+                Obj::Vector(Vector::Complex(_)) => Vector::from(Vec::<Complex>::new()),
                 Obj::Vector(Vector::Character(_)) => Vector::from(Vec::<Character>::new()),
+                Obj::Vector(Vector::Raw(_)) => Vector::from(Vec::<Raw>::new()),
                 _ => unreachable!(),
             })
-            .fold(Vector::from(Vec::<Logical>::new()), |l, r| match (l, r) {
+            .fold(Vector::from(Vec::<Raw>::new()), |l, r| match (l, r) {
                 (v @ Vector::Character(_), _) => v,
                 (_, v @ Vector::Character(_)) => v,
+                (v @ Vector::Complex(_), _) => v,
+                (_, v @ Vector::Complex(_)) => v,
                 (v @ Vector::Double(_), _) => v,
                 (_, v @ Vector::Double(_)) => v,
                 (v @ Vector::Integer(_), _) => v,
                 (_, v @ Vector::Integer(_)) => v,
                 (v @ Vector::Logical(_), _) => v,
+                (_, v @ Vector::Logical(_)) => v,
+                (v @ Vector::Raw(_), _) => v,
             });
 
         // consume values and merge into a new collection
         let v = match ret {
             Vector::Character(_) => Vector::from(
-                Vec::<OptionNA<String>>::new()
+                Vec::<Character>::new()
                     .into_iter()
                     .chain(vals.iter_values().flat_map(|i| match i.as_character() {
                         Ok(Obj::Vector(Vector::Character(v))) => v.iter_values(),
@@ -173,6 +194,18 @@ impl Callable for PrimitiveC {
                     }))
                     .collect::<Vec<Character>>(),
             ),
+            Vector::Complex(_) => Vector::from(
+                Vec::<OptionNA<Complex64>>::new()
+                    .into_iter()
+                    .chain(
+                        vals.iter_values()
+                            .flat_map(|i| match i.clone().as_complex() {
+                                Ok(Obj::Vector(Vector::Complex(v))) => v.iter_values(),
+                                _ => unreachable!(),
+                            }),
+                    )
+                    .collect::<Vec<Complex>>(),
+            ),
             Vector::Double(_) => Vector::from(
                 Vec::<OptionNA<f64>>::new()
                     .into_iter()
@@ -209,6 +242,15 @@ impl Callable for PrimitiveC {
                     )
                     .collect::<Vec<Logical>>(),
             ),
+            Vector::Raw(_) => Vector::from(
+                Vec::<OptionNA<u8>>::new()
+                    .into_iter()
+                    .chain(vals.iter_values().flat_map(|i| match i.clone().as_raw() {
+                        Ok(Obj::Vector(Vector::Raw(v))) => v.iter_values(),
+                        _ => unreachable!(),
+                    }))
+                    .collect::<Vec<Raw>>(),
+            ),
         };
 
         if let Some(names) = names {
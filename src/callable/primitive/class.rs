@@ -0,0 +1,247 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Get the Class of an Object
+///
+/// Returns the `class` attribute of an object. For objects without a
+/// `class` attribute, the class implied by the type of the object is
+/// returned.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// class(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object whose class to retrieve.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// class(1)
+/// class(structure(list(), class = "record"))
+/// ```
+///
+#[doc(alias = "class")]
+#[builtin(sym = "class")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveClass;
+
+formals!(PrimitiveClass, "(x)");
+
+impl Callable for PrimitiveClass {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+        Ok(Obj::Vector(x.class().into()))
+    }
+}
+
+/// Set the Class of an Object
+///
+/// The replacement function used by `class(x) <- value`, setting the
+/// `class` attribute of an object. A `null` value removes the attribute.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// class(x) <- value
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object whose class to set.
+/// * `value`: A `character` vector of classes, or `null`.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// x <- list(name = "Ada")
+/// class(x) <- "person"
+/// class(x)
+/// ```
+///
+#[doc(alias = "class<-")]
+#[builtin(sym = "class<-")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveClassAssign;
+
+formals!(PrimitiveClassAssign, "(x, value)");
+
+impl Callable for PrimitiveClassAssign {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = args.try_get_named("x")?.force(stack)?;
+        let value = args.try_get_named("value")?.force(stack)?;
+
+        match value {
+            Obj::Null | Obj::Vector(Vector::Character(_)) => x.set_attr("class", value),
+            _ => Error::Other("attempt to set invalid 'class' attribute".to_string()).into(),
+        }
+    }
+}
+
+/// Remove the Class of an Object
+///
+/// Returns the object without its `class` attribute.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// unclass(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object whose class to remove.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// unclass(structure(1, class = "meters"))
+/// ```
+///
+#[doc(alias = "unclass")]
+#[builtin(sym = "unclass")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveUnclass;
+
+formals!(PrimitiveUnclass, "(x)");
+
+impl Callable for PrimitiveUnclass {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+        match x {
+            Obj::Vector(_) | Obj::List(_) | Obj::Function(..) => x.set_attr("class", Obj::Null),
+            x => Ok(x),
+        }
+    }
+}
+
+/// Test Whether an Object Inherits from a Class
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// inherits(x, what, which = false)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object to test.
+/// * `what`: A `character` vector of class names.
+/// * `which`: When `true`, returns an `integer` vector giving the position
+///   of each of `what` in the class of `x`, or `0` if absent.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// x <- structure(list(), class = ["dog", "animal"])
+/// inherits(x, "animal")
+/// inherits(x, ["cat", "dog"], which = true)
+/// ```
+///
+#[doc(alias = "inherits")]
+#[builtin(sym = "inherits")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveInherits;
+
+formals!(PrimitiveInherits, "(x, what, which = false)");
+
+impl Callable for PrimitiveInherits {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = args.try_get_named("x")?.force(stack)?;
+        let which: bool = args.try_get_named("which")?.force(stack)?.try_into()?;
+
+        let what: Vec<String> = match args.try_get_named("what")?.force(stack)? {
+            Obj::Vector(v @ Vector::Character(_)) => v.into(),
+            _ => return Error::Other("'what' must be a character vector".to_string()).into(),
+        };
+
+        let classes = x.class();
+        let positions = what.iter().map(|w| {
+            classes
+                .iter()
+                .position(|c| c == w)
+                .map_or(0, |i| i as i32 + 1)
+        });
+
+        if which {
+            Ok(Obj::Vector(positions.collect::<Vec<i32>>().into()))
+        } else {
+            let inherits = positions.into_iter().any(|i| i > 0);
+            Ok(Obj::Vector(vec![OptionNA::Some(inherits)].into()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{r, r_expect};
+
+    #[test]
+    fn implicit_classes() {
+        assert_eq!(
+            r! { [class(1), class(1L), class(true), class("a"), class(list()), class(null)] },
+            r! { ["numeric", "integer", "logical", "character", "list", "NULL"] }
+        );
+    }
+
+    #[test]
+    fn class_assignment() {
+        assert_eq!(
+            r! {{"
+                x <- list(name = 'Ada')
+                class(x) <- ['person', 'record']
+                class(x)
+            "}},
+            r! { ["person", "record"] }
+        );
+    }
+
+    #[test]
+    fn unclass_removes_class() {
+        r_expect! {{"
+            x <- structure([1, 2], class = 'meters')
+            class(unclass(x)) == 'numeric'
+        "}}
+    }
+
+    #[test]
+    fn inherits_from_any_class() {
+        r_expect! {{"
+            x <- structure(list(), class = ['dog', 'animal'])
+            inherits(x, 'animal') && !inherits(x, 'cat')
+        "}}
+    }
+
+    #[test]
+    fn inherits_which() {
+        assert_eq!(
+            r! { inherits(structure(list(), class = ["dog", "animal"]), ["cat", "animal"], which = true) },
+            r! { [0L, 2L] }
+        );
+    }
+
+    #[test]
+    fn conditions_have_classes() {
+        r_expect! {{"
+            tryCatch(stop('oops'), error = function(e) inherits(e, 'simpleError'))
+        "}}
+    }
+}
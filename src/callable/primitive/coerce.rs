@@ -0,0 +1,286 @@
+use r_derive::*;
+use std::ops::ControlFlow;
+
+use super::warning::warn;
+use crate::callable::core::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::coercion::truncate_to_integer;
+use crate::object::*;
+
+/// Coerce to an Integer Vector
+///
+/// Attributes, including names, are dropped. Factors are coerced to their
+/// `integer` codes. Numbers are truncated, while those outside the range
+/// of integers produce `NA` with a warning.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// as.integer(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object to coerce.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// as.integer([1.5, 2.5])
+/// as.integer(factor(["b", "a"]))
+/// ```
+///
+#[doc(alias = "as.integer")]
+#[builtin(sym = "as.integer")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveAsInteger;
+
+formals!(PrimitiveAsInteger, "(x)");
+
+impl Callable for PrimitiveAsInteger {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        coerce("as.integer", args, ellipsis, stack, |x, stack| {
+            if exceeds_integer_range(&x) {
+                warn("NAs introduced by coercion to integer range", stack)?;
+            }
+            x.as_integer()
+        })
+    }
+}
+
+/// Whether any numbers of a vector are outside the range of integers
+fn exceeds_integer_range(x: &Obj) -> bool {
+    let Obj::Vector(v @ (Vector::Double(_) | Vector::Complex(_))) = x else {
+        return false;
+    };
+
+    let Vector::Double(values) = v.clone().as_double() else {
+        unreachable!()
+    };
+
+    values
+        .iter_values()
+        .any(|x| matches!(x, OptionNA::Some(x) if !x.is_nan() && truncate_to_integer(x).is_none()))
+}
+
+/// Coerce to a Double Vector
+///
+/// Attributes, including names, are dropped. Factors are coerced to their
+/// codes.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// as.double(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object to coerce.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// as.double("3.14")
+/// ```
+///
+#[doc(alias = "as.double")]
+#[builtin(sym = "as.double")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveAsDouble;
+
+formals!(PrimitiveAsDouble, "(x)");
+
+impl Callable for PrimitiveAsDouble {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        coerce("as.double", args, ellipsis, stack, |x, _| x.as_double())
+    }
+}
+
+/// Coerce to a Logical Vector
+///
+/// Attributes, including names, are dropped.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// as.logical(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object to coerce.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// as.logical([0, 1, 2])
+/// ```
+///
+#[doc(alias = "as.logical")]
+#[builtin(sym = "as.logical")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveAsLogical;
+
+formals!(PrimitiveAsLogical, "(x)");
+
+impl Callable for PrimitiveAsLogical {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        coerce("as.logical", args, ellipsis, stack, |x, _| x.as_logical())
+    }
+}
+
+/// Coerce to a Complex Vector
+///
+/// Attributes, including names, are dropped. Character values are parsed
+/// as complex numbers of the form `a+bi`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// as.complex(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object to coerce.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// as.complex(-1)
+/// as.complex("1+2i")
+/// ```
+///
+#[doc(alias = "as.complex")]
+#[builtin(sym = "as.complex")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveAsComplex;
+
+formals!(PrimitiveAsComplex, "(x)");
+
+impl Callable for PrimitiveAsComplex {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        coerce("as.complex", args, ellipsis, stack, |x, _| x.as_complex())
+    }
+}
+
+/// Coerce to a Character Vector
+///
+/// Attributes, including names, are dropped. Factors are coerced to the
+/// labels of their levels.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// as.character(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object to coerce.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// as.character([1, 2])
+/// as.character(factor(["b", "a"]))
+/// ```
+///
+#[doc(alias = "as.character")]
+#[builtin(sym = "as.character")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveAsCharacter;
+
+formals!(PrimitiveAsCharacter, "(x)");
+
+impl Callable for PrimitiveAsCharacter {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        coerce("as.character", args, ellipsis, stack, |x, _| {
+            x.as_character()
+        })
+    }
+}
+
+fn coerce(
+    generic: &str,
+    args: List,
+    ellipsis: List,
+    stack: &mut CallStack,
+    f: impl FnOnce(Obj, &mut CallStack) -> EvalResult,
+) -> EvalResult {
+    let mut args = Obj::List(args);
+    let x = match dispatch_first(generic, args.try_get_named("x")?, &ellipsis, stack)? {
+        ControlFlow::Break(result) => return Ok(result),
+        ControlFlow::Continue(x) => x,
+    };
+
+    match f(x, stack)? {
+        Obj::Vector(v) => Ok(Obj::Vector(v.without_names().with_attributes(None))),
+        x => Ok(x),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{r, r_expect};
+
+    #[test]
+    fn as_integer_truncates_doubles() {
+        assert_eq!(r! { as.integer([1.5, -2.5]) }, r! { c(1L, -2L) });
+    }
+
+    #[test]
+    fn as_integer_out_of_range_is_na() {
+        assert_eq!(
+            r! {{"as.integer(c(1e10, NaN, 1))"}},
+            r! {{"c(NA * 1L, NA * 1L, 1L)"}}
+        );
+        assert_eq!(
+            r! {{"tryCatch(as.integer(1e10), warning = function(w) conditionMessage(w))"}},
+            r! { "NAs introduced by coercion to integer range" }
+        );
+    }
+
+    #[test]
+    fn as_character_of_numbers() {
+        assert_eq!(r! { as.character([1, 2]) }, r! { ["1", "2"] });
+    }
+
+    #[test]
+    fn as_complex_parses_characters() {
+        assert_eq!(r! { as.complex(["1+2i", "-3i"]) }, r! { c(1+2i, -3i) });
+        assert_eq!(r! { as.character(1-2i) }, r! { "1-2i" });
+    }
+
+    #[test]
+    fn coercion_drops_attributes() {
+        r_expect! {{"
+            x <- structure([1, 2], names = ['a', 'b'], unit = 'cm')
+            is_null(attributes(as.double(x)))
+        "}}
+    }
+
+    #[test]
+    fn factors_coerce_to_codes_and_labels() {
+        assert_eq!(
+            r! { as.integer(factor(["b", "a", "b"])) },
+            r! { c(2L, 1L, 2L) }
+        );
+        assert_eq!(
+            r! { as.character(factor(["b", "a", "b"])) },
+            r! { ["b", "a", "b"] }
+        );
+    }
+}
@@ -0,0 +1,335 @@
+use r_derive::*;
+
+use super::warning::warn;
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::types::*;
+use crate::object::*;
+
+/// Real Part of a Complex Number
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// Re(z)
+/// ```
+///
+/// ## Arguments
+///
+/// * `z`: A numeric or complex vector.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// Re(3+4i)
+/// ```
+///
+#[doc(alias = "Re")]
+#[builtin(sym = "Re")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveRe;
+
+formals!(PrimitiveRe, "(z)");
+
+impl Callable for PrimitiveRe {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        complex_part(args, stack, |z| z.re)
+    }
+}
+
+/// Imaginary Part of a Complex Number
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// Im(z)
+/// ```
+///
+/// ## Arguments
+///
+/// * `z`: A numeric or complex vector.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// Im(3+4i)
+/// ```
+///
+#[doc(alias = "Im")]
+#[builtin(sym = "Im")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveIm;
+
+formals!(PrimitiveIm, "(z)");
+
+impl Callable for PrimitiveIm {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        complex_part(args, stack, |z| z.im)
+    }
+}
+
+/// Modulus of a Complex Number
+///
+/// The distance of a complex number from the origin. For real numbers, this
+/// is their absolute value.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// Mod(z)
+/// ```
+///
+/// ## Arguments
+///
+/// * `z`: A numeric or complex vector.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// Mod(3+4i)
+/// ```
+///
+#[doc(alias = "Mod")]
+#[builtin(sym = "Mod")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveMod;
+
+formals!(PrimitiveMod, "(z)");
+
+impl Callable for PrimitiveMod {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        complex_part(args, stack, |z| z.modulus())
+    }
+}
+
+/// Argument of a Complex Number
+///
+/// The angle, in radians, between the positive real axis and a complex
+/// number, in the range `(-pi, pi]`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// Arg(z)
+/// ```
+///
+/// ## Arguments
+///
+/// * `z`: A numeric or complex vector.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// Arg(1i)
+/// Arg(-1)
+/// ```
+///
+#[doc(alias = "Arg")]
+#[builtin(sym = "Arg")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveArg;
+
+formals!(PrimitiveArg, "(z)");
+
+impl Callable for PrimitiveArg {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        complex_part(args, stack, |z| z.argument())
+    }
+}
+
+/// Complex Conjugate
+///
+/// Negates the imaginary part of a complex number. Real numbers are
+/// returned unchanged.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// Conj(z)
+/// ```
+///
+/// ## Arguments
+///
+/// * `z`: A numeric or complex vector.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// Conj(3+4i)
+/// ```
+///
+#[doc(alias = "Conj")]
+#[builtin(sym = "Conj")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveConj;
+
+formals!(PrimitiveConj, "(z)");
+
+impl Callable for PrimitiveConj {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let z = Obj::List(args).try_get_named("z")?.force(stack)?;
+        match numeric(z)? {
+            Vector::Complex(z) => {
+                let attrs = z.attributes();
+                let values: Vec<Complex> = z.iter_values().map(|z| z.map(|z| z.conj())).collect();
+                Ok(Obj::Vector(Vector::from(values).with_attributes(attrs)))
+            }
+            z => Ok(Obj::Vector(z)),
+        }
+    }
+}
+
+/// Square Root
+///
+/// The square root of negative `double`s is `NaN`, with a warning. Complex
+/// numbers produce their principal square root, such that the square root
+/// of a negative number can be taken by first coercing it to `complex`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// sqrt(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A numeric or complex vector.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// sqrt([4, 9])
+/// sqrt(-4+0i)
+/// ```
+///
+#[doc(alias = "sqrt")]
+#[builtin(sym = "sqrt")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveSqrt;
+
+formals!(PrimitiveSqrt, "(x)");
+
+impl Callable for PrimitiveSqrt {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+        match numeric(x)? {
+            Vector::Complex(x) => {
+                let attrs = x.attributes();
+                let values: Vec<Complex> = x.iter_values().map(|x| x.map(|x| x.sqrt())).collect();
+                Ok(Obj::Vector(Vector::from(values).with_attributes(attrs)))
+            }
+            x => {
+                let attrs = x.attributes();
+                let Vector::Double(x) = x.as_double() else {
+                    unreachable!()
+                };
+
+                let values: Vec<Double> = x.iter_values().map(|x| x.map(f64::sqrt)).collect();
+                if x.iter_values()
+                    .any(|x| matches!(x, OptionNA::Some(x) if x < 0.0))
+                {
+                    warn("NaNs produced", stack)?;
+                }
+
+                Ok(Obj::Vector(Vector::from(values).with_attributes(attrs)))
+            }
+        }
+    }
+}
+
+/// Force a numeric or complex vector argument
+fn numeric(x: Obj) -> Result<Vector, Signal> {
+    match x {
+        Obj::Vector(v) if v.is_unbounded() => return Error::UnboundedSequence.into(),
+        Obj::Vector(Vector::Character(_)) => (),
+        Obj::Vector(v) if v.factor_levels().is_none() => return Ok(v),
+        _ => (),
+    };
+
+    Error::Other("non-numeric argument to function".to_string()).into()
+}
+
+/// Map each element of a vector, as a complex number, to a `double`
+fn complex_part(args: List, stack: &mut CallStack, f: fn(Complex64) -> f64) -> EvalResult {
+    let z = Obj::List(args).try_get_named("z")?.force(stack)?;
+    let z = numeric(z)?;
+    let attrs = z.attributes();
+    let Vector::Complex(z) = z.as_complex() else {
+        unreachable!()
+    };
+
+    let values: Vec<Double> = z.iter_values().map(|z| z.map(f)).collect();
+    Ok(Obj::Vector(Vector::from(values).with_attributes(attrs)))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::{r, r_expect};
+
+    #[test]
+    fn complex_literals() {
+        r_expect! { 2i * 2i == -4 }
+        r_expect! { 1 + 2i == 1+2i }
+    }
+
+    #[test]
+    fn complex_arithmetic() {
+        assert_eq!(r! { (1+2i) * (3-1i) }, r! { 5+5i });
+        assert_eq!(r! { (5+5i) / (3-1i) }, r! { 1+2i });
+        assert_eq!(r! { -(1+2i) }, r! { -1-2i });
+        assert_eq!(r! { 1i ^ 2 }, r! { -1+0i });
+        assert_eq!(r! { c(1L, 2i) }, r! { c(1+0i, 0+2i) });
+    }
+
+    #[test]
+    fn complex_parts() {
+        assert_eq!(r! { Re(3+4i) }, r! { 3 });
+        assert_eq!(r! { Im(3+4i) }, r! { 4 });
+        assert_eq!(r! { Mod(3+4i) }, r! { 5 });
+        assert_eq!(r! { Mod(-2) }, r! { 2 });
+        assert_eq!(r! { Arg(-1) }, r! { Arg(-1+0i) });
+        assert_eq!(r! { Conj(3+4i) }, r! { 3-4i });
+    }
+
+    #[test]
+    fn sqrt_of_negative_numbers() {
+        assert_eq!(r! { sqrt(-4+0i) }, r! { 0+2i });
+        assert_eq!(r! { sqrt([4, 9]) }, r! { [2, 3] });
+        assert_eq!(format!("{}", r! { sqrt(-1) }.unwrap()), "[1] NaN");
+    }
+
+    #[test]
+    fn complex_values_are_unordered() {
+        assert_eq!(
+            r! { 1i < 2i },
+            Error::Other("invalid comparison with complex values".to_string()).into()
+        );
+    }
+
+    #[test]
+    fn complex_printing() {
+        assert_eq!(
+            format!("{}", r! { c(1+2i, -1.5i) }.unwrap()),
+            "[1]   1+2i 0-1.5i"
+        );
+        assert_eq!(
+            format!("{}", r! { as.complex(null) }.unwrap()),
+            "complex(0)"
+        );
+    }
+}
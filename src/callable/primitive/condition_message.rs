@@ -0,0 +1,92 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::error::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Get the Message of a Condition
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// conditionMessage(c)
+/// ```
+///
+/// ## Arguments
+///
+/// * `c`: A condition object.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// tryCatch(stop("oops"), error = function(e) conditionMessage(e))
+/// ```
+///
+#[doc(alias = "conditionMessage")]
+#[builtin(sym = "conditionMessage")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveConditionMessage;
+
+formals!(PrimitiveConditionMessage, "(c)");
+
+impl Callable for PrimitiveConditionMessage {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let cond = Obj::List(args).try_get_named("c")?.force(stack)?;
+        Ok(Obj::Vector(vec![condition_message(&cond)].into()))
+    }
+}
+
+/// Get the Call of a Condition
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// conditionCall(c)
+/// ```
+///
+/// ## Arguments
+///
+/// * `c`: A condition object.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// f <- function() stop("oops")
+/// tryCatch(f(), error = function(e) conditionCall(e))
+/// ```
+///
+#[doc(alias = "conditionCall")]
+#[builtin(sym = "conditionCall")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveConditionCall;
+
+formals!(PrimitiveConditionCall, "(c)");
+
+impl Callable for PrimitiveConditionCall {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut cond = Obj::List(args).try_get_named("c")?.force(stack)?;
+        match cond.try_get_named("call") {
+            Ok(call) => Ok(call),
+            Err(_) => Ok(Obj::Null),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn top_level_conditions_have_no_call() {
+        assert_eq!(
+            r! { tryCatch(stop("x"), error = function(e) conditionCall(e)) },
+            r! { null }
+        );
+    }
+}
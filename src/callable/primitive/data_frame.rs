@@ -0,0 +1,332 @@
+use r_derive::*;
+use std::ops::ControlFlow;
+
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Create a Data Frame
+///
+/// Data frames are tables of named columns of equal length, such that
+/// each row is an observation and each column is a variable.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// data.frame(...)
+/// ```
+///
+/// ## Arguments
+///
+/// `...`: Named vectors to use as columns. Vectors of length one are
+///   recycled to the number of rows, while all other columns must have the
+///   same length.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// df <- data.frame(x = 1:3, y = ["a", "b", "c"])
+/// df$y
+/// df[2:3, "x"]
+/// ```
+///
+#[doc(alias = "data.frame")]
+#[builtin(sym = "data.frame")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveDataFrame;
+
+formals!(PrimitiveDataFrame, "(...)");
+
+impl Callable for PrimitiveDataFrame {
+    fn call_matched(&self, _args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let columns = force_promises(ellipsis, stack)?;
+        let atomic = |x: &Obj| matches!(x, Obj::Vector(_) | Obj::Null);
+        if !columns.iter().all(|(_, column)| atomic(column)) {
+            return Error::Other("data frame columns must be atomic vectors".to_string()).into();
+        }
+
+        Obj::new_data_frame(columns)
+    }
+}
+
+/// Get the First Elements of an Object
+///
+/// Returns the first `n` elements of a vector or list, or the first `n`
+/// rows of a data frame. A negative `n` returns all but the last `-n`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// head(x, n = 6)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A vector, list or data frame.
+/// * `n`: The number of elements or rows to return.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// head(1:10, 3)
+/// head(data.frame(x = 1:10), n = -8)
+/// ```
+///
+#[doc(alias = "head")]
+#[builtin(sym = "head")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveHead;
+
+formals!(PrimitiveHead, "(x, n = 6)");
+
+impl Callable for PrimitiveHead {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = match dispatch_first("head", args.try_get_named("x")?, &ellipsis, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(x) => x,
+        };
+
+        let n = args.try_get_named("n")?.force(stack)?;
+        let len = extent(&x);
+        let n = taken(n, len)?;
+        take_positions(x, (1..=n as i32).collect())
+    }
+}
+
+/// Get the Last Elements of an Object
+///
+/// Returns the last `n` elements of a vector or list, or the last `n` rows
+/// of a data frame. A negative `n` returns all but the first `-n`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// tail(x, n = 6)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A vector, list or data frame.
+/// * `n`: The number of elements or rows to return.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// tail(1:10, 3)
+/// tail(data.frame(x = 1:10), n = 2)
+/// ```
+///
+#[doc(alias = "tail")]
+#[builtin(sym = "tail")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveTail;
+
+formals!(PrimitiveTail, "(x, n = 6)");
+
+impl Callable for PrimitiveTail {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = match dispatch_first("tail", args.try_get_named("x")?, &ellipsis, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(x) => x,
+        };
+
+        // the end of an unbounded sequence can't be found
+        if matches!(&x, Obj::Vector(v) if v.is_unbounded()) {
+            return Error::UnboundedSequence.into();
+        }
+
+        let n = args.try_get_named("n")?.force(stack)?;
+        let len = extent(&x);
+        let n = taken(n, len)?;
+        take_positions(x, ((len - n + 1) as i32..=len as i32).collect())
+    }
+}
+
+/// The number of rows of a data frame, or elements of any other object
+fn extent(x: &Obj) -> usize {
+    match x.frame_shape() {
+        Some((nrow, _)) => nrow,
+        None => x.len().unwrap_or_default(),
+    }
+}
+
+/// The number of elements to take, given `n` and the extent of an object
+fn taken(n: Obj, len: usize) -> Result<usize, Signal> {
+    let n: i32 = match n.as_integer()? {
+        Obj::Vector(Vector::Integer(n)) => match n.iter_values().next() {
+            Some(OptionNA::Some(n)) => n,
+            _ => return Error::Other("invalid 'n' - must be a number".to_string()).into(),
+        },
+        _ => unreachable!(),
+    };
+
+    Ok(match n {
+        n if n >= 0 => (n as usize).min(len),
+        n => len.saturating_sub(n.unsigned_abs() as usize),
+    })
+}
+
+fn take_positions(x: Obj, positions: Vec<i32>) -> EvalResult {
+    let index = Obj::Vector(positions.into());
+    match x {
+        x if x.is_data_frame() => x.try_get_frame(Some(index), None, false),
+        Obj::Vector(_) | Obj::List(_) => Ok(match x.try_get(index)? {
+            Obj::Vector(v) => Obj::Vector(v.materialize()?),
+            Obj::List(l) => Obj::List(l.materialize()?),
+            x => x,
+        }),
+        Obj::Null => Ok(Obj::Null),
+        _ => Error::Other("'x' must be a vector, list or data frame".to_string()).into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::{r, r_expect};
+
+    #[test]
+    fn data_frame_dimensions() {
+        r_expect! {{"
+            df <- data.frame(x = 1:3, y = ['a', 'b', 'c'])
+            nrow(df) == 3 && ncol(df) == 2
+        "}}
+    }
+
+    #[test]
+    fn data_frame_recycles_scalars() {
+        assert_eq!(
+            r! { data.frame(x = 1:3, y = "a")$y },
+            r! { ["a", "a", "a"] }
+        );
+    }
+
+    #[test]
+    fn data_frame_columns_must_have_equal_lengths() {
+        assert_eq!(
+            r! { data.frame(x = 1:3, y = [1, 2]) },
+            Error::Other("arguments imply differing number of rows: 3, 2".to_string()).into()
+        );
+    }
+
+    #[test]
+    fn data_frame_column_access() {
+        assert_eq!(
+            r! {{"
+                df <- data.frame(x = 1:3, y = ['a', 'b', 'c'])
+                df[['y']]
+            "}},
+            r! { ["a", "b", "c"] }
+        );
+    }
+
+    #[test]
+    fn data_frame_indexing() {
+        assert_eq!(
+            r! {{"
+                df <- data.frame(x = 1:3, y = ['a', 'b', 'c'])
+                df[2:3, 'y']
+            "}},
+            r! { ["b", "c"] }
+        );
+        assert_eq!(
+            r! {{"
+                df <- data.frame(x = 1:3, y = ['a', 'b', 'c'])
+                ncol(df['x'])
+            "}},
+            r! { 1L }
+        );
+        assert_eq!(
+            r! {{"
+                df <- data.frame(x = 1:3, y = ['a', 'b', 'c'])
+                df[-1, ]$x
+            "}},
+            r! { [2, 3] }
+        );
+    }
+
+    #[test]
+    fn data_frame_column_assignment() {
+        assert_eq!(
+            r! {{"
+                df <- data.frame(x = 1:3)
+                df$y <- df$x * 2
+                df[['z']] <- 0
+                df$y + df$z
+            "}},
+            r! { [2, 4, 6] }
+        );
+    }
+
+    #[test]
+    fn data_frame_column_assignment_checks_length() {
+        assert_eq!(
+            r! {{"
+                df <- data.frame(x = 1:3)
+                df$y <- [1, 2]
+            "}},
+            Error::Other("replacement has 2 rows, data has 3".to_string()).into()
+        );
+    }
+
+    #[test]
+    fn data_frame_column_removal() {
+        r_expect! {{"
+            df <- data.frame(x = 1:3, y = 1:3)
+            df$x <- null
+            ncol(df) == 1
+        "}}
+    }
+
+    #[test]
+    fn head_and_tail() {
+        assert_eq!(r! { head(1:10, 3) }, r! { [1, 2, 3] });
+        assert_eq!(r! { tail(1:10, 3) }, r! { [8, 9, 10] });
+        assert_eq!(r! { head(1:10, -8) }, r! { [1, 2] });
+        assert_eq!(r! { tail(1:10, -8) }, r! { [9, 10] });
+        assert_eq!(r! { nrow(head(data.frame(x = 1:10))) }, r! { 6L });
+        assert_eq!(r! { tail(data.frame(x = 1:10), 2)$x }, r! { [9, 10] });
+    }
+
+    #[test]
+    fn data_frame_printing() {
+        assert_eq!(
+            format!(
+                "{}",
+                r! { data.frame(x = [1, 10], name = ["a", "bc"]) }.unwrap()
+            ),
+            "   x name\n1  1    a\n2 10   bc"
+        );
+    }
+
+    #[test]
+    fn data_frame_printing_truncates_columns() {
+        let df = r! {{"
+            data.frame(
+                a_very_long_column_name_number_one = 1,
+                a_very_long_column_name_number_two = 2,
+                a_very_long_column_name_number_three = 3
+            )
+        "}}
+        .unwrap();
+
+        assert!(format!("{df}")
+            .ends_with("[ omitting 1 columns: a_very_long_column_name_number_three ]"));
+    }
+
+    #[test]
+    fn data_frame_printing_truncates_rows() {
+        let df = r! { data.frame(x = 1:25) }.unwrap();
+        assert!(format!("{df}").ends_with("20 20\n[ omitting 5 rows ]"));
+    }
+}
@@ -0,0 +1,226 @@
+use r_derive::*;
+use std::ops::ControlFlow;
+
+use crate::callable::core::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Get the Dimensions of an Object
+///
+/// Returns the extent of each dimension of a matrix or array, or `null`
+/// for objects without dimensions. Data frames have a row and a column
+/// dimension.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// dim(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object whose dimensions to retrieve.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// dim(matrix(1:6, nrow = 2))
+/// ```
+///
+#[doc(alias = "dim")]
+#[builtin(sym = "dim")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveDim;
+
+formals!(PrimitiveDim, "(x)");
+
+impl Callable for PrimitiveDim {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = match dispatch_first("dim", args.try_get_named("x")?, &ellipsis, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(x) => x,
+        };
+
+        match x.dim() {
+            Some(dim) => Ok(Obj::Vector(
+                dim.into_iter()
+                    .map(|i| i as i32)
+                    .collect::<Vec<i32>>()
+                    .into(),
+            )),
+            None => Ok(Obj::Null),
+        }
+    }
+}
+
+/// Set the Dimensions of an Object
+///
+/// The replacement function used by `dim(x) <- value`, turning a vector into
+/// a matrix or array. The product of the dimensions must equal the length
+/// of the vector. A `null` value removes the dimensions.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// dim(x) <- value
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A vector whose dimensions to set.
+/// * `value`: An `integer` vector of extents, or `null`.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// x <- 1:6
+/// dim(x) <- [3, 2]
+/// x
+/// ```
+///
+#[doc(alias = "dim<-")]
+#[builtin(sym = "dim<-")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveDimAssign;
+
+formals!(PrimitiveDimAssign, "(x, value)");
+
+impl Callable for PrimitiveDimAssign {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = args.try_get_named("x")?.force(stack)?;
+        let value = args.try_get_named("value")?.force(stack)?;
+        x.set_attr("dim", value)
+    }
+}
+
+/// Count the Rows of a Matrix
+///
+/// Returns the extent of the first dimension, or `null` for objects without
+/// dimensions.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// nrow(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A matrix or array.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// nrow(matrix(1:6, nrow = 2))
+/// ```
+///
+#[doc(alias = "nrow")]
+#[builtin(sym = "nrow")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveNrow;
+
+formals!(PrimitiveNrow, "(x)");
+
+impl Callable for PrimitiveNrow {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+        Ok(extent(&x, 0))
+    }
+}
+
+/// Count the Columns of a Matrix
+///
+/// Returns the extent of the second dimension, or `null` for objects
+/// without at least two dimensions.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// ncol(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A matrix or array.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// ncol(matrix(1:6, nrow = 2))
+/// ```
+///
+#[doc(alias = "ncol")]
+#[builtin(sym = "ncol")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveNcol;
+
+formals!(PrimitiveNcol, "(x)");
+
+impl Callable for PrimitiveNcol {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+        Ok(extent(&x, 1))
+    }
+}
+
+fn extent(x: &Obj, dimension: usize) -> Obj {
+    match x.dim().and_then(|dim| dim.get(dimension).copied()) {
+        Some(n) => Obj::Vector(vec![n as i32].into()),
+        None => Obj::Null,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::{r, r_expect};
+
+    #[test]
+    fn dim_of_vector_is_null() {
+        assert_eq!(r! { dim([1, 2, 3]) }, r! { null });
+    }
+
+    #[test]
+    fn dim_assignment() {
+        r_expect! {{"
+            x <- 1:6
+            dim(x) <- [3, 2]
+            nrow(x) == 3 && ncol(x) == 2
+        "}}
+    }
+
+    #[test]
+    fn dim_must_match_length() {
+        assert_eq!(
+            r! {{"
+                x <- 1:6
+                dim(x) <- [4, 2]
+            "}},
+            Error::Other("dims [product 8] do not match the length of object [6]".to_string())
+                .into()
+        );
+    }
+
+    #[test]
+    fn removing_dims() {
+        assert_eq!(
+            r! {{"
+                x <- matrix(1:4, nrow = 2)
+                dim(x) <- null
+                x
+            "}},
+            r! { 1:4 }
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use r_derive::*;
+use std::ops::ControlFlow;
+
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::coercion::factor_codes;
+use crate::object::types::*;
+use crate::object::*;
+
+/// Encode a Vector as a Factor
+///
+/// Factors represent categorical data as `integer` codes into a set of
+/// levels. By default, the levels are the sorted unique values of `x`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// factor(x, levels, labels)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A vector of values to encode.
+/// * `levels`: An optional vector of the values which `x` may take. Values
+///   of `x` which are not one of the `levels` are encoded as `NA`.
+/// * `labels`: An optional `character` vector of labels to use in place of
+///   `levels`, of the same length as `levels`.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// factor(["b", "a", "b"])
+/// factor(["lo", "hi"], levels = ["lo", "hi"])
+/// ```
+///
+#[doc(alias = "factor")]
+#[builtin(sym = "factor")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveFactor;
+
+formals!(PrimitiveFactor, "(x, levels, labels)");
+
+impl Callable for PrimitiveFactor {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = args.try_get_named("x")?.force(stack)?;
+        let (values, default_levels) = factor_values(x)?;
+
+        let levels = match args.try_get_named("levels") {
+            Ok(Obj::Promise(_, Expr::Missing, _)) | Err(_) => default_levels,
+            Ok(levels) => characters(levels.force(stack)?)?,
+        };
+
+        let labels = match args.try_get_named("labels") {
+            Ok(Obj::Promise(_, Expr::Missing, _)) | Err(_) => levels.clone(),
+            Ok(labels) => characters(labels.force(stack)?)?,
+        };
+
+        if labels.len() != levels.len() {
+            return Error::Other(format!(
+                "invalid 'labels'; length {} should be {}",
+                labels.len(),
+                levels.len()
+            ))
+            .into();
+        }
+
+        new_factor(factor_codes(&values, &levels), labels)
+    }
+}
+
+/// Get the Levels of a Factor
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// levels(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object whose levels to retrieve.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// levels(factor(["b", "a", "b"]))
+/// ```
+///
+#[doc(alias = "levels")]
+#[builtin(sym = "levels")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveLevels;
+
+formals!(PrimitiveLevels, "(x)");
+
+impl Callable for PrimitiveLevels {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = match dispatch_first("levels", args.try_get_named("x")?, &ellipsis, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(x) => x,
+        };
+
+        Ok(x.get_attr("levels").unwrap_or(Obj::Null))
+    }
+}
+
+/// Count the Levels of a Factor
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// nlevels(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object whose levels to count.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// nlevels(factor(["b", "a", "b"]))
+/// ```
+///
+#[doc(alias = "nlevels")]
+#[builtin(sym = "nlevels")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveNlevels;
+
+formals!(PrimitiveNlevels, "(x)");
+
+impl Callable for PrimitiveNlevels {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+        let n = match x.get_attr("levels") {
+            Some(levels) => levels.len().unwrap_or_default(),
+            None => 0,
+        };
+
+        Ok(Obj::Vector(vec![n as i32].into()))
+    }
+}
+
+/// Drop Unused Levels of a Factor
+///
+/// Removes levels which do not appear in a factor, preserving the order of
+/// the remaining levels.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// droplevels(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A factor.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// x <- factor(["a", "b", "c"])
+/// droplevels(x[1:2])
+/// ```
+///
+#[doc(alias = "droplevels")]
+#[builtin(sym = "droplevels")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveDroplevels;
+
+formals!(PrimitiveDroplevels, "(x)");
+
+impl Callable for PrimitiveDroplevels {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = match dispatch_first("droplevels", args.try_get_named("x")?, &ellipsis, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(x) => x,
+        };
+
+        if !x.is_factor() {
+            return Ok(x);
+        }
+
+        let (values, levels) = factor_values(x)?;
+        new_factor(factor_codes(&values, &levels), levels)
+    }
+}
+
+/// The values of a vector, as characters, and their default factor levels
+///
+/// For factors, the default levels are the levels which are used, in their
+/// original order. Otherwise, they are the sorted unique non-`NA` values.
+pub(super) fn factor_values(x: Obj) -> Result<(Vec<Character>, Vec<Character>), Signal> {
+    let v = match x {
+        Obj::Vector(v) if v.is_unbounded() => return Error::UnboundedSequence.into(),
+        Obj::Vector(v) => v,
+        Obj::Null => return Ok((vec![], vec![])),
+        _ => {
+            return Error::Other("'x' must be an atomic vector to be used as a factor".into())
+                .into()
+        }
+    };
+
+    let values: Vec<Character> = match v.clone().as_character() {
+        Vector::Character(values) => values.iter_values().collect(),
+        _ => unreachable!(),
+    };
+
+    let levels = match v {
+        v if v.factor_levels().is_some() => {
+            let levels = v.factor_levels().unwrap_or_default();
+            levels
+                .into_iter()
+                .filter(|level| values.contains(level))
+                .collect()
+        }
+        Vector::Character(_) => {
+            let mut levels: Vec<Str> = values
+                .iter()
+                .filter_map(|value| match value {
+                    OptionNA::Some(value) => Some(value.clone()),
+                    OptionNA::NA => None,
+                })
+                .collect();
+            levels.sort();
+            levels.dedup();
+            levels.into_iter().map(OptionNA::Some).collect()
+        }
+        // complex values are ordered by their real, then imaginary parts
+        Vector::Complex(v) => {
+            let mut levels: Vec<Complex64> = v
+                .iter_values()
+                .filter_map(|value| match value {
+                    OptionNA::Some(value) => Some(value),
+                    OptionNA::NA => None,
+                })
+                .collect();
+            levels.sort_by(|a, b| a.re.total_cmp(&b.re).then(a.im.total_cmp(&b.im)));
+            levels.dedup();
+
+            let levels: Vec<Complex> = levels.into_iter().map(OptionNA::Some).collect();
+            match Vector::from(levels).as_character() {
+                Vector::Character(levels) => levels.iter_values().collect(),
+                _ => unreachable!(),
+            }
+        }
+        v => {
+            let mut levels: Vec<f64> = match v.as_double() {
+                Vector::Double(v) => v
+                    .iter_values()
+                    .filter_map(|value| match value {
+                        OptionNA::Some(value) => Some(value),
+                        OptionNA::NA => None,
+                    })
+                    .collect(),
+                _ => unreachable!(),
+            };
+            levels.sort_by(f64::total_cmp);
+            levels.dedup();
+
+            let levels: Vec<Double> = levels.into_iter().map(OptionNA::Some).collect();
+            match Vector::from(levels).as_character() {
+                Vector::Character(levels) => levels.iter_values().collect(),
+                _ => unreachable!(),
+            }
+        }
+    };
+
+    Ok((values, levels))
+}
+
+/// Build a factor from its codes and levels
+pub(super) fn new_factor(codes: Vec<Integer>, levels: Vec<Character>) -> EvalResult {
+    Obj::Vector(Vector::from(codes))
+        .set_attr("levels", Obj::Vector(Vector::from(levels)))?
+        .set_attr("class", Obj::Vector(vec!["factor".to_string()].into()))
+}
+
+fn characters(x: Obj) -> Result<Vec<Character>, Signal> {
+    match x {
+        Obj::Null => Ok(vec![]),
+        Obj::Vector(v) if v.is_unbounded() => Error::UnboundedSequence.into(),
+        Obj::Vector(v) => match v.as_character() {
+            Vector::Character(v) => Ok(v.iter_values().collect()),
+            _ => unreachable!(),
+        },
+        _ => Error::Other("levels and labels must be atomic vectors".to_string()).into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{r, r_expect};
+
+    #[test]
+    fn factor_levels_are_sorted_unique_values() {
+        assert_eq!(
+            r! { levels(factor(["b", "a", "c", "a"])) },
+            r! { ["a", "b", "c"] }
+        );
+        assert_eq!(r! { levels(factor([10, 2, 10])) }, r! { ["2", "10"] });
+    }
+
+    #[test]
+    fn factor_of_complex_values() {
+        assert_eq!(
+            r! {{"levels(factor(c(2i, 1+1i, 1, 2i)))"}},
+            r! { ["0+2i", "1+0i", "1+1i"] }
+        );
+        assert_eq!(r! {{"as.integer(factor(c(1i, 1)))"}}, r! {{"c(1L, 2L)"}});
+    }
+
+    #[test]
+    fn factor_with_explicit_levels() {
+        r_expect! {{"
+            x <- factor(['lo', 'hi', 'mid'], levels = ['lo', 'mid', 'hi'])
+            nlevels(x) == 3 && as.integer(x)[[3]] == 2
+        "}}
+    }
+
+    #[test]
+    fn factor_with_labels() {
+        assert_eq!(
+            r! { as.character(factor([1, 2, 1], levels = [1, 2], labels = ["one", "two"])) },
+            r! { ["one", "two", "one"] }
+        );
+    }
+
+    #[test]
+    fn factor_codes_values_outside_levels_as_na() {
+        assert_eq!(
+            r! { as.character(factor(["a", "z"], levels = ["a", "b"])) },
+            r! { c("a", na) }
+        );
+    }
+
+    #[test]
+    fn subsetting_preserves_levels() {
+        assert_eq!(
+            r! {{"
+                x <- factor(['a', 'b', 'c'])
+                levels(x[2])
+            "}},
+            r! { ["a", "b", "c"] }
+        );
+    }
+
+    #[test]
+    fn droplevels_removes_unused_levels() {
+        assert_eq!(
+            r! {{"
+                x <- factor(['c', 'b', 'a'], levels = ['c', 'b', 'a'])
+                levels(droplevels(x[c(1, 3)]))
+            "}},
+            r! { ["c", "a"] }
+        );
+    }
+
+    #[test]
+    fn assigning_levels_into_factor() {
+        assert_eq!(
+            r! {{"
+                x <- factor(['a', 'b', 'a'])
+                x[2] <- 'a'
+                as.character(x)
+            "}},
+            r! { ["a", "a", "a"] }
+        );
+    }
+
+    #[test]
+    fn assigning_invalid_levels_produces_na() {
+        assert_eq!(
+            r! {{"
+                x <- factor(['a', 'b'])
+                x[1] <- 'z'
+                as.character(x)
+            "}},
+            r! { c(na, "b") }
+        );
+    }
+
+    #[test]
+    fn factor_printing() {
+        assert_eq!(
+            format!("{}", r! { factor(["lo", "high", "lo"]) }.unwrap()),
+            "[1] lo   high lo\nLevels: high lo"
+        );
+    }
+}
@@ -0,0 +1,97 @@
+use r_derive::*;
+use std::ops::ControlFlow;
+
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Format an Object as Strings
+///
+/// Converts the elements of a vector into a `character` vector of a common
+/// width, as they would be printed. Numbers are right-aligned and strings
+/// are left-aligned. Other objects are formatted as a single string.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// format(x, ...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object to format.
+/// * `...`: Additional arguments passed on to methods.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// format([1, 10, 100])
+/// ```
+///
+#[doc(alias = "format")]
+#[builtin(sym = "format")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveFormat;
+
+formals!(PrimitiveFormat, "(x, ...)");
+
+impl Callable for PrimitiveFormat {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = match dispatch_first("format", args.try_get_named("x")?, &ellipsis, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(x) => x,
+        };
+
+        let v = match x {
+            Obj::Vector(v) if v.is_unbounded() => return Error::UnboundedSequence.into(),
+            Obj::Vector(v) => v,
+            x => return Ok(Obj::Vector(vec![format!("{x}")].into())),
+        };
+
+        let names = v.names();
+        let left_align = matches!(v, Vector::Character(_));
+        let strs: Vec<String> = v.into();
+        let width = strs.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+
+        let strs: Vec<String> = strs
+            .into_iter()
+            .map(|s| match left_align {
+                true => format!("{s:<width$}"),
+                false => format!("{s:>width$}"),
+            })
+            .collect();
+
+        let formatted = Obj::Vector(strs.into());
+        match names {
+            Some(names) => formatted.set_attr("names", Obj::Vector(names.into())),
+            None => Ok(formatted),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn pads_to_common_width() {
+        assert_eq!(r! { format([1, 10, 100]) }, r! { ["  1", " 10", "100"] });
+        assert_eq!(r! { format(["a", "bbb"]) }, r! { ["a  ", "bbb"] });
+    }
+
+    #[test]
+    fn dispatches_on_class() {
+        assert_eq!(
+            r! {{"
+                format.money <- function(x, ...) paste('$', unclass(x))
+                format(structure(5, class = 'money'))
+            "}},
+            r! { "$ 5" }
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::error::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Invoke a Restart
+///
+/// Transfers control to the most recently established restart with the
+/// given name, passing along any additional arguments.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// invokeRestart(r, ...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `r`: The name of the restart to invoke.
+/// * `...`: Arguments passed to the restart function.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// withRestarts(invokeRestart("retry", 3), retry = function(n) n)
+/// ```
+///
+#[doc(alias = "invokeRestart")]
+#[builtin(sym = "invokeRestart")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveInvokeRestart;
+
+formals!(PrimitiveInvokeRestart, "(r, ...)");
+
+impl Callable for PrimitiveInvokeRestart {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let name: String = match Obj::List(args).try_get_named("r")?.force(stack)? {
+            Obj::Vector(v) if v.len() == 1 => v.into(),
+            _ => return Error::ArgumentInvalid("r".to_string()).into(),
+        };
+
+        let Some(restart) = stack.restarts.iter().rev().find(|r| r.name == name) else {
+            return Error::Other(format!("no 'restart' '{name}' found")).into();
+        };
+
+        let frame = restart.frame;
+        let args = List::from(force_promises(ellipsis, stack)?);
+        Cond::Restart(frame, name, args).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::r;
+
+    #[test]
+    fn missing_restarts_raise_errors() {
+        assert_eq!(
+            r! { invokeRestart("nope") },
+            Error::Other("no 'restart' 'nope' found".to_string()).into()
+        );
+    }
+}
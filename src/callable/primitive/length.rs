@@ -1,4 +1,5 @@
 use r_derive::*;
+use std::ops::ControlFlow;
 
 use crate::callable::core::*;
 use crate::error::Error;
@@ -36,15 +37,24 @@ pub struct PrimitiveLength;
 formals!(PrimitiveLength, "(x,)");
 
 impl Callable for PrimitiveLength {
-    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
         let mut args = Obj::List(args);
-        let x = args.try_get_named("x")?.force(stack)?;
+        let x = match dispatch_first("length", args.try_get_named("x")?, &ellipsis, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(x) => x,
+        };
 
         let length: usize = match x {
+            // unbounded sequences have infinite length
+            Obj::Vector(ref vec) if vec.is_unbounded() => {
+                return Ok(Obj::Vector(Vector::from(vec![f64::INFINITY])));
+            }
             Obj::Vector(ref vec) => match vec {
                 Vector::Double(rep) => rep.len(),
                 Vector::Integer(rep) => rep.len(),
                 Vector::Logical(rep) => rep.len(),
+                Vector::Complex(rep) => rep.len(),
+                Vector::Raw(rep) => rep.len(),
                 Vector::Character(rep) => rep.len(),
             },
             Obj::List(rep) => rep.len(),
@@ -0,0 +1,448 @@
+use r_derive::*;
+use std::ops::ControlFlow;
+
+use super::PrimitiveC;
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::types::*;
+use crate::object::*;
+
+/// Create a Matrix
+///
+/// Arranges values into a matrix of the given number of rows and columns,
+/// filling the matrix by column unless `byrow` is `true`. Values are
+/// recycled to fill the matrix. When only one of `nrow` or `ncol` is
+/// given, the other is chosen to fit all values.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// matrix(data = na, nrow, ncol, byrow = false)
+/// ```
+///
+/// ## Arguments
+///
+/// * `data`: A vector of values.
+/// * `nrow`: The number of rows.
+/// * `ncol`: The number of columns.
+/// * `byrow`: Whether to fill the matrix by row.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// matrix(1:6, nrow = 2)
+/// matrix(1:6, ncol = 2, byrow = true)
+/// ```
+///
+#[doc(alias = "matrix")]
+#[builtin(sym = "matrix")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveMatrix;
+
+formals!(PrimitiveMatrix, "(data = na, nrow, ncol, byrow = false)");
+
+impl Callable for PrimitiveMatrix {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let data = vector_data(args.try_get_named("data")?.force(stack)?)?;
+        let byrow: bool = args.try_get_named("byrow")?.force(stack)?.try_into()?;
+
+        let len = data.len().unwrap_or_default();
+        let nrow = optional_extent(&mut args, "nrow", stack)?;
+        let ncol = optional_extent(&mut args, "ncol", stack)?;
+
+        let (nrow, ncol) = match (nrow, ncol) {
+            (None, None) => (len, 1),
+            (Some(nrow), None) => (nrow, len.div_ceil(nrow.max(1))),
+            (None, Some(ncol)) => (len.div_ceil(ncol.max(1)), ncol),
+            (Some(nrow), Some(ncol)) => (nrow, ncol),
+        };
+
+        let positions = (0..ncol).flat_map(|j| {
+            (0..nrow).map(move |i| match byrow {
+                true => i * ncol + j,
+                false => i + j * nrow,
+            })
+        });
+
+        recycle(&data, positions)?.set_attr("dim", extents(&[nrow, ncol]))
+    }
+}
+
+/// Create an Array
+///
+/// Arranges values into an array with the given dimensions, filling the
+/// first dimension fastest. Values are recycled to fill the array.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// array(data = na, dim)
+/// ```
+///
+/// ## Arguments
+///
+/// * `data`: A vector of values.
+/// * `dim`: An `integer` vector giving the extent of each dimension.
+///   Defaults to the length of `data`.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// array(1:12, [2, 3, 2])
+/// ```
+///
+#[doc(alias = "array")]
+#[builtin(sym = "array")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveArray;
+
+formals!(PrimitiveArray, "(data = na, dim)");
+
+impl Callable for PrimitiveArray {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let data = vector_data(args.try_get_named("data")?.force(stack)?)?;
+
+        let dim = match args.try_get_named("dim") {
+            Ok(Obj::Promise(_, Expr::Missing, _)) | Err(_) => {
+                extents(&[data.len().unwrap_or_default()])
+            }
+            Ok(dim) => dim.force(stack)?,
+        };
+
+        let Obj::Vector(Vector::Integer(extents)) = dim.clone().as_integer()? else {
+            return Error::Other("'dim' must be an integer vector".to_string()).into();
+        };
+
+        let n = extents
+            .iter_values()
+            .map(|i| match i {
+                OptionNA::Some(i) if i >= 0 => Ok(i as usize),
+                _ => Err(Error::Other(
+                    "negative length vectors are not allowed".to_string(),
+                )),
+            })
+            .product::<Result<usize, Error>>()?;
+
+        recycle(&data, 0..n)?.set_attr("dim", dim)
+    }
+}
+
+/// Transpose a Matrix
+///
+/// Swaps the rows and columns of a matrix. A vector is treated as a single
+/// column, producing a matrix with a single row.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// t(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A matrix or vector.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// t(matrix(1:6, nrow = 2))
+/// ```
+///
+#[doc(alias = "t")]
+#[builtin(sym = "t")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveT;
+
+formals!(PrimitiveT, "(x)");
+
+impl Callable for PrimitiveT {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        match dispatch_first("t", args.try_get_named("x")?, &ellipsis, stack)? {
+            ControlFlow::Break(result) => Ok(result),
+            ControlFlow::Continue(x) => x.transpose(),
+        }
+    }
+}
+
+/// Combine Values by Columns
+///
+/// Combines vectors and matrices into a matrix, with each vector forming a
+/// column and each matrix contributing its columns. Vectors are recycled to
+/// the number of rows.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// cbind(...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `...`: Vectors or matrices to combine.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// cbind(1:3, 4:6)
+/// ```
+///
+#[doc(alias = "cbind")]
+#[builtin(sym = "cbind")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveCbind;
+
+formals!(PrimitiveCbind, "(...)");
+
+impl Callable for PrimitiveCbind {
+    fn call_matched(&self, _args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let values = force_promises(ellipsis, stack)?;
+        bind_columns(values.into_iter().map(|(_, v)| v).collect())
+    }
+}
+
+/// Combine Values by Rows
+///
+/// Combines vectors and matrices into a matrix, with each vector forming a
+/// row and each matrix contributing its rows. Vectors are recycled to the
+/// number of columns.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// rbind(...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `...`: Vectors or matrices to combine.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// rbind(1:3, 4:6)
+/// ```
+///
+#[doc(alias = "rbind")]
+#[builtin(sym = "rbind")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveRbind;
+
+formals!(PrimitiveRbind, "(...)");
+
+impl Callable for PrimitiveRbind {
+    fn call_matched(&self, _args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        // binding rows is binding the columns of transposed matrices
+        let values = force_promises(ellipsis, stack)?
+            .into_iter()
+            .map(|(_, v)| match v.dim() {
+                Some(_) => v.transpose(),
+                None => Ok(v),
+            })
+            .collect::<Result<Vec<Obj>, Signal>>()?;
+
+        match bind_columns(values)? {
+            Obj::Null => Ok(Obj::Null),
+            x => x.transpose(),
+        }
+    }
+}
+
+fn bind_columns(values: Vec<Obj>) -> EvalResult {
+    if values
+        .iter()
+        .any(|v| matches!(v, Obj::Vector(v) if v.is_unbounded()))
+    {
+        return Error::UnboundedSequence.into();
+    }
+
+    let values: Vec<Obj> = values
+        .into_iter()
+        .filter(|v| v.len().is_some_and(|n| n > 0))
+        .collect();
+
+    let mut nrow = None;
+    for (i, value) in values.iter().enumerate() {
+        match (value.dim().as_deref(), nrow) {
+            (Some(&[n, _]), Some(m)) if n != m => {
+                return Error::Other(format!(
+                    "number of rows of matrices must match (see arg {})",
+                    i + 1
+                ))
+                .into()
+            }
+            (Some(&[n, _]), _) => nrow = Some(n),
+            (Some(_), _) => return Error::Other("cannot bind arrays".to_string()).into(),
+            (None, _) => (),
+        }
+    }
+
+    let nrow = match nrow {
+        Some(n) => n,
+        None => match values.iter().filter_map(Obj::len).max() {
+            Some(n) => n,
+            None => return Ok(Obj::Null),
+        },
+    };
+
+    let mut ncol = 0;
+    let mut columns = Vec::with_capacity(values.len());
+    for value in values {
+        let value = match value.dim() {
+            Some(dim) => {
+                ncol += dim[1];
+                value.with_attributes(None)
+            }
+            None => {
+                ncol += 1;
+                recycle(&value, 0..nrow)?
+            }
+        };
+
+        columns.push((Character::NA, value));
+    }
+
+    PrimitiveC::combine(List::from(columns))?.set_attr("dim", extents(&[nrow, ncol]))
+}
+
+fn vector_data(data: Obj) -> EvalResult {
+    match data {
+        Obj::Vector(v) if v.is_unbounded() => Error::UnboundedSequence.into(),
+        Obj::Vector(v) => Ok(Obj::Vector(v.without_names())),
+        Obj::List(l) => Ok(Obj::List(l.without_names())),
+        _ => Error::Other("'data' must be of a vector type".to_string()).into(),
+    }
+}
+
+/// Take values of a vector by position, recycling the vector as needed
+fn recycle(x: &Obj, positions: impl Iterator<Item = usize>) -> EvalResult {
+    let len = x.len().unwrap_or_default();
+    let indices: Vec<Integer> = positions
+        .map(|i| match len {
+            0 => OptionNA::NA,
+            _ => OptionNA::Some((i % len) as i32 + 1),
+        })
+        .collect();
+
+    x.clone()
+        .with_attributes(None)
+        .try_get(Obj::Vector(indices.into()))
+}
+
+fn optional_extent(
+    args: &mut Obj,
+    name: &str,
+    stack: &mut CallStack,
+) -> Result<Option<usize>, Signal> {
+    match args.try_get_named(name) {
+        Ok(Obj::Promise(_, Expr::Missing, _)) | Err(_) => Ok(None),
+        Ok(x) => {
+            let n: i32 = x.force(stack)?.try_into()?;
+            if n < 0 {
+                return Error::Other(format!("invalid '{name}' value (< 0)")).into();
+            }
+            Ok(Some(n as usize))
+        }
+    }
+}
+
+fn extents(extents: &[usize]) -> Obj {
+    Obj::Vector(
+        extents
+            .iter()
+            .map(|&i| i as i32)
+            .collect::<Vec<i32>>()
+            .into(),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::{r, r_expect};
+
+    #[test]
+    fn matrix_fills_by_column() {
+        r_expect! {{"
+            m <- matrix(1:6, nrow = 2)
+            m[1, 2] == 3 && m[2, 3] == 6
+        "}}
+    }
+
+    #[test]
+    fn matrix_fills_by_row() {
+        r_expect! {{"
+            m <- matrix(1:6, ncol = 3, byrow = true)
+            m[1, 2] == 2 && m[2, 1] == 4
+        "}}
+    }
+
+    #[test]
+    fn matrix_recycles_data() {
+        assert_eq!(r! { c(matrix(0, 2, 2)) }, r! { [0, 0, 0, 0] });
+    }
+
+    #[test]
+    fn matrix_class() {
+        assert_eq!(r! { class(matrix(1:4, 2)) }, r! { ["matrix", "array"] });
+    }
+
+    #[test]
+    fn array_dims() {
+        r_expect! {{"
+            a <- array(1:24, [2, 3, 4])
+            a[2, 3, 4] == 24 && length(dim(a)) == 3 && class(a) == 'array'
+        "}}
+    }
+
+    #[test]
+    fn transpose() {
+        r_expect! {{"
+            m <- t(matrix(1:6, nrow = 2))
+            nrow(m) == 3 && ncol(m) == 2 && m[3, 1] == 5
+        "}}
+    }
+
+    #[test]
+    fn transpose_vector() {
+        assert_eq!(r! { dim(t(1:3)) }, r! { [1L, 3L] });
+    }
+
+    #[test]
+    fn cbind_vectors_and_matrices() {
+        assert_eq!(
+            r! { cbind(matrix(1:4, 2), [5, 6], 0) },
+            r! { matrix([1, 2, 3, 4, 5, 6, 0, 0], nrow = 2) }
+        );
+    }
+
+    #[test]
+    fn rbind_vectors() {
+        assert_eq!(
+            r! { rbind(1:3, 4:6) },
+            r! { matrix(1:6, nrow = 2, byrow = true) }
+        );
+    }
+
+    #[test]
+    fn cbind_requires_matching_rows() {
+        assert_eq!(
+            r! { cbind(matrix(1:4, 2), matrix(1:3, 3)) },
+            Error::Other("number of rows of matrices must match (see arg 2)".to_string()).into()
+        );
+    }
+}
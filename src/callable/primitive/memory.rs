@@ -0,0 +1,125 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::types::{Double, Integer};
+use crate::object::*;
+
+/// Garbage Collection
+///
+/// Collect unreachable cycles of environments, such as the environment of a
+/// function call which binds a closure that was created within it.
+/// Collections are also triggered as new environments are created.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// gc()
+/// ```
+///
+/// ## Value
+///
+/// A named `integer` vector, with the number of environments `reclaimed`
+/// since the last call to `gc()`, the number of `environments` that are
+/// still alive and the number of distinct `strings` held by character
+/// vectors.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// f <- function() { g <- function() 1; g }
+/// f()
+/// gc()
+/// ```
+///
+#[doc(alias = "gc")]
+#[builtin(sym = "gc")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveGc;
+
+formals!(PrimitiveGc, "()");
+
+impl Callable for PrimitiveGc {
+    fn call_matched(&self, _args: List, _ellipsis: List, _stack: &mut CallStack) -> EvalResult {
+        let Collection { reclaimed, environments } = collect();
+        let stats = vec![
+            (
+                Some("reclaimed".to_string()),
+                Integer::Some(reclaimed as i32),
+            ),
+            (
+                Some("environments".to_string()),
+                Integer::Some(environments as i32),
+            ),
+            (
+                Some("strings".to_string()),
+                Integer::Some(Str::pool_size() as i32),
+            ),
+        ];
+
+        Ok(Obj::Vector(Vector::Integer(stats.into())))
+    }
+}
+
+/// Object Size
+///
+/// Approximate the memory used by an object in bytes, including data that is
+/// shared with other objects. Values bound in environments are not counted.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// object.size(x)
+/// ```
+///
+/// ## Arguments
+///
+/// `x`: An object whose size to approximate.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// object.size(c(1, 2, 3))
+/// object.size(list(a = 1, b = "text"))
+/// ```
+///
+#[doc(alias = "object.size")]
+#[builtin(sym = "object.size")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveObjectSize;
+
+formals!(PrimitiveObjectSize, "(x,)");
+
+impl Callable for PrimitiveObjectSize {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = args.try_get_named("x")?.force(stack)?;
+        let size = object_size(&x) as f64;
+        Ok(Obj::Vector(Vector::from(vec![Double::Some(size)])))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{r, r_expect};
+
+    #[test]
+    fn gc_reports_environments() {
+        r_expect! {{"
+            x <- gc()
+            x[['environments']] > 0
+        "}}
+    }
+
+    #[test]
+    fn object_size_counts_lists() {
+        r_expect! {{"
+            object.size(list(1, 2)) > object.size(list(1))
+        "}}
+    }
+}
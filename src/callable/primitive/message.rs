@@ -0,0 +1,74 @@
+use r_derive::*;
+use std::io::Write;
+
+use crate::callable::core::*;
+use crate::error::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+use super::stop::condition_from_args;
+use super::warning::signal_with_muffle;
+
+/// Signal a Message
+///
+/// Signals a message condition. If no handler unwinds the stack or invokes
+/// the `muffleMessage` restart, the message is printed.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// message(...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `...`: Either a single condition object, or objects which are pasted
+///   together without separator to form the message.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// message("Hello, ", "World!")
+/// ```
+///
+#[doc(alias = "message")]
+#[builtin(sym = "message")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveMessage;
+
+formals!(PrimitiveMessage, "(...)");
+
+impl Callable for PrimitiveMessage {
+    fn call_matched(&self, _args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let call = stack.frames[stack.env_frame()].call.clone();
+        let cond = condition_from_args(ellipsis, call, "simpleMessage", "message", stack)?;
+        let message = condition_message(&cond);
+
+        if signal_with_muffle(cond, "muffleMessage", stack)? {
+            writeln!(stack.session.output, "{message}").ok();
+        }
+
+        Ok(Obj::Null)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn messages_can_be_caught() {
+        assert_eq!(
+            r! { tryCatch(message("a", "b"), message = function(m) conditionMessage(m)) },
+            r! { "ab" }
+        );
+    }
+
+    #[test]
+    fn messages_return_null() {
+        assert_eq!(r! { message("hi") }, r! { null });
+    }
+}
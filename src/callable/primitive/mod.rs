@@ -34,3 +34,63 @@ mod is_null;
 pub use is_null::PrimitiveIsNull;
 mod type_reflection;
 pub use type_reflection::PrimitiveTypeOf;
+mod stop;
+pub use stop::PrimitiveStop;
+mod warning;
+pub use warning::{warn, PrimitiveWarning};
+mod message;
+pub use message::PrimitiveMessage;
+mod signal_condition;
+pub use signal_condition::PrimitiveSignalCondition;
+mod try_catch;
+pub use try_catch::PrimitiveTryCatch;
+mod with_calling_handlers;
+pub use with_calling_handlers::PrimitiveWithCallingHandlers;
+mod with_restarts;
+pub use with_restarts::PrimitiveWithRestarts;
+mod invoke_restart;
+pub use invoke_restart::PrimitiveInvokeRestart;
+mod condition_message;
+pub use condition_message::{PrimitiveConditionCall, PrimitiveConditionMessage};
+mod on_exit;
+pub use on_exit::PrimitiveOnExit;
+mod attr;
+pub use attr::{PrimitiveAttr, PrimitiveAttrAssign};
+mod attributes;
+pub use attributes::PrimitiveAttributes;
+mod structure;
+pub use structure::PrimitiveStructure;
+mod use_method;
+pub use use_method::{PrimitiveNextMethod, PrimitiveUseMethod};
+mod class;
+pub use class::{PrimitiveClass, PrimitiveClassAssign, PrimitiveInherits, PrimitiveUnclass};
+mod format;
+pub use format::PrimitiveFormat;
+mod dim;
+pub use dim::{PrimitiveDim, PrimitiveDimAssign, PrimitiveNcol, PrimitiveNrow};
+mod matrix;
+pub use matrix::{PrimitiveArray, PrimitiveCbind, PrimitiveMatrix, PrimitiveRbind, PrimitiveT};
+mod factor;
+pub use factor::{PrimitiveDroplevels, PrimitiveFactor, PrimitiveLevels, PrimitiveNlevels};
+mod table;
+pub use table::PrimitiveTable;
+mod coerce;
+pub use coerce::{
+    PrimitiveAsCharacter, PrimitiveAsComplex, PrimitiveAsDouble, PrimitiveAsInteger,
+    PrimitiveAsLogical,
+};
+mod data_frame;
+pub use data_frame::{PrimitiveDataFrame, PrimitiveHead, PrimitiveTail};
+mod complex;
+pub use complex::{
+    PrimitiveArg, PrimitiveConj, PrimitiveIm, PrimitiveMod, PrimitiveRe, PrimitiveSqrt,
+};
+mod raw;
+pub use raw::{
+    PrimitiveAsRaw, PrimitiveCharToRaw, PrimitiveRawShift, PrimitiveRawToChar, PrimitiveReadBin,
+    PrimitiveWriteBin, PrimitiveXor,
+};
+mod memory;
+pub use memory::{PrimitiveGc, PrimitiveObjectSize};
+mod tracemem;
+pub use tracemem::{PrimitiveTracemem, PrimitiveUntracemem};
@@ -74,7 +74,12 @@ impl Callable for PrimitiveNames {
                 None => Ok(Null),
             },
             Environment(e) => {
-                let mut names = e.values.borrow().keys().cloned().collect::<Vec<String>>();
+                let mut names = e
+                    .values
+                    .borrow()
+                    .keys()
+                    .map(|k| k.to_string())
+                    .collect::<Vec<String>>();
                 names.sort();
                 Ok(names.into())
             }
@@ -0,0 +1,168 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Register Expressions to Evaluate on Exit
+///
+/// Records an expression to be evaluated when the calling function exits,
+/// whether it returns normally or exits early due to an error or other
+/// signal. The value returned by the function is preserved, unless the exit
+/// expression itself raises an error.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// on.exit(expr = null, add = false, after = true)
+/// ```
+///
+/// ## Arguments
+///
+/// * `expr`: An expression to evaluate on exit. If `null`, any previously
+///   registered expressions are removed (unless `add` is `true`).
+/// * `add`: Whether to add `expr` to previously registered expressions,
+///   or replace them.
+/// * `after`: When `add` is `true`, whether `expr` should be evaluated after
+///   previously registered expressions, or before them.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// f <- function() {
+///   on.exit(print("cleaning up"))
+///   print("working")
+///   "done"
+/// }
+/// f()
+/// ```
+///
+#[doc(alias = "on.exit")]
+#[builtin(sym = "on.exit")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveOnExit;
+
+formals!(PrimitiveOnExit, "(expr = null, add = false, after = true)");
+
+impl Callable for PrimitiveOnExit {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+
+        let expr = match args.try_get_named("expr")? {
+            Obj::Promise(_, Expr::Missing, _) => Expr::Null,
+            Obj::Promise(_, expr, _) => expr,
+            _ => Expr::Null,
+        };
+
+        let add: bool = args.try_get_named("add")?.force(stack)?.try_into()?;
+        let after: bool = args.try_get_named("after")?.force(stack)?.try_into()?;
+
+        // register on the frame of the function which called `on.exit()`
+        let i = stack.env_frame();
+        let on_exit = &mut stack.frames[i].on_exit;
+
+        if !add {
+            on_exit.clear();
+        }
+
+        if expr != Expr::Null {
+            if after {
+                on_exit.push(expr);
+            } else {
+                on_exit.insert(0, expr);
+            }
+        }
+
+        Ok(Obj::Null)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn runs_on_normal_exit() {
+        assert_eq!(
+            r! {{"
+                f <- function() {
+                    on.exit(message('exiting'))
+                    return(1)
+                }
+                tryCatch(f(), message = function(m) conditionMessage(m))
+            "}},
+            r! { "exiting" }
+        );
+    }
+
+    #[test]
+    fn exit_value_is_preserved() {
+        assert_eq!(
+            r! {{"
+                f <- function() {
+                    on.exit(2)
+                    1
+                }
+                f()
+            "}},
+            r! { 1 }
+        );
+    }
+
+    #[test]
+    fn runs_on_error() {
+        assert_eq!(
+            r! {{"
+                f <- function() {
+                    on.exit(stop('from exit'))
+                    stop('from body')
+                }
+                tryCatch(f(), error = function(e) conditionMessage(e))
+            "}},
+            r! { "from exit" }
+        );
+    }
+
+    #[test]
+    fn add_and_after_control_ordering() {
+        assert_eq!(
+            r! {{"
+                f <- function() {
+                    on.exit(message('1'))
+                    on.exit(message('2'), add = true)
+                    on.exit(stop('3'), add = true, after = false)
+                }
+                tryCatch(f(), error = function(e) conditionMessage(e))
+            "}},
+            r! { "3" }
+        );
+
+        assert_eq!(
+            r! {{"
+                f <- function() {
+                    on.exit(stop('1'))
+                    on.exit(stop('2'))
+                }
+                tryCatch(f(), error = function(e) conditionMessage(e))
+            "}},
+            r! { "2" }
+        );
+    }
+
+    #[test]
+    fn handler_errors_replace_exit_value() {
+        assert_eq!(
+            r! {{"
+                f <- function() {
+                    on.exit(stop('oops'))
+                    1
+                }
+                tryCatch(f(), error = function(e) 2)
+            "}},
+            r! { 2 }
+        );
+    }
+}
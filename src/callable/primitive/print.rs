@@ -1,5 +1,6 @@
 use r_derive::*;
 use std::io::Write;
+use std::ops::ControlFlow;
 
 use crate::callable::core::*;
 use crate::formals;
@@ -34,10 +35,26 @@ pub struct PrimitivePrint;
 formals!(PrimitivePrint, "(x, ...)");
 
 impl Callable for PrimitivePrint {
-    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
         let mut args = Obj::List(args);
-        let x = args.try_get_named("x")?.force(stack)?;
+        let x = match dispatch_first("print", args.try_get_named("x")?, &ellipsis, stack)? {
+            ControlFlow::Break(result) => return Ok(result),
+            ControlFlow::Continue(x) => x,
+        };
         writeln!(stack.session.output, "{x}").ok();
         Ok(x)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::{r, r_expect};
+
+    #[test]
+    fn dispatches_on_class() {
+        r_expect! {{"
+            print.greeting <- function(x, ...) paste('hello', unclass(x))
+            print(structure('world', class = 'greeting')) == 'hello world'
+        "}}
+    }
+}
@@ -0,0 +1,458 @@
+use r_derive::*;
+
+use super::warning::warn;
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::rep::try_binary_raw_op;
+use crate::object::types::*;
+use crate::object::*;
+
+/// Coerce to a Raw Vector
+///
+/// Values are truncated to whole numbers, and values which are missing or
+/// outside of the range `0` to `255` are coerced to `0` with a warning.
+/// Attributes, including names, are dropped.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// as.raw(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: An object to coerce.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// as.raw([1, 16, 255])
+/// ```
+///
+#[doc(alias = "as.raw")]
+#[builtin(sym = "as.raw")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveAsRaw;
+
+formals!(PrimitiveAsRaw, "(x)");
+
+impl Callable for PrimitiveAsRaw {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+        let x = match x {
+            Obj::Vector(v) if v.is_unbounded() => return Error::UnboundedSequence.into(),
+            Obj::Vector(v) => v.without_names().with_attributes(None),
+            Obj::Null => return Ok(Obj::Vector(Vector::from(Vec::<Raw>::new()))),
+            _ => return Error::CannotBeCoercedTo("raw").into(),
+        };
+
+        if !matches!(x, Vector::Raw(_)) {
+            let Vector::Double(values) = x.clone().as_double() else {
+                unreachable!()
+            };
+
+            let in_range = |x: Double| matches!(x, OptionNA::Some(x) if (0.0..256.0).contains(&x));
+            if !values.iter_values().all(in_range) {
+                warn("out-of-range values treated as 0 in coercion to raw", stack)?;
+            }
+        }
+
+        Ok(Obj::Vector(x.as_raw()))
+    }
+}
+
+/// Convert Raw Bytes to a String
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// rawToChar(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A raw vector of UTF-8 encoded bytes.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// rawToChar(as.raw([72, 105]))
+/// ```
+///
+#[doc(alias = "rawToChar")]
+#[builtin(sym = "rawToChar")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveRawToChar;
+
+formals!(PrimitiveRawToChar, "(x)");
+
+impl Callable for PrimitiveRawToChar {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+        let bytes = bytes(x)?;
+        if bytes.contains(&0) {
+            return Error::Other("embedded nul in string".to_string()).into();
+        }
+
+        let string = String::from_utf8_lossy(&bytes).to_string();
+        Ok(Obj::Vector(Vector::from(vec![string])))
+    }
+}
+
+/// Convert a String to Raw Bytes
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// charToRaw(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A single string, whose UTF-8 encoding is returned.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// charToRaw("Hi")
+/// ```
+///
+#[doc(alias = "charToRaw")]
+#[builtin(sym = "charToRaw")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveCharToRaw;
+
+formals!(PrimitiveCharToRaw, "(x)");
+
+impl Callable for PrimitiveCharToRaw {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+        let Obj::Vector(Vector::Character(x)) = x else {
+            return Error::Other("argument must be a character vector of length 1".to_string())
+                .into();
+        };
+
+        if x.len() > 1 {
+            warn(
+                "argument should be a character vector of length 1, all but the first element will be ignored",
+                stack,
+            )?;
+        }
+
+        let bytes = match x.iter_values().next() {
+            Some(OptionNA::Some(x)) => x.as_bytes().to_vec(),
+            Some(OptionNA::NA) => b"NA".to_vec(),
+            None => vec![],
+        };
+
+        Ok(Obj::Vector(Vector::from(bytes)))
+    }
+}
+
+/// Exclusive Or
+///
+/// Raw vectors are combined bitwise, while all other vectors are combined
+/// as logical values.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// xor(x, y)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`, `y`: Logical or raw vectors.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// xor([true, false], true)
+/// xor(as.raw(12), as.raw(10))
+/// ```
+///
+#[doc(alias = "xor")]
+#[builtin(sym = "xor")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveXor;
+
+formals!(PrimitiveXor, "(x, y)");
+
+impl Callable for PrimitiveXor {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = args.try_get_named("x")?.force(stack)?;
+        let y = args.try_get_named("y")?.force(stack)?;
+
+        let (Obj::Vector(x), Obj::Vector(y)) = (x.as_vector()?, y.as_vector()?) else {
+            unreachable!()
+        };
+
+        match (x, y) {
+            (Vector::Raw(x), Vector::Raw(y)) => {
+                Ok(Obj::Vector(try_binary_raw_op(x, y, |x, y| x ^ y)?.into()))
+            }
+            (x, y) => {
+                let either = (x.clone() | y.clone())?;
+                let both = (x & y)?;
+                Ok(Obj::Vector((either & (!both)?)?))
+            }
+        }
+    }
+}
+
+/// Shift the Bits of Raw Bytes
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// rawShift(x, n)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A raw vector.
+/// * `n`: The number of bits to shift by, between `-8` and `8`. Positive
+///   numbers shift to the left and negative numbers shift to the right.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// rawShift(as.raw(1), 4)
+/// ```
+///
+#[doc(alias = "rawShift")]
+#[builtin(sym = "rawShift")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveRawShift;
+
+formals!(PrimitiveRawShift, "(x, n)");
+
+impl Callable for PrimitiveRawShift {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let x = args.try_get_named("x")?.force(stack)?;
+        let n: i32 = args.try_get_named("n")?.force(stack)?.try_into()?;
+
+        let Obj::Vector(Vector::Raw(x)) = x else {
+            return Error::Other("argument 'x' must be a raw vector".to_string()).into();
+        };
+
+        if !(-8..=8).contains(&n) {
+            return Error::Other("argument 'n' must be a small integer".to_string()).into();
+        }
+
+        let shift = |x: u8| match n {
+            n if n < 0 => ((x as u32) >> -n) as u8,
+            n => ((x as u32) << n) as u8,
+        };
+
+        let values: Vec<Raw> = x.iter_values().map(|x| x.map(shift)).collect();
+        Ok(Obj::Vector(Vector::from(values)))
+    }
+}
+
+/// Read Binary Data from a File
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// readBin(con, what = "raw", n = -1)
+/// ```
+///
+/// ## Arguments
+///
+/// * `con`: The path of a file to read.
+/// * `what`: The type of vector to read into. Only `"raw"` is supported.
+/// * `n`: The maximum number of bytes to read. Negative values read the
+///   whole file.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// writeBin(charToRaw("hello"), "hello.bin")
+/// readBin("hello.bin", n = 4)
+/// ```
+///
+#[doc(alias = "readBin")]
+#[builtin(sym = "readBin")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveReadBin;
+
+formals!(PrimitiveReadBin, "(con, what = 'raw', n = -1)");
+
+impl Callable for PrimitiveReadBin {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let con = path(args.try_get_named("con")?.force(stack)?)?;
+        let what = args.try_get_named("what")?.force(stack)?;
+        let n: i32 = args.try_get_named("n")?.force(stack)?.try_into()?;
+
+        if what != Obj::Vector(Vector::from(vec!["raw".to_string()])) {
+            return Error::Other("only raw vectors can be read".to_string()).into();
+        }
+
+        let mut bytes = std::fs::read(&con)
+            .map_err(|e| Error::Other(format!("cannot open file '{con}': {e}")))?;
+
+        if n >= 0 {
+            bytes.truncate(n as usize);
+        }
+
+        Ok(Obj::Vector(Vector::from(bytes)))
+    }
+}
+
+/// Write Binary Data to a File
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// writeBin(object, con)
+/// ```
+///
+/// ## Arguments
+///
+/// * `object`: A raw vector to write.
+/// * `con`: The path of the file to write, which is replaced if it exists.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// writeBin(as.raw([0, 255]), "bytes.bin")
+/// ```
+///
+#[doc(alias = "writeBin")]
+#[builtin(sym = "writeBin")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveWriteBin;
+
+formals!(PrimitiveWriteBin, "(object, con)");
+
+impl Callable for PrimitiveWriteBin {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let object = args.try_get_named("object")?.force(stack)?;
+        let con = path(args.try_get_named("con")?.force(stack)?)?;
+
+        let bytes = bytes(object)?;
+        std::fs::write(&con, bytes)
+            .map_err(|e| Error::Other(format!("cannot open file '{con}': {e}")))?;
+
+        Ok(Obj::Null)
+    }
+}
+
+/// The bytes of a raw vector
+fn bytes(x: Obj) -> Result<Vec<u8>, Signal> {
+    let Obj::Vector(Vector::Raw(x)) = x else {
+        return Error::Other("argument 'x' must be a raw vector".to_string()).into();
+    };
+
+    Ok(x.iter_values()
+        .map(|x| x.as_option().unwrap_or(0))
+        .collect())
+}
+
+/// A file path, given as a single string
+fn path(x: Obj) -> Result<String, Signal> {
+    match x {
+        Obj::Vector(Vector::Character(x)) if x.len() == 1 => match x.iter_values().next() {
+            Some(OptionNA::Some(path)) => Ok(path.into()),
+            _ => Error::Other("invalid file path".to_string()).into(),
+        },
+        _ => Error::Other("invalid file path".to_string()).into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::context::Context;
+    use crate::error::Error;
+    use crate::lang::CallStack;
+    use crate::{r, r_expect};
+
+    #[test]
+    fn raw_printing() {
+        assert_eq!(
+            format!("{}", r! { as.raw([1, 16, 255]) }.unwrap()),
+            "[1] 01 10 ff"
+        );
+        assert_eq!(format!("{}", r! { as.raw(null) }.unwrap()), "raw(0)");
+    }
+
+    #[test]
+    fn raw_out_of_range_values_are_zero() {
+        assert_eq!(r! { as.raw([256, -1, na]) }, r! { as.raw([0, 0, 0]) });
+    }
+
+    #[test]
+    fn raw_strings_round_trip() {
+        assert_eq!(r! { rawToChar(charToRaw("hello")) }, r! { "hello" });
+        assert_eq!(r! { charToRaw("Hi") }, r! { as.raw([72, 105]) });
+        assert_eq!(r! { as.character(as.raw(255)) }, r! { "ff" });
+    }
+
+    #[test]
+    fn raw_bitwise_operations() {
+        assert_eq!(r! { as.raw(12) & as.raw(10) }, r! { as.raw(8) });
+        assert_eq!(r! { as.raw(12) | as.raw(10) }, r! { as.raw(14) });
+        assert_eq!(r! { xor(as.raw(12), as.raw(10)) }, r! { as.raw(6) });
+        assert_eq!(r! { !as.raw(0) }, r! { as.raw(255) });
+        assert_eq!(r! { rawShift(as.raw(1), 4) }, r! { as.raw(16) });
+        assert_eq!(r! { xor([true, false], true) }, r! { [false, true] });
+    }
+
+    #[test]
+    fn raw_arithmetic_is_an_error() {
+        assert_eq!(
+            r! { as.raw(1) + 1 },
+            Error::Other("non-numeric argument to binary operator".to_string()).into()
+        );
+    }
+
+    #[test]
+    fn raw_comparison() {
+        r_expect! { as.raw(16) == 16 }
+        r_expect! { as.raw(16) == "10" }
+        r_expect! { as.raw(1) < as.raw(2) }
+    }
+
+    #[test]
+    fn raw_combines_with_other_types() {
+        assert_eq!(r! { c(as.raw(1), as.raw(2)) }, r! { as.raw([1, 2]) });
+        assert_eq!(r! { c(as.raw(1), 2L) }, r! { c(1L, 2L) });
+        assert_eq!(r! { c(as.raw(1), true) }, r! { [true, true] });
+    }
+
+    #[test]
+    fn raw_files_round_trip() {
+        let path = std::env::temp_dir().join(format!("r-raw-{}.bin", std::process::id()));
+        let path = path.to_string_lossy().replace('\\', "/");
+
+        let mut stack = CallStack::default();
+        let code = format!("writeBin(as.raw([0, 1, 255]), '{path}')\nreadBin('{path}', n = 2)");
+        let result = stack
+            .parse(&code)
+            .and_then(|expr| stack.eval_and_finalize(expr));
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(result, r! { as.raw([0, 1]) });
+    }
+}
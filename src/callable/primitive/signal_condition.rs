@@ -0,0 +1,74 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Signal a Condition
+///
+/// Passes a condition object to any established handlers. If no handler
+/// unwinds the stack, `NULL` is returned.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// signalCondition(cond)
+/// ```
+///
+/// ## Arguments
+///
+/// * `cond`: A condition object, a `list` with `message` and `call`
+///   elements and a `class` attribute.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// cond <- structure(list(message = "custom", call = null), class = ["custom", "condition"])
+/// tryCatch(signalCondition(cond), custom = function(c) "caught!")
+/// ```
+///
+#[doc(alias = "signalCondition")]
+#[builtin(sym = "signalCondition")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveSignalCondition;
+
+formals!(PrimitiveSignalCondition, "(cond)");
+
+impl Callable for PrimitiveSignalCondition {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let cond = Obj::List(args).try_get_named("cond")?.force(stack)?;
+        stack.signal(cond)?;
+        Ok(Obj::Null)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn custom_conditions_dispatch_on_class() {
+        assert_eq!(
+            r! {{"
+                cond <- structure(list(message = 'custom', call = null), class = ['custom', 'condition'])
+                tryCatch(
+                    signalCondition(cond),
+                    error = function(e) 'error',
+                    custom = function(c) conditionMessage(c)
+                )
+            "}},
+            r! { "custom" }
+        );
+    }
+
+    #[test]
+    fn unhandled_conditions_return_null() {
+        assert_eq!(
+            r! { signalCondition(structure(list(message = "x", call = null), class = "condition")) },
+            r! { null }
+        );
+    }
+}
@@ -0,0 +1,114 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::error::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Stop Execution with an Error
+///
+/// Signals an error condition, which is passed on to any established
+/// handlers before aborting evaluation.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// stop(...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `...`: Either a single condition object, or objects which are pasted
+///   together without separator to form the error message.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// f <- function(x) if (x < 0) stop("x must be positive") else x
+/// f(-1)
+/// ```
+///
+/// ```custom,{class=r-repl}
+/// tryCatch(stop("oops"), error = function(e) conditionMessage(e))
+/// ```
+///
+#[doc(alias = "stop")]
+#[builtin(sym = "stop")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveStop;
+
+formals!(PrimitiveStop, "(...)");
+
+impl Callable for PrimitiveStop {
+    fn call_matched(&self, _args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let call = stack.frames[stack.env_frame()].call.clone();
+        let cond = condition_from_args(ellipsis, call, "simpleError", "error", stack)?;
+        stack.signal(cond.clone())?;
+        Error::Condition(cond).into()
+    }
+}
+
+/// Build a condition from the arguments to `stop()`, `warning()` or
+/// `message()`.
+///
+/// A single condition object is passed through as-is, otherwise all
+/// arguments are pasted together to form the condition message.
+pub fn condition_from_args(
+    ellipsis: List,
+    call: Expr,
+    class: &str,
+    kind: &str,
+    stack: &mut CallStack,
+) -> EvalResult {
+    let args = force_promises(ellipsis, stack)?;
+
+    if let [(_, cond @ Obj::List(_))] = args.as_slice() {
+        if condition_classes(cond).iter().any(|c| c == "condition") {
+            return Ok(cond.clone());
+        }
+    }
+
+    let mut message = String::new();
+    for (_, arg) in args {
+        if let Obj::Vector(v) = arg.as_character()? {
+            let parts: Vec<String> = v.into();
+            message.push_str(&parts.concat());
+        }
+    }
+
+    Ok(new_condition(message, call, vec![class, kind, "condition"]))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::lang::{condition_classes, Signal};
+    use crate::r;
+
+    #[test]
+    fn stop_raises_condition() {
+        let Err(Signal::Error(Error::Condition(cond))) = r!(stop("a", 1, "b")) else {
+            panic!("expected condition")
+        };
+
+        assert_eq!(Error::Condition(cond.clone()).to_string(), "Error: a1b");
+        assert_eq!(
+            condition_classes(&cond),
+            vec!["simpleError", "error", "condition"]
+        );
+    }
+
+    #[test]
+    fn stop_captures_calling_frame() {
+        assert_eq!(
+            r! {{"
+                f <- function() stop('oops')
+                tryCatch(f(), error = function(e) conditionCall(e))
+            "}},
+            r! { quote(f()) }
+        );
+    }
+}
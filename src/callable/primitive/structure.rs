@@ -0,0 +1,86 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::types::Character;
+use crate::object::*;
+
+/// Attach Attributes to an Object
+///
+/// Returns `.Data` with each named argument set as an attribute.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// structure(.Data, ...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `.Data`: An object to which attributes are attached.
+/// * `...`: Named attribute values. A `null` value removes the attribute.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// structure([1, 2, 3], units = "cm")
+/// ```
+///
+#[doc(alias = "structure")]
+#[builtin(sym = "structure")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveStructure;
+
+formals!(PrimitiveStructure, "(.Data, ...)");
+
+impl Callable for PrimitiveStructure {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut x = Obj::List(args).try_get_named(".Data")?.force(stack)?;
+
+        for (name, value) in force_promises(ellipsis, stack)? {
+            let Character::Some(name) = name else {
+                return Error::Other("attributes must be named".to_string()).into();
+            };
+
+            x = x.set_attr(&name, value)?;
+        }
+
+        Ok(x)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{r, r_expect};
+
+    #[test]
+    fn structure_sets_attributes() {
+        r_expect! { attr(structure([1, 2], units = "cm"), "units") == "cm" }
+    }
+
+    #[test]
+    fn attributes_preserved_through_arithmetic() {
+        r_expect! {{"
+            x <- structure([1, 2, 3], units = 'cm')
+            attr(x + 1, 'units') == 'cm' && attr(-x, 'units') == 'cm'
+        "}}
+    }
+
+    #[test]
+    fn attributes_dropped_when_subset() {
+        r_expect! {{"
+            x <- structure([1, 2, 3], units = 'cm')
+            is_null(attr(x[1], 'units'))
+        "}}
+    }
+
+    #[test]
+    fn attributes_are_printed() {
+        let x = r! { structure([1, 2], units = "cm") }.unwrap();
+        assert!(format!("{x}").ends_with("attr(,\"units\")\n[1] \"cm\""));
+    }
+}
@@ -1,4 +1,5 @@
 use r_derive::*;
+use std::rc::Rc;
 
 use crate::callable::core::*;
 use crate::callable::keywords::KeywordParen;
@@ -59,23 +60,24 @@ impl Callable for PrimitiveSubstitute {
             return internal_err!();
         };
 
-        fn recurse(exprs: ExprList, env: &Environment, paren: bool) -> ExprList {
-            exprs
+        fn recurse(exprs: &ExprList, env: &Environment, paren: bool) -> Rc<ExprList> {
+            let exprs = exprs
+                .clone()
                 .into_iter()
                 .map(|(key, expr)| (key, substitute(expr, env, paren)))
-                .collect()
+                .collect();
+            Rc::new(exprs)
         }
 
         // add parenthesis around ambigous expressions, namely anonymous functions and infix calls
         fn paren_if_infix(expr: Expr) -> Expr {
-            match expr {
+            match &expr {
                 Function(..) => Expr::new_primitive_call(KeywordParen, ExprList::from(vec![expr])),
-                Call(what, exprs) => match *what {
+                Call(what, _) => match &**what {
                     Primitive(p) if p.is_infix() => {
-                        let expr = Call(Box::new(Primitive(p)), exprs);
                         Expr::new_primitive_call(KeywordParen, ExprList::from(vec![expr]))
                     }
-                    _ => Call(what, exprs),
+                    _ => expr,
                 },
                 _ => expr,
             }
@@ -97,18 +99,16 @@ impl Callable for PrimitiveSubstitute {
                         _ => Symbol(s),
                     }
                 }
-                List(exprs) => List(recurse(exprs, env, false)),
+                List(exprs) => List(recurse(&exprs, env, false)),
                 Function(params, body) => Function(
-                    recurse(params, env, false),
-                    Box::new(substitute(*body, env, false)),
+                    recurse(&params, env, false),
+                    Rc::new(substitute((*body).clone(), env, false)),
                 ),
-                Call(what, exprs) => match *what {
-                    Primitive(p) if p.is_infix() => {
-                        Call(Box::new(Primitive(p)), recurse(exprs, env, true))
-                    }
+                Call(what, exprs) => match &*what {
+                    Primitive(p) if p.is_infix() => Call(what.clone(), recurse(&exprs, env, true)),
                     _ => Call(
-                        Box::new(substitute(*what, env, true)),
-                        recurse(exprs, env, false),
+                        Rc::new(substitute((*what).clone(), env, true)),
+                        recurse(&exprs, env, false),
                     ),
                 },
                 other => other,
@@ -52,9 +52,9 @@ impl Callable for PrimitiveSum {
 
         for obj in &objects {
             match obj {
+                Obj::Vector(v) if v.is_unbounded() => return Error::UnboundedSequence.into(),
                 Obj::Vector(Vector::Double(..)) => {
                     any_double = true;
-                    break;
                 }
                 Obj::Vector(Vector::Logical(..)) | Obj::Vector(Vector::Integer(..)) => {
                     continue;
@@ -74,41 +74,57 @@ impl Callable for PrimitiveSum {
                 match obj {
                     Obj::Vector(vect) => {
                         match vect {
+                            Vector::Logical(repr) if repr.is_computed() => {
+                                let Some(x) = sum_computed(&repr, |x| x as i32 as f64) else {
+                                    let rep: Rep<OptionNA<f64>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
+                                };
+                                sum += x;
+                            }
                             Vector::Logical(repr) => {
-                                for x in repr.inner().borrow().iter() {
-                                    match *x {
-                                        OptionNA::NA => {
-                                            let rep: Rep<OptionNA<f64>> =
-                                                Rep::from(vec![OptionNA::NA]);
-                                            return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
-                                        }
-                                        OptionNA::Some(x) => sum += x as i32 as f64,
-                                    }
+                                let values = repr.inner();
+                                let values = values.borrow();
+                                if values.has_na() {
+                                    let rep: Rep<OptionNA<f64>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
                                 }
+                                sum += values.dense().iter().map(|&x| x as i32 as f64).sum::<f64>();
+                            }
+                            Vector::Integer(repr) if repr.is_computed() => {
+                                let Some(x) = sum_computed(&repr, |x| x as f64) else {
+                                    let rep: Rep<OptionNA<f64>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
+                                };
+                                sum += x;
                             }
                             Vector::Integer(repr) => {
-                                for x in repr.inner().borrow().iter() {
-                                    match *x {
-                                        OptionNA::NA => {
-                                            let rep: Rep<OptionNA<f64>> =
-                                                Rep::from(vec![OptionNA::NA]);
-                                            return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
-                                        }
-                                        OptionNA::Some(x) => sum += x as f64,
-                                    }
+                                let values = repr.inner();
+                                let values = values.borrow();
+                                if values.has_na() {
+                                    let rep: Rep<OptionNA<f64>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
                                 }
+                                sum += values.dense().iter().map(|&x| x as f64).sum::<f64>();
+                            }
+                            // unbounded sequences were rejected above
+                            Vector::Double(Rep::Seq(seq, _)) => {
+                                sum += seq.sum().unwrap_or(f64::INFINITY);
+                            }
+                            Vector::Double(repr) if repr.is_computed() => {
+                                let Some(x) = sum_computed(&repr, |x| x) else {
+                                    let rep: Rep<OptionNA<f64>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
+                                };
+                                sum += x;
                             }
                             Vector::Double(repr) => {
-                                for x in repr.inner().borrow().iter() {
-                                    match *x {
-                                        OptionNA::NA => {
-                                            let rep: Rep<OptionNA<f64>> =
-                                                Rep::from(vec![OptionNA::NA]);
-                                            return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
-                                        }
-                                        OptionNA::Some(x) => sum += x,
-                                    }
+                                let values = repr.inner();
+                                let values = values.borrow();
+                                if values.has_na() {
+                                    let rep: Rep<OptionNA<f64>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
                                 }
+                                sum += values.dense().iter().sum::<f64>();
                             }
                             _ => return internal_err!(),
                         };
@@ -124,29 +140,37 @@ impl Callable for PrimitiveSum {
                 match obj {
                     Obj::Vector(vect) => {
                         match vect {
+                            Vector::Logical(repr) if repr.is_computed() => {
+                                let Some(x) = sum_computed(&repr, |x| x as i32) else {
+                                    let rep: Rep<OptionNA<i32>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
+                                };
+                                sum += x;
+                            }
                             Vector::Logical(repr) => {
-                                for x in repr.inner().borrow().iter() {
-                                    match *x {
-                                        OptionNA::NA => {
-                                            let rep: Rep<OptionNA<i32>> =
-                                                Rep::from(vec![OptionNA::NA]);
-                                            return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
-                                        }
-                                        OptionNA::Some(x) => sum += x as i32,
-                                    }
+                                let values = repr.inner();
+                                let values = values.borrow();
+                                if values.has_na() {
+                                    let rep: Rep<OptionNA<i32>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
                                 }
+                                sum += values.dense().iter().map(|&x| x as i32).sum::<i32>();
+                            }
+                            Vector::Integer(repr) if repr.is_computed() => {
+                                let Some(x) = sum_computed(&repr, |x| x) else {
+                                    let rep: Rep<OptionNA<i32>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
+                                };
+                                sum += x;
                             }
                             Vector::Integer(repr) => {
-                                for x in repr.inner().borrow().iter() {
-                                    match *x {
-                                        OptionNA::NA => {
-                                            let rep: Rep<OptionNA<i32>> =
-                                                Rep::from(vec![OptionNA::NA]);
-                                            return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
-                                        }
-                                        OptionNA::Some(x) => sum += x,
-                                    }
+                                let values = repr.inner();
+                                let values = values.borrow();
+                                if values.has_na() {
+                                    let rep: Rep<OptionNA<i32>> = Rep::from(vec![OptionNA::NA]);
+                                    return EvalResult::Ok(Obj::Vector(Vector::from(rep)));
                                 }
+                                sum += values.dense().iter().sum::<i32>();
                             }
                             _ => return internal_err!(),
                         };
@@ -159,6 +183,21 @@ impl Callable for PrimitiveSum {
     }
 }
 
+/// Sum the elements of a sequence or lazy vector as they are computed,
+/// rather than materializing them, or `None` if any element is missing
+fn sum_computed<T, S>(repr: &Rep<OptionNA<T>>, f: impl Fn(T) -> S) -> Option<S>
+where
+    OptionNA<T>: Element + Default,
+    S: std::iter::Sum,
+{
+    repr.iter_values()
+        .map(|x| match x {
+            OptionNA::Some(x) => Some(f(x)),
+            OptionNA::NA => None,
+        })
+        .sum()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::r;
@@ -238,4 +277,22 @@ mod tests {
     fn sum_named_args() {
         assert_eq!(r! {{"sum(a = 1, b = 2)"}}, r! {{"3"}},)
     }
+
+    #[test]
+    fn sum_replaced_na() {
+        assert_eq!(r! {{"x <- c(1, NA, 3); x[2] <- 2; sum(x)"}}, r! {{"6"}},)
+    }
+
+    #[test]
+    fn sum_sequence() {
+        assert_eq!(r! {{"sum(1:1e9)"}}, r! {{"500000000500000000"}});
+        assert_eq!(r! {{"sum(3:5, 1)"}}, r! {{"13"}});
+    }
+
+    #[test]
+    fn sum_lazy() {
+        assert_eq!(r! {{"sum((1:1e6) * 2)"}}, r! {{"1000001000000"}});
+        assert_eq!(r! {{"sum(1:10 > 5)"}}, r! {{"5L"}});
+        assert_eq!(r! {{"sum((1:3) * c(1, NA, 1))"}}, r! {NA * 1});
+    }
 }
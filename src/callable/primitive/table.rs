@@ -0,0 +1,86 @@
+use r_derive::*;
+
+use super::factor::factor_values;
+use crate::callable::core::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::coercion::factor_codes;
+use crate::object::types::*;
+use crate::object::*;
+
+/// Count Occurrences of Values
+///
+/// Tabulates the number of times each level of `x` occurs, producing an
+/// `integer` vector of counts named by level. Factors are counted over all
+/// of their levels, including unused ones. `NA` values are not counted.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// table(x)
+/// ```
+///
+/// ## Arguments
+///
+/// * `x`: A factor, or a vector to be treated as one.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// table(["b", "a", "b"])
+/// table(factor(["lo"], levels = ["lo", "hi"]))
+/// ```
+///
+#[doc(alias = "table")]
+#[builtin(sym = "table")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveTable;
+
+formals!(PrimitiveTable, "(x)");
+
+impl Callable for PrimitiveTable {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let x = Obj::List(args).try_get_named("x")?.force(stack)?;
+
+        let levels = match &x {
+            Obj::Vector(v) => v.factor_levels(),
+            _ => None,
+        };
+
+        let (values, used_levels) = factor_values(x)?;
+        let levels = levels.unwrap_or(used_levels);
+
+        let mut counts = vec![0; levels.len()];
+        for code in factor_codes(&values, &levels) {
+            if let OptionNA::Some(i) = code {
+                counts[i as usize - 1] += 1;
+            }
+        }
+
+        let counts: Vec<Integer> = counts.into_iter().map(OptionNA::Some).collect();
+        Obj::Vector(Vector::from(counts)).set_attr("names", Obj::Vector(Vector::from(levels)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn table_counts_values() {
+        assert_eq!(
+            r! { table(["b", "a", "b", "c"]) },
+            r! { c(a = 1L, b = 2L, c = 1L) }
+        );
+    }
+
+    #[test]
+    fn table_counts_unused_factor_levels() {
+        assert_eq!(
+            r! { table(factor(["lo", "lo"], levels = ["lo", "hi"])) },
+            r! { c(lo = 2L, hi = 0L) }
+        );
+    }
+}
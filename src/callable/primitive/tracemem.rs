@@ -0,0 +1,188 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+/// Trace Copies of an Object
+///
+/// Mark the data of a vector or list, such that a message is printed with
+/// the calls on the stack whenever the data is copied, for example when it
+/// is modified while it is shared with another object. Copies of traced data
+/// are traced as well.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// tracemem(x)
+/// ```
+///
+/// ## Arguments
+///
+/// `x`: A vector or list whose data to trace.
+///
+/// ## Value
+///
+/// A `character` vector with the address of the traced data.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// x <- c(1, 2, 3)
+/// tracemem(x)
+/// y <- x
+/// y[1] <- 10
+/// ```
+///
+#[doc(alias = "tracemem")]
+#[builtin(sym = "tracemem")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveTracemem;
+
+formals!(PrimitiveTracemem, "(x,)");
+
+impl Callable for PrimitiveTracemem {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let arg = args.try_get_named("x")?;
+        let mut x = arg.clone().force(stack)?;
+
+        // computed vectors are materialized where they are bound, giving
+        // them data to trace
+        if let (Obj::Vector(v), Obj::Promise(_, Expr::Symbol(name), env)) = (&x, &arg) {
+            if v.is_computed() {
+                x = env.get_mut(*name)?;
+            }
+        }
+
+        let address = match &x {
+            Obj::Vector(v) => v.trace(),
+            Obj::List(l) => l.trace(),
+            _ => None,
+        };
+
+        match address {
+            Some(address) => Ok(Obj::Vector(Vector::from(vec![format!("<{address:#x}>")]))),
+            None => Error::ArgumentInvalid("x".to_string()).into(),
+        }
+    }
+}
+
+/// Stop Tracing Copies of an Object
+///
+/// Stop printing messages when the data of an object that was marked by
+/// `tracemem()` is copied.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// untracemem(x)
+/// ```
+///
+/// ## Arguments
+///
+/// `x`: A vector or list whose data is traced.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// x <- c(1, 2, 3)
+/// tracemem(x)
+/// untracemem(x)
+/// y <- x
+/// y[1] <- 10
+/// ```
+///
+#[doc(alias = "untracemem")]
+#[builtin(sym = "untracemem")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveUntracemem;
+
+formals!(PrimitiveUntracemem, "(x,)");
+
+impl Callable for PrimitiveUntracemem {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        match args.try_get_named("x")?.force(stack)? {
+            Obj::Vector(v) => v.untrace(),
+            Obj::List(l) => l.untrace(),
+            _ => (),
+        }
+
+        Ok(Obj::Null)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use crate::context::Context;
+    use crate::lang::CallStack;
+    use crate::session::{Session, SessionOutput};
+    use crate::{r, r_expect};
+
+    fn output_of(code: &str) -> String {
+        let output = Rc::new(RefCell::new(String::new()));
+        let out = output.clone();
+        let session = Session::default().with_output(SessionOutput::Callback(Rc::new(move |s| {
+            out.borrow_mut().push_str(&s)
+        })));
+
+        let mut stack = CallStack::from(session);
+        let expr = stack.parse(code).unwrap();
+        stack.eval_and_finalize(expr).unwrap();
+        output.take()
+    }
+
+    #[test]
+    fn tracemem_returns_address() {
+        r_expect! {{"
+            x <- c(1, 2, 3)
+            tracemem(x) == tracemem(x)
+        "}}
+    }
+
+    #[test]
+    fn copies_are_reported() {
+        let output = output_of(
+            "
+            x <- c(1, 2, 3)
+            tracemem(x)
+            f <- function(v) { v[1] <- 10; v }
+            g <- function(v) f(v)
+            y <- g(x)
+            ",
+        );
+
+        assert!(output.starts_with("tracemem[0x"));
+        assert!(output.ends_with("]: f g\n"));
+    }
+
+    #[test]
+    fn untraced_copies_are_not_reported() {
+        let output = output_of(
+            "
+            x <- c(1, 2, 3)
+            tracemem(x)
+            untracemem(x)
+            y <- x
+            y[1] <- 10
+            ",
+        );
+
+        assert_eq!(output, "");
+    }
+
+    #[test]
+    fn functions_cannot_be_traced() {
+        assert!(r!(tracemem(function() 1)).is_err());
+    }
+}
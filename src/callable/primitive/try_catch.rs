@@ -0,0 +1,182 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::types::Character;
+use crate::object::*;
+
+/// Evaluate an Expression, Catching Conditions
+///
+/// Establishes exiting handlers while evaluating an expression. When a
+/// condition is signaled that matches one of the handlers, the stack is
+/// unwound and the handler is called with the condition. Its result becomes
+/// the result of `tryCatch()`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// tryCatch(expr, ..., finally)
+/// ```
+///
+/// ## Arguments
+///
+/// * `expr`: An expression to evaluate.
+/// * `...`: Named handler functions, where each name is a condition class.
+/// * `finally`: An expression that is evaluated before returning, regardless
+///   of how `expr` exited.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// tryCatch(stop("oops"), error = function(e) conditionMessage(e))
+/// ```
+///
+/// ```custom,{class=r-repl}
+/// tryCatch(undefined_variable, objectNotFoundError = function(e) "not found!")
+/// ```
+///
+#[doc(alias = "tryCatch")]
+#[builtin(sym = "tryCatch")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveTryCatch;
+
+formals!(PrimitiveTryCatch, "(expr, ..., finally)");
+
+impl Callable for PrimitiveTryCatch {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let frame = stack.frames.len();
+        let n_handlers = stack.handlers.len();
+
+        // the first handler takes precedence, so it is established last
+        let handlers = force_promises(ellipsis, stack)?;
+        for (class, fun) in handlers.into_iter().rev() {
+            let Character::Some(class) = class else {
+                continue;
+            };
+
+            stack
+                .handlers
+                .push(Handler { class: class.into(), fun, exiting: true, frame });
+        }
+
+        let result = args.try_get_named("expr")?.force(stack);
+        stack.handlers.truncate(n_handlers);
+
+        let result = match result {
+            Err(Signal::Condition(Cond::Unwind(to, fun, cond))) if to == frame => {
+                stack.frames.truncate(frame);
+                let args = List::from(vec![(None, cond)]);
+                stack.call_function(fun, args)
+            }
+            result => result,
+        };
+
+        match args.try_get_named("finally") {
+            Err(_) | Ok(Obj::Promise(_, Expr::Missing, _)) => (),
+            Ok(finally) => {
+                finally.force(stack)?;
+            }
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn catches_errors() {
+        assert_eq!(
+            r! { tryCatch(stop("oops"), error = function(e) conditionMessage(e)) },
+            r! { "oops" }
+        );
+    }
+
+    #[test]
+    fn passes_through_values() {
+        assert_eq!(r! { tryCatch(1 + 2, error = function(e) 0) }, r! { 3 });
+    }
+
+    #[test]
+    fn catches_internal_errors_by_type() {
+        assert_eq!(
+            r! {{"
+                tryCatch(
+                    undefined_variable,
+                    coercionError = function(e) 'coercion',
+                    objectNotFoundError = function(e) conditionMessage(e)
+                )
+            "}},
+            r! { "object 'undefined_variable' not found" }
+        );
+
+        assert_eq!(
+            r! { tryCatch(undefined_variable, error = function(e) "caught") },
+            r! { "caught" }
+        );
+    }
+
+    #[test]
+    fn first_matching_handler_takes_precedence() {
+        assert_eq!(
+            r! { tryCatch(stop("x"), condition = function(c) 1, error = function(e) 2) },
+            r! { 1 }
+        );
+    }
+
+    #[test]
+    fn unmatched_conditions_propagate() {
+        assert_eq!(
+            r! {{"
+                tryCatch(
+                    tryCatch(stop('oops'), warning = function(w) 'inner'),
+                    error = function(e) conditionMessage(e)
+                )
+            "}},
+            r! { "oops" }
+        );
+    }
+
+    #[test]
+    fn nested_handlers_unwind_to_innermost() {
+        assert_eq!(
+            r! {{"
+                f <- function() tryCatch(stop('inner'), error = function(e) 'inner handler')
+                tryCatch(f(), error = function(e) 'outer handler')
+            "}},
+            r! { "inner handler" }
+        );
+    }
+
+    #[test]
+    fn finally_is_always_evaluated() {
+        assert_eq!(
+            r! {{"
+                x <- 1
+                y <- tryCatch(stop('oops'), error = function(e) x, finally = x <- 2)
+                [x, y]
+            "}},
+            r! { [2, 1] }
+        );
+    }
+
+    #[test]
+    fn stack_is_restored_after_catching() {
+        assert_eq!(
+            r! {{"
+                f <- function() stop('deep')
+                g <- function() f()
+                n <- length(callstack())
+                tryCatch(g(), error = function(e) 1)
+                length(callstack()) == n
+            "}},
+            r! { true }
+        );
+    }
+}
@@ -0,0 +1,302 @@
+use std::rc::Rc;
+
+use r_derive::*;
+
+use crate::callable::builtins::BUILTIN;
+use crate::callable::core::*;
+use crate::error::Error;
+use crate::formals;
+use crate::lang::*;
+use crate::object::types::Character;
+use crate::object::*;
+
+/// Dispatch a Generic to a Method
+///
+/// Calls the method for the class of an object, named `generic.class`. Each
+/// class of the object is tried in turn, followed by `generic.default`.
+/// Methods are found through the environment of the calling function.
+///
+/// The method is called with the arguments of the calling function, and
+/// the result of the method is returned.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// UseMethod(generic, object)
+/// ```
+///
+/// ## Arguments
+///
+/// * `generic`: The name of the generic, as a `character` string.
+/// * `object`: The object whose class is used for dispatch. Defaults to the
+///   first argument of the calling function.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// area <- function(shape, ...) UseMethod("area")
+/// area.square <- function(shape, ...) shape$side ^ 2
+/// area(structure(list(side = 3), class = "square"))
+/// ```
+///
+#[doc(alias = "UseMethod")]
+#[builtin(sym = "UseMethod")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveUseMethod;
+
+formals!(PrimitiveUseMethod, "(generic, object)");
+
+impl Callable for PrimitiveUseMethod {
+    fn call_matched(&self, args: List, _ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+
+        let generic = match args.try_get_named("generic")?.force(stack)? {
+            Obj::Vector(v @ Vector::Character(_)) if v.len() == 1 => String::from(v),
+            _ => {
+                return Error::Other("'generic' argument must be a character string".to_string())
+                    .into()
+            }
+        };
+
+        // dispatch on the arguments of the function which called `UseMethod()`
+        let frame = stack.frames[stack.env_frame()].clone();
+        let Obj::Function(..) = frame.to else {
+            return Error::Other("UseMethod called from outside a function".to_string()).into();
+        };
+
+        let mut dispatch_args = generic_args(&frame.to, &frame.env);
+
+        let object = match args.try_get_named("object") {
+            Ok(Obj::Promise(_, Expr::Missing, _)) | Err(_) => match dispatch_args.first_mut() {
+                Some((_, arg)) => force_in_place(arg, stack)?,
+                None => Obj::Null,
+            },
+            Ok(object) => object.force(stack)?,
+        };
+
+        let mut classes = object.dispatch_class();
+        classes.push("default".to_string());
+
+        let args = List::from(dispatch_args);
+        let dispatch = Dispatch { generic: generic.clone(), classes, args };
+        dispatch_next(dispatch, stack).unwrap_or_else(|| {
+            Error::Other(format!(
+                "no applicable method for '{generic}' applied to an object of class '{}'",
+                object.class().join("/")
+            ))
+            .into()
+        })
+    }
+}
+
+/// Dispatch to the Next Method
+///
+/// Called from within a method to call the method for the next class of the
+/// object being dispatched, or the default method if no further classes
+/// have methods. The next method is called with the same arguments as the
+/// current method.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// NextMethod(...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `...`: Named arguments which replace or are added to the arguments
+///   passed to the next method.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// describe <- function(x) UseMethod("describe")
+/// describe.default <- function(x) "an object"
+/// describe.dog <- function(x) paste("a dog and", NextMethod())
+/// describe(structure(list(), class = "dog"))
+/// ```
+///
+#[doc(alias = "NextMethod")]
+#[builtin(sym = "NextMethod")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveNextMethod;
+
+formals!(PrimitiveNextMethod, "(...)");
+
+impl Callable for PrimitiveNextMethod {
+    fn call_matched(&self, _args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let Some(mut dispatch) = stack.frames[stack.env_frame()].dispatch.clone() else {
+            return Error::Other("NextMethod called from outside a method dispatch".to_string())
+                .into();
+        };
+
+        // named arguments replace those of the current method
+        let mut args: Vec<(Character, Obj)> = dispatch.args.iter_pairs().collect();
+        for (k, v) in ellipsis.iter_pairs() {
+            match args
+                .iter()
+                .position(|(ki, _)| matches!(k, Character::Some(_)) && ki == &k)
+            {
+                Some(i) => args[i].1 = v,
+                None => args.push((k, v)),
+            }
+        }
+        dispatch.args = List::from(args);
+
+        let generic = dispatch.generic.clone();
+        dispatch_next(dispatch, stack)
+            .unwrap_or_else(|| Error::Other(format!("no more methods for '{generic}'")).into())
+    }
+}
+
+/// Call the next method of a dispatch
+///
+/// Falls back to a builtin of the same name as the generic once no methods
+/// remain, returning `None` if there is no such builtin.
+fn dispatch_next(dispatch: Dispatch, stack: &mut CallStack) -> Option<EvalResult> {
+    if let Some((name, method, classes)) = stack.find_method(&dispatch.generic, &dispatch.classes) {
+        return Some(stack.call_method(name, method, Dispatch { classes, ..dispatch }));
+    }
+
+    let builtin = BUILTIN.get(dispatch.generic.as_str())?.clone();
+    let dispatch = Dispatch { classes: vec![], ..dispatch };
+    Some(stack.call_builtin_method(builtin, dispatch))
+}
+
+/// Collect the arguments that were passed to a function
+///
+/// Parameters that were not supplied, and therefore hold their default
+/// values, are omitted so that methods apply their own defaults.
+/// Parameters are passed by position until one is omitted.
+fn generic_args(fun: &Obj, env: &Rc<Environment>) -> Vec<(Character, Obj)> {
+    let mut args = vec![];
+    let mut positional = true;
+
+    for (param, value) in fun.formals().into_iter() {
+        let name = match (param, value) {
+            (None, Expr::Ellipsis(None)) => Symbol::new("..."),
+            (None, Expr::Ellipsis(Some(name))) => name,
+            (Some(name), _) => name,
+            _ => continue,
+        };
+
+        let value = env.values.borrow().get(&name).cloned();
+        match value {
+            // arguments collected by an ellipsis keep their names
+            Some(Obj::List(more)) if name == "..." => args.extend(more.iter_pairs()),
+            Some(Obj::Promise(_, _, ref penv)) if Rc::ptr_eq(penv, env) => positional = false,
+            Some(value) if positional => args.push((Character::NA, value)),
+            Some(value) => args.push((Character::Some(name.into()), value)),
+            None => positional = false,
+        }
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{r, r_expect};
+
+    #[test]
+    fn dispatches_on_class() {
+        assert_eq!(
+            r! {{"
+                area <- function(shape, ...) UseMethod('area')
+                area.square <- function(shape, ...) shape$side ^ 2
+                area.circle <- function(shape, ...) 3 * shape$r ^ 2
+                [
+                    area(structure(list(side = 3), class = 'square')),
+                    area(structure(list(r = 1), class = 'circle'))
+                ]
+            "}},
+            r! { [9, 3] }
+        );
+    }
+
+    #[test]
+    fn dispatches_to_default() {
+        assert_eq!(
+            r! {{"
+                f <- function(x) UseMethod('f')
+                f.default <- function(x) 'default'
+                f(structure(1, class = 'other'))
+            "}},
+            r! { "default" }
+        );
+    }
+
+    #[test]
+    fn dispatches_on_implicit_class() {
+        assert_eq!(
+            r! {{"
+                f <- function(x) UseMethod('f')
+                f.numeric <- function(x) 'numeric'
+                f.character <- function(x) 'character'
+                [f(1), f('a')]
+            "}},
+            r! { ["numeric", "character"] }
+        );
+    }
+
+    #[test]
+    fn errors_without_applicable_method() {
+        r_expect! {{"
+            f <- function(x) UseMethod('f')
+            tryCatch(f(1), error = function(e) true)
+        "}}
+    }
+
+    #[test]
+    fn passes_additional_arguments() {
+        assert_eq!(
+            r! {{"
+                scale <- function(x, ...) UseMethod('scale')
+                scale.default <- function(x, by = 1, ...) x * by
+                [scale(2), scale(2, by = 3)]
+            "}},
+            r! { [2, 6] }
+        );
+    }
+
+    #[test]
+    fn evaluates_dispatch_argument_once() {
+        assert_eq!(
+            r! {{"
+                e <- environment()
+                e$n <- 0
+                f <- function(x) UseMethod('f')
+                f.default <- function(x) x
+                f({ e$n <- e$n + 1; 'value' })
+                e$n
+            "}},
+            r! { 1 }
+        );
+    }
+
+    #[test]
+    fn next_method_continues_dispatch() {
+        assert_eq!(
+            r! {{"
+                describe <- function(x) UseMethod('describe')
+                describe.default <- function(x) 'an object'
+                describe.animal <- function(x) paste('an animal,', NextMethod())
+                describe.dog <- function(x) paste('a dog,', NextMethod())
+                describe(structure(list(), class = ['dog', 'animal']))
+            "}},
+            r! { "a dog, an animal, an object" }
+        );
+    }
+
+    #[test]
+    fn next_method_falls_back_to_builtin() {
+        r_expect! {{"
+            length.stack <- function(x) NextMethod() + 100
+            length(structure(list(1, 2), class = 'stack')) == 102
+        "}}
+    }
+}
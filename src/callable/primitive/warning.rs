@@ -0,0 +1,137 @@
+use r_derive::*;
+use std::io::Write;
+
+use crate::callable::core::*;
+use crate::error::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::*;
+
+use super::stop::condition_from_args;
+
+/// Signal a Warning
+///
+/// Signals a warning condition. If no handler unwinds the stack or invokes
+/// the `muffleWarning` restart, the warning message is printed and evaluation
+/// continues.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// warning(...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `...`: Either a single condition object, or objects which are pasted
+///   together without separator to form the warning message.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// f <- function(x) { warning("careful!"); x }
+/// f(1)
+/// ```
+///
+/// ```custom,{class=r-repl}
+/// withCallingHandlers(
+///   f(1),
+///   warning = function(w) invokeRestart("muffleWarning")
+/// )
+/// ```
+///
+#[doc(alias = "warning")]
+#[builtin(sym = "warning")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveWarning;
+
+formals!(PrimitiveWarning, "(...)");
+
+impl Callable for PrimitiveWarning {
+    fn call_matched(&self, _args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let call = stack.frames[stack.env_frame()].call.clone();
+        let cond = condition_from_args(ellipsis, call, "simpleWarning", "warning", stack)?;
+        let message = condition_message(&cond);
+
+        if signal_with_muffle(cond, "muffleWarning", stack)? {
+            writeln!(stack.session.output, "Warning message:\n{message}").ok();
+        }
+
+        Ok(Obj::Vector(vec![message].into()))
+    }
+}
+
+/// Signal a warning from within a builtin
+///
+/// The warning is printed unless a handler muffles it.
+pub fn warn(message: &str, stack: &mut CallStack) -> Result<(), Signal> {
+    let call = stack.frames[stack.env_frame()].call.clone();
+    let cond = new_condition(
+        message.to_string(),
+        call,
+        vec!["simpleWarning", "warning", "condition"],
+    );
+
+    if signal_with_muffle(cond, "muffleWarning", stack)? {
+        writeln!(stack.session.output, "Warning message:\n{message}").ok();
+    }
+
+    Ok(())
+}
+
+/// Signal a condition with a muffling restart established
+///
+/// Returns `false` if the condition was muffled, in which case its default
+/// handling should be skipped.
+pub fn signal_with_muffle(cond: Obj, restart: &str, stack: &mut CallStack) -> Result<bool, Signal> {
+    let frame = stack.frames.len();
+    let n_restarts = stack.restarts.len();
+
+    stack
+        .restarts
+        .push(Restart { name: restart.to_string(), fun: Obj::Null, frame });
+    let result = stack.signal(cond);
+    stack.restarts.truncate(n_restarts);
+
+    match result {
+        Ok(()) => Ok(true),
+        Err(Signal::Condition(Cond::Restart(to, ..))) if to == frame => {
+            stack.frames.truncate(frame);
+            Ok(false)
+        }
+        Err(e) => Err(e),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn warnings_continue_evaluation() {
+        assert_eq!(r! { f <- function() { warning("hmm"); 1 }; f() }, r! { 1 });
+    }
+
+    #[test]
+    fn warnings_can_be_muffled() {
+        assert_eq!(
+            r! {{"
+                withCallingHandlers(
+                    { warning('hmm'); 2 },
+                    warning = function(w) invokeRestart('muffleWarning')
+                )
+            "}},
+            r! { 2 }
+        );
+    }
+
+    #[test]
+    fn warnings_can_be_caught() {
+        assert_eq!(
+            r! { tryCatch({ warning("hmm"); 2 }, warning = function(w) conditionMessage(w)) },
+            r! { "hmm" }
+        );
+    }
+}
@@ -0,0 +1,129 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::formals;
+use crate::lang::*;
+use crate::object::types::Character;
+use crate::object::*;
+
+/// Evaluate an Expression with Calling Handlers
+///
+/// Establishes calling handlers while evaluating an expression. When a
+/// condition is signaled that matches one of the handlers, the handler is
+/// called without unwinding the stack. If the handler returns, the search
+/// for further handlers continues. Handlers may invoke restarts to resume
+/// evaluation elsewhere.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// withCallingHandlers(expr, ...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `expr`: An expression to evaluate.
+/// * `...`: Named handler functions, where each name is a condition class.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// withCallingHandlers(
+///   { message("hello"); "done" },
+///   message = function(m) print("got a message")
+/// )
+/// ```
+///
+#[doc(alias = "withCallingHandlers")]
+#[builtin(sym = "withCallingHandlers")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveWithCallingHandlers;
+
+formals!(PrimitiveWithCallingHandlers, "(expr, ...)");
+
+impl Callable for PrimitiveWithCallingHandlers {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let frame = stack.frames.len();
+        let n_handlers = stack.handlers.len();
+
+        // the first handler takes precedence, so it is established last
+        let handlers = force_promises(ellipsis, stack)?;
+        for (class, fun) in handlers.into_iter().rev() {
+            let Character::Some(class) = class else {
+                continue;
+            };
+
+            stack
+                .handlers
+                .push(Handler { class: class.into(), fun, exiting: false, frame });
+        }
+
+        let result = args.try_get_named("expr")?.force(stack);
+        stack.handlers.truncate(n_handlers);
+        result
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn handlers_do_not_unwind() {
+        assert_eq!(
+            r! {{"
+                withCallingHandlers(
+                    { message('hi'); 'done' },
+                    message = function(m) invokeRestart('muffleMessage')
+                )
+            "}},
+            r! { "done" }
+        );
+    }
+
+    #[test]
+    fn handlers_are_called_before_exiting_handlers() {
+        assert_eq!(
+            r! {{"
+                tryCatch(
+                    withCallingHandlers(
+                        stop('a'),
+                        error = function(e) stop('b')
+                    ),
+                    error = function(e) conditionMessage(e)
+                )
+            "}},
+            r! { "b" }
+        );
+    }
+
+    #[test]
+    fn handlers_can_invoke_restarts() {
+        assert_eq!(
+            r! {{"
+                f <- function() withRestarts(
+                    stop('oops'),
+                    useValue = function(x) x
+                )
+                withCallingHandlers(f(), error = function(e) invokeRestart('useValue', 42))
+            "}},
+            r! { 42 }
+        );
+    }
+
+    #[test]
+    fn handlers_are_masked_while_running() {
+        assert_eq!(
+            r! {{"
+                withCallingHandlers(
+                    { warning('a'); 'done' },
+                    warning = function(w) { warning('b'); invokeRestart('muffleWarning') }
+                )
+            "}},
+            r! { "done" }
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use r_derive::*;
+
+use crate::callable::core::*;
+use crate::formals;
+use crate::internal_err;
+use crate::lang::*;
+use crate::object::types::Character;
+use crate::object::*;
+
+/// Evaluate an Expression with Restarts
+///
+/// Establishes restarts while evaluating an expression. When a restart is
+/// invoked using `invokeRestart()`, the stack is unwound back to
+/// `withRestarts()` and the restart function is called with the arguments
+/// passed to `invokeRestart()`. Its result becomes the result of
+/// `withRestarts()`.
+///
+/// # In-Language
+///
+/// ## Usage
+///
+/// ```custom,{class=r}
+/// withRestarts(expr, ...)
+/// ```
+///
+/// ## Arguments
+///
+/// * `expr`: An expression to evaluate.
+/// * `...`: Named restart functions.
+///
+/// ## Examples
+///
+/// ```custom,{class=r-repl}
+/// withRestarts(
+///   { invokeRestart("skip", 10); "not reached" },
+///   skip = function(x) x * 2
+/// )
+/// ```
+///
+#[doc(alias = "withRestarts")]
+#[builtin(sym = "withRestarts")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrimitiveWithRestarts;
+
+formals!(PrimitiveWithRestarts, "(expr, ...)");
+
+impl Callable for PrimitiveWithRestarts {
+    fn call_matched(&self, args: List, ellipsis: List, stack: &mut CallStack) -> EvalResult {
+        let mut args = Obj::List(args);
+        let frame = stack.frames.len();
+        let n_restarts = stack.restarts.len();
+
+        let restarts = force_promises(ellipsis, stack)?;
+        for (name, fun) in restarts.into_iter().rev() {
+            let Character::Some(name) = name else {
+                continue;
+            };
+
+            stack
+                .restarts
+                .push(Restart { name: name.into(), fun, frame });
+        }
+
+        let result = args.try_get_named("expr")?.force(stack);
+        let restarts = stack.restarts.split_off(n_restarts);
+
+        match result {
+            Err(Signal::Condition(Cond::Restart(to, name, args))) if to == frame => {
+                stack.frames.truncate(frame);
+                let Some(restart) = restarts.into_iter().rev().find(|r| r.name == name) else {
+                    return internal_err!();
+                };
+
+                stack.call_function(restart.fun, args)
+            }
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn restarts_unwind_to_establishing_call() {
+        assert_eq!(
+            r! {{"
+                withRestarts(
+                    { invokeRestart('skip', 10); 'not reached' },
+                    skip = function(x) x * 2
+                )
+            "}},
+            r! { 20 }
+        );
+    }
+
+    #[test]
+    fn innermost_restart_is_invoked() {
+        assert_eq!(
+            r! {{"
+                withRestarts(
+                    withRestarts(invokeRestart('r'), r = function() 'inner'),
+                    r = function() 'outer'
+                )
+            "}},
+            r! { "inner" }
+        );
+    }
+}
@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 pub enum Experiment {
     TailCalls,
     RestArgs,
+    Optimize,
 }
 
 /// Run the R REPL
@@ -1,23 +1,25 @@
 use std::rc::Rc;
 
 use crate::lang::{EvalResult, Signal};
+use crate::object::types::Character;
 use crate::object::*;
+use crate::parser::literal;
 use crate::{error::*, internal_err};
 
 pub trait Context: std::fmt::Debug + std::fmt::Display {
     #[inline]
-    fn get(&mut self, name: String) -> EvalResult {
+    fn get(&mut self, name: Symbol) -> EvalResult {
         (*self).env().get(name)
     }
     #[inline]
-    fn get_mut(&mut self, name: String) -> EvalResult {
+    fn get_mut(&mut self, name: Symbol) -> EvalResult {
         self.get(name)
     }
 
     #[inline]
     fn get_ellipsis(&mut self) -> EvalResult {
         let err = Err(Signal::Error(Error::IncorrectContext("...".to_string())));
-        self.get("...".to_string()).or(err)
+        self.get(Symbol::new("...")).or(err)
     }
 
     #[inline]
@@ -88,13 +90,13 @@ pub trait Context: std::fmt::Debug + std::fmt::Display {
                         }
                     }
                     // Avoid creating a new promise just to point to another, just reuse it
-                    (k, Expr::Symbol(s)) => match self.env().get(s.clone()) {
+                    (k, Expr::Symbol(s)) => match self.env().get(s) {
                         Ok(c @ Obj::Promise(..)) => {
-                            let k = k.map_or(OptionNA::NA, OptionNA::Some);
+                            let k = Character::from(k);
                             Ok(List::from(vec![(k, c)]).iter_pairs())
                         }
                         _ => {
-                            let k = k.map_or(OptionNA::NA, OptionNA::Some);
+                            let k = Character::from(k);
                             Ok(List::from(vec![(
                                 k,
                                 Obj::Promise(None, Expr::Symbol(s), self.env()),
@@ -103,12 +105,15 @@ pub trait Context: std::fmt::Debug + std::fmt::Display {
                         }
                     },
                     (k, c @ Expr::Call(..)) => {
-                        let k = k.map_or(OptionNA::NA, OptionNA::Some);
+                        let k = Character::from(k);
                         let elem = vec![(k, Obj::Promise(None, c, self.env()))];
                         Ok(List::from(elem).iter_pairs())
                     }
+                    // literals need no evaluation, but retain their expression
                     (k, v) => {
-                        Ok(List::from(vec![(k, Obj::Promise(None, v, self.env()))]).iter_pairs())
+                        let value = literal(&v).map(Box::new);
+                        let k = Character::from(k);
+                        Ok(List::from(vec![(k, Obj::Promise(value, v, self.env()))]).iter_pairs())
                     }
                 })
                 .collect::<Result<Vec<_>, _>>()?
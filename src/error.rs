@@ -1,6 +1,6 @@
 use crate::{
     lang::{CallStack, Signal},
-    object::Expr,
+    object::{Attributes, Expr, List, Obj},
     parser::*,
 };
 
@@ -29,8 +29,10 @@ pub enum Error {
     CannotBeCoercedToLogical,
     CannotBeCoercedTo(&'static str),
     InvalidRange,
+    UnboundedSequence,
 
     NonRecyclableLengths(usize, usize),
+    IncompatibleFormatSpec(String, String),
 
     // destructuring
     CannotBeDestructuredIntoList,
@@ -47,6 +49,11 @@ pub enum Error {
     ParseFailureVerbose(pest::error::Error<en::Rule>),
     ParseFailure(pest::error::Error<en::Rule>),
     ParseUnexpected(en::Rule, (usize, usize)),
+    InvalidEscape(String),
+    EscapeWithoutHexDigits(String),
+    InvalidUnicodeEscape(String),
+    NulEscape(String),
+    InvalidFormatSpec(String),
 
     // temporary workaround until we propagate call stack to all error locations
     WithCallStack(Box<Error>, CallStack),
@@ -59,6 +66,13 @@ pub enum Error {
     // features
     FeatureDisabledRestArgs,
 
+    // namespaces
+    NamespaceNotFound(String),
+    NotExported(String, String),
+
+    // conditions signaled from within the language, e.g. through `stop()`
+    Condition(Obj),
+
     Other(String),
 }
 
@@ -75,6 +89,22 @@ impl Error {
             Error::ParseUnexpected(rule, _span) => {
                 format!("Parse failed. Found unexpected parsing rule '{:#?}'", rule)
             }
+            Error::InvalidEscape(escape) => {
+                format!("'{escape}' is an unrecognized escape in character string")
+            }
+            Error::EscapeWithoutHexDigits(escape) => {
+                format!("'{escape}' used without hex digits in character string")
+            }
+            Error::InvalidUnicodeEscape(escape) => {
+                format!("'{escape}' is an invalid unicode escape in character string")
+            }
+            Error::NulEscape(escape) => {
+                format!("nul character '{escape}' not allowed in character string")
+            }
+            Error::InvalidFormatSpec(spec) => format!("'{spec}' is an invalid format spec"),
+            Error::IncompatibleFormatSpec(spec, type_of) => {
+                format!("format spec '{spec}' cannot be applied to values of type '{type_of}'")
+            }
             Error::NotInterpretableAsLogical => {
                 "argument is not interpretable as logical".to_string()
             }
@@ -100,7 +130,11 @@ impl Error {
             Error::InvalidRange => {
                 "Ranges without a `by` should have an end >= start. Provide a negative `by` for the range using `start:by:end`".to_string()
             }
+            Error::UnboundedSequence => {
+                "unbounded sequences can only be iterated over or indexed".to_string()
+            }
             Error::Other(s) => s.to_string(),
+            Error::Condition(cond) => condition_message(cond),
             Error::WithCallStack(e, c) => format!("{}\n{c}", e.as_str()),
             Error::ArgumentMissing(s) => format!("argument '{s}' is missing with no default"),
             Error::ArgumentInvalid(s) => format!("argument '{s}' is invalid"),
@@ -121,6 +155,10 @@ impl Error {
                 "..rest syntax currently disabled. To enable launch with\n\n    --experiments rest-args\n".to_string()
             }
             Error::Missing => "object is missing".to_string(),
+            Error::NamespaceNotFound(name) => format!("there is no namespace called '{name}'"),
+            Error::NotExported(name, namespace) => {
+                format!("'{name}' is not an exported object from 'namespace:{namespace}'")
+            }
             Error::InvalidFunctionParameter(expr) => format!("invalid function parameter: {}", expr),
             Error::DuplicatedParameter(name) => format!("duplicated parameter name: {}", name),
             Error::DuplicatedMoreParameter() => "duplicated '..<more>' parameters".to_string(),
@@ -128,6 +166,59 @@ impl Error {
         }
     }
 
+    /// The condition class used when an error is signaled as a condition
+    pub fn class(&self) -> &'static str {
+        match self {
+            Error::VariableNotFound(_) => "objectNotFoundError",
+            Error::IncorrectContext(_) => "incorrectContextError",
+            Error::NotInterpretableAsLogical
+            | Error::CannotBeCoercedToCharacter
+            | Error::CannotBeCoercedToDouble
+            | Error::CannotBeCoercedToInteger
+            | Error::CannotBeCoercedToLogical
+            | Error::CannotBeCoercedTo(_) => "coercionError",
+            Error::ConditionIsNotScalar => "conditionLengthError",
+            Error::InvalidRange | Error::UnboundedSequence => "invalidRangeError",
+            Error::NonRecyclableLengths(..) => "recyclingError",
+            Error::CannotBeDestructuredIntoList => "destructuringError",
+            Error::InvalidFunctionParameter(_)
+            | Error::DuplicatedParameter(_)
+            | Error::DuplicatedMoreParameter() => "functionDefinitionError",
+            Error::Missing | Error::ArgumentMissing(_) => "missingArgumentError",
+            Error::ArgumentInvalid(_) => "invalidArgumentError",
+            Error::ParseFailureVerbose(_)
+            | Error::ParseFailure(_)
+            | Error::ParseUnexpected(..)
+            | Error::InvalidEscape(_)
+            | Error::EscapeWithoutHexDigits(_)
+            | Error::InvalidUnicodeEscape(_)
+            | Error::NulEscape(_)
+            | Error::InvalidFormatSpec(_) => "parseError",
+            Error::IncompatibleFormatSpec(..) => "formatError",
+            Error::WithCallStack(e, _) => e.class(),
+            Error::Unimplemented(_) => "unimplementedError",
+            Error::Internal(..) => "internalError",
+            Error::CannotEvaluateAsMutable(_) => "mutabilityError",
+            Error::FeatureDisabledRestArgs => "featureDisabledError",
+            Error::NamespaceNotFound(_) | Error::NotExported(..) => "namespaceError",
+            Error::Condition(_) | Error::Other(_) => "simpleError",
+        }
+    }
+
+    pub fn is_condition(&self) -> bool {
+        matches!(self, Error::Condition(_))
+    }
+
+    /// Convert an error into a condition object, with class
+    /// `c(<error class>, "error", "condition")`
+    pub fn into_condition(self, call: Expr) -> Obj {
+        match self {
+            Error::Condition(cond) => cond,
+            Error::WithCallStack(e, _) => e.into_condition(call),
+            e => new_condition(e.as_str(), call, vec![e.class(), "error", "condition"]),
+        }
+    }
+
     pub fn from_parse_error<R>(input: &str, error: pest::error::Error<R>) -> Error
     where
         R: pest::RuleType + Into<en::Rule>,
@@ -159,6 +250,39 @@ impl Error {
     }
 }
 
+/// Build a condition object
+///
+/// Conditions are represented as lists with `message` and `call` elements,
+/// with a `class` attribute.
+pub fn new_condition(message: String, call: Expr, class: Vec<&str>) -> Obj {
+    let call = match call {
+        Expr::Null => Obj::Null,
+        call => Obj::Expr(call),
+    };
+
+    let class: Vec<String> = class.into_iter().map(String::from).collect();
+
+    let attrs = Attributes::from_iter([("class".to_string(), Obj::Vector(class.into()))]);
+
+    Obj::List(List::from(vec![
+        (
+            Some("message".to_string()),
+            Obj::Vector(vec![message].into()),
+        ),
+        (Some("call".to_string()), call),
+    ]))
+    .with_attributes(Some(attrs))
+}
+
+/// Retrieve the message of a condition object
+pub fn condition_message(cond: &Obj) -> String {
+    let mut cond = cond.clone();
+    match cond.try_get_named("message") {
+        Ok(Obj::Vector(v)) => v.into(),
+        _ => String::new(),
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Error: {}", self.as_str())
@@ -1,5 +1,6 @@
+use crate::bytecode::Compiled;
 use crate::callable::builtins::BUILTIN;
-use crate::callable::core::Callable;
+use crate::callable::core::{Builtin, Callable};
 use crate::cli::Experiment;
 use crate::context::Context;
 use crate::error::*;
@@ -14,6 +15,7 @@ use hashbrown::HashSet;
 
 use core::fmt;
 use std::fmt::Display;
+use std::io::Write;
 use std::rc::Rc;
 
 pub type EvalResult = Result<Obj, Signal>;
@@ -23,6 +25,8 @@ pub enum Cond {
     Break,
     Continue,
     Terminate,
+    Unwind(usize, Obj, Obj),      // (establishing frame, handler, condition)
+    Restart(usize, String, List), // (establishing frame, restart name, arguments)
 }
 
 impl From<Cond> for Signal {
@@ -69,6 +73,8 @@ impl ViewMut for Obj {
                 Vector::Character(v) => Vector::Character(v.view_mut()),
                 Vector::Integer(v) => Vector::Integer(v.view_mut()),
                 Vector::Logical(v) => Vector::Logical(v.view_mut()),
+                Vector::Complex(v) => Vector::Complex(v.view_mut()),
+                Vector::Raw(v) => Vector::Raw(v.view_mut()),
             }),
 
             Obj::List(l) => Obj::List(l.view_mut()),
@@ -87,6 +93,8 @@ impl Obj {
                 Vector::Integer(_) => "integer",
                 Vector::Double(_) => "double",
                 Vector::Logical(_) => "logical",
+                Vector::Complex(_) => "complex",
+                Vector::Raw(_) => "raw",
             },
             Obj::List(_) => "list",
             Obj::Expr(_) => "expression",
@@ -140,6 +148,12 @@ impl Obj {
                         Vector::Double(r) => {
                             l.assign(r)?;
                         }
+                        Vector::Complex(r) => {
+                            l.assign(r)?;
+                        }
+                        Vector::Raw(r) => {
+                            l.assign(r)?;
+                        }
                     },
                     _ => return Err(err.into()),
                 };
@@ -157,18 +171,18 @@ impl Obj {
             Obj::List(l) => Ok(Obj::List(l.clone())),
             Obj::Expr(e) => match e {
                 Expr::List(exprlist) => Ok(Obj::List(List::from(
-                    exprlist
+                    (**exprlist)
                         .clone()
                         .into_iter()
-                        .map(|(k, v)| (k, Obj::Expr(v)))
+                        .map(|(k, v)| (k.map(String::from), Obj::Expr(v)))
                         .collect::<Vec<_>>(),
                 ))),
                 Expr::Function(_, _) => internal_err!(),
                 Expr::Call(what, args) => Ok(Obj::List(List::from(
                     vec![(None, (**what).clone())]
                         .into_iter()
-                        .chain((*args).clone())
-                        .map(|(k, v)| (k, Obj::Expr(v)))
+                        .chain((**args).clone())
+                        .map(|(k, v)| (k.map(String::from), Obj::Expr(v)))
                         .collect::<Vec<_>>(),
                 ))),
                 other => Ok(Obj::List(List::from(vec![(
@@ -184,6 +198,7 @@ impl Obj {
 
     pub fn as_integer(self) -> EvalResult {
         match self {
+            Obj::Vector(v) if v.is_unbounded() => Error::UnboundedSequence.into(),
             Obj::Vector(v) => Ok(Obj::Vector(v.as_integer())),
             Obj::Null => Ok(Obj::Vector(Vector::from(Vec::<Integer>::new()))),
             _ => Err(Signal::Error(Error::CannotBeCoercedToInteger)),
@@ -192,6 +207,7 @@ impl Obj {
 
     pub fn as_double(self) -> EvalResult {
         match self {
+            Obj::Vector(v) if v.is_unbounded() => Error::UnboundedSequence.into(),
             Obj::Vector(v) => Ok(Obj::Vector(v.as_double())),
             Obj::Null => Ok(Obj::Vector(Vector::from(Vec::<Double>::new()))),
             _ => Error::CannotBeCoercedToDouble.into(),
@@ -200,14 +216,34 @@ impl Obj {
 
     pub fn as_logical(self) -> EvalResult {
         match self {
+            Obj::Vector(v) if v.is_unbounded() => Error::UnboundedSequence.into(),
             Obj::Vector(v) => Ok(Obj::Vector(v.as_logical())),
             Obj::Null => Ok(Obj::Vector(Vector::from(Vec::<Logical>::new()))),
             _ => Error::CannotBeCoercedToLogical.into(),
         }
     }
 
+    pub fn as_complex(self) -> EvalResult {
+        match self {
+            Obj::Vector(v) if v.is_unbounded() => Error::UnboundedSequence.into(),
+            Obj::Vector(v) => Ok(Obj::Vector(v.as_complex())),
+            Obj::Null => Ok(Obj::Vector(Vector::from(Vec::<Complex>::new()))),
+            _ => Error::CannotBeCoercedTo("complex").into(),
+        }
+    }
+
+    pub fn as_raw(self) -> EvalResult {
+        match self {
+            Obj::Vector(v) if v.is_unbounded() => Error::UnboundedSequence.into(),
+            Obj::Vector(v) => Ok(Obj::Vector(v.as_raw())),
+            Obj::Null => Ok(Obj::Vector(Vector::from(Vec::<Raw>::new()))),
+            _ => Error::CannotBeCoercedTo("raw").into(),
+        }
+    }
+
     pub fn as_character(self) -> EvalResult {
         match self {
+            Obj::Vector(v) if v.is_unbounded() => Error::UnboundedSequence.into(),
             Obj::Vector(v) => Ok(Obj::Vector(v.as_character())),
             Obj::Null => Ok(Obj::Vector(Vector::from(Vec::<Character>::new()))),
             _ => Error::CannotBeCoercedToCharacter.into(),
@@ -227,16 +263,16 @@ impl Obj {
         use Vector::*;
         match self {
             Obj::Vector(rvec) => match rvec {
-                Double(v) => match v.inner().clone().borrow()[..] {
-                    [Some(x)] => Ok(x as usize),
+                Double(v) => match v.as_scalar() {
+                    Option::Some(Some(x)) => Ok(x as usize),
                     _ => Err(Signal::Error(Error::CannotBeCoercedToInteger)),
                 },
-                Integer(v) => match v.inner().clone().borrow()[..] {
-                    [Some(x)] => Ok(x as usize),
+                Integer(v) => match v.as_scalar() {
+                    Option::Some(Some(x)) => Ok(x as usize),
                     _ => Err(Signal::Error(Error::CannotBeCoercedToInteger)),
                 },
-                Logical(v) => match v.inner().clone().borrow()[..] {
-                    [Some(true)] => Ok(1_usize),
+                Logical(v) => match v.as_scalar() {
+                    Option::Some(Some(true)) => Ok(1_usize),
                     _ => Err(Signal::Error(Error::CannotBeCoercedToInteger)),
                 },
                 _ => Err(Signal::Error(Error::CannotBeCoercedToInteger)),
@@ -259,7 +295,9 @@ impl Obj {
 
     pub fn environment(&self) -> Option<Rc<Environment>> {
         match self {
-            Obj::Promise(.., e) | Obj::Function(.., e) | Obj::Environment(e) => Some(e.clone()),
+            Obj::Promise(.., e) | Obj::Function(_, _, e, ..) | Obj::Environment(e) => {
+                Some(e.clone())
+            }
             _ => None,
         }
     }
@@ -268,7 +306,7 @@ impl Obj {
     pub fn try_set_named(&mut self, name: &str, value: Obj) -> EvalResult {
         match self {
             Obj::List(l) => {
-                let subset = Subset::Names(vec![Character::Some(name.to_string())].into());
+                let subset = Subset::Names(vec![Character::Some(name.into())].into());
                 Ok(l.set_subset(subset, value)?)
             }
             Obj::Environment(e) => {
@@ -283,10 +321,10 @@ impl Obj {
     pub fn try_get_named_mut(&mut self, name: &str) -> EvalResult {
         match self {
             Obj::List(l) => {
-                let subset = Subset::Names(vec![Character::Some(name.to_string())].into());
+                let subset = Subset::Names(vec![Character::Some(name.into())].into());
                 Ok(l.try_get_inner_mut(subset)?)
             }
-            Obj::Environment(e) => match e.get_mut(String::from(name)) {
+            Obj::Environment(e) => match e.get_mut(Symbol::new(name)) {
                 Ok(v) => Ok(v),
                 Err(_) => Err(Error::VariableNotFound(name.into()).into()),
             },
@@ -296,12 +334,20 @@ impl Obj {
 
     /// Used for `$`-access.
     pub fn try_get_named(&mut self, name: &str) -> EvalResult {
+        // data frames are `NULL` at names which aren't one of their columns
+        if let (true, Obj::List(l)) = (self.is_data_frame(), &self) {
+            let is_column = |col: Character| matches!(col, OptionNA::Some(col) if col == name);
+            if !l.iter_names().is_some_and(|mut n| n.any(is_column)) {
+                return Ok(Obj::Null);
+            }
+        }
+
         match self {
             Obj::List(l) => {
-                let subset = Subset::Names(vec![Character::Some(name.to_string())].into());
+                let subset = Subset::Names(vec![Character::Some(name.into())].into());
                 Ok(l.try_get_inner(subset)?)
             }
-            Obj::Environment(e) => match e.get(String::from(name)) {
+            Obj::Environment(e) => match e.get(Symbol::new(name)) {
                 Ok(v) => Ok(v),
                 Err(_) => Err(Error::VariableNotFound(name.into()).into()),
             },
@@ -313,7 +359,12 @@ impl Obj {
     pub fn try_get(&self, index: Obj) -> EvalResult {
         let index = index.as_vector()?;
         match self {
+            Obj::Vector(v) if v.factor_levels().is_some() => match v.try_get(index)? {
+                Obj::Vector(x) => Ok(Obj::Vector(x.with_factor_attributes(v))),
+                _ => internal_err!(),
+            },
             Obj::Vector(v) => v.try_get(index),
+            Obj::List(_) if self.is_data_frame() => self.try_get_frame(None, Some(index), false),
             Obj::List(l) => {
                 let subset = Subset::try_from(index)?;
                 let x = l.subset(subset);
@@ -326,7 +377,7 @@ impl Obj {
     // Used for `[[`-access.
     pub fn try_get_inner(&self, index: Obj) -> EvalResult {
         match self {
-            Obj::Vector(v) => v.try_get(index),
+            Obj::Vector(_) => self.try_get(index),
             Obj::List(l) => EvalResult::Ok(l.try_get_inner(index.try_into()?)?),
             // To access environments use try_get_named
             Obj::Environment(_) => internal_err!(),
@@ -355,6 +406,154 @@ impl Obj {
     pub fn is_empty(&self) -> bool {
         self.len().is_some_and(|i| i > 0)
     }
+    /// The attributes of an object, excluding names
+    /// Whether the object is a factor
+    pub fn is_factor(&self) -> bool {
+        matches!(self, Obj::Vector(v) if v.factor_levels().is_some())
+    }
+
+    pub fn attributes(&self) -> Option<Attributes> {
+        match self {
+            Obj::Vector(v) => v.attributes(),
+            Obj::List(l) => l.attributes(),
+            Obj::Function(_, _, _, attrs, _) => attrs.clone(),
+            _ => None,
+        }
+    }
+
+    /// Replace the attributes of an object, excluding names
+    pub fn with_attributes(self, attrs: Option<Attributes>) -> Obj {
+        match self {
+            Obj::Vector(v) => Obj::Vector(v.with_attributes(attrs)),
+            Obj::List(l) => Obj::List(l.with_attributes(attrs)),
+            Obj::Function(formals, body, env, _, code) => Obj::Function(
+                formals,
+                body,
+                env,
+                attrs.and_then(|a| a.into_option()),
+                code,
+            ),
+            obj => obj,
+        }
+    }
+
+    /// Get an attribute by name
+    ///
+    /// Names are stored separately from other attributes, but are accessible
+    /// as the `names` attribute.
+    pub fn get_attr(&self, name: &str) -> Option<Obj> {
+        match (name, self) {
+            ("names", Obj::Vector(v)) => v.names().map(|n| Obj::Vector(n.into())),
+            ("names", Obj::List(l)) => l.names().map(|n| Obj::Vector(n.into())),
+            _ => self.attributes()?.get(name).cloned(),
+        }
+    }
+
+    /// Set an attribute by name, where a `NULL` value removes the attribute
+    pub fn set_attr(self, name: &str, value: Obj) -> EvalResult {
+        match (name, self, value) {
+            ("names", Obj::Vector(v), Obj::Null) => Ok(Obj::Vector(v.without_names())),
+            ("names", Obj::List(l), Obj::Null) => Ok(Obj::List(l.without_names())),
+            ("names", x @ (Obj::Vector(_) | Obj::List(_)), names) => {
+                let Obj::Vector(Vector::Character(names)) = names.as_character()? else {
+                    return internal_err!();
+                };
+
+                let n = x.len().unwrap_or_default();
+                let mut names: Vec<Character> = names.iter_values().collect();
+                if names.len() > n {
+                    return Error::Other(format!(
+                        "'names' attribute [{}] must be the same length as the vector [{n}]",
+                        names.len()
+                    ))
+                    .into();
+                }
+                names.resize(n, Character::NA);
+
+                Ok(match x {
+                    Obj::Vector(v) => Obj::Vector(v.materialize()?.set_names(names.into())),
+                    Obj::List(l) => Obj::List(l.materialize()?.set_names(names.into())),
+                    _ => unreachable!(),
+                })
+            }
+            ("dim", x @ (Obj::Vector(_) | Obj::List(_)), dim @ Obj::Vector(_)) => x.set_dim(dim),
+            (_, x @ (Obj::Vector(_) | Obj::List(_) | Obj::Function(..)), value) => {
+                let mut attrs = x.attributes().unwrap_or_default();
+                attrs.set(name, value);
+                Ok(x.with_attributes(Some(attrs)))
+            }
+            (_, Obj::Null, _) => {
+                Error::Other("attempt to set an attribute on NULL".to_string()).into()
+            }
+            (_, x, _) => Error::Other(format!(
+                "attributes cannot be set on an object of type '{}'",
+                x.type_of()
+            ))
+            .into(),
+        }
+    }
+
+    /// Whether an object has an explicit `class` attribute
+    pub fn is_object(&self) -> bool {
+        self.attributes().is_some_and(|a| a.get("class").is_some())
+    }
+
+    /// The class of an object
+    ///
+    /// Uses the `class` attribute if present, otherwise the class is implied
+    /// by the dimensions and type of the object.
+    pub fn class(&self) -> Vec<String> {
+        match self.attributes().and_then(|a| a.get("class").cloned()) {
+            Some(Obj::Vector(v @ Vector::Character(_))) => v.into(),
+            _ => match self.dim().map(|d| d.len()) {
+                Some(2) => vec!["matrix".to_string(), "array".to_string()],
+                Some(_) => vec!["array".to_string()],
+                None => vec![self.implicit_class().to_string()],
+            },
+        }
+    }
+
+    /// The classes used to find methods when dispatching on an object
+    ///
+    /// Objects without a `class` attribute additionally dispatch on their
+    /// numeric type, such that both `f.double` and `f.numeric` apply to
+    /// doubles. Matrices and arrays first dispatch on `matrix` and `array`.
+    pub fn dispatch_class(&self) -> Vec<String> {
+        if self.is_object() {
+            return self.class();
+        }
+
+        let mut classes = match self.dim() {
+            Some(_) => self.class(),
+            None => vec![],
+        };
+
+        match self {
+            Obj::Vector(Vector::Double(_)) => classes.extend(["double".into(), "numeric".into()]),
+            Obj::Vector(Vector::Integer(_)) => classes.extend(["integer".into(), "numeric".into()]),
+            _ => classes.push(self.implicit_class().to_string()),
+        }
+
+        classes
+    }
+
+    fn implicit_class(&self) -> &'static str {
+        match self {
+            Obj::Null => "NULL",
+            Obj::Vector(Vector::Double(_)) => "numeric",
+            Obj::Vector(Vector::Integer(_)) => "integer",
+            Obj::Vector(Vector::Logical(_)) => "logical",
+            Obj::Vector(Vector::Complex(_)) => "complex",
+            Obj::Vector(Vector::Raw(_)) => "raw",
+            Obj::Vector(Vector::Character(_)) => "character",
+            Obj::List(_) => "list",
+            Obj::Expr(Expr::Symbol(_)) => "name",
+            Obj::Expr(_) => "call",
+            Obj::Promise(..) => "promise",
+            Obj::Function(..) => "function",
+            Obj::Environment(_) => "environment",
+        }
+    }
 }
 
 impl TryInto<List> for Obj {
@@ -384,30 +583,98 @@ impl TryInto<bool> for Obj {
 
 impl Display for Obj {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Obj::Vector(v) => write!(f, "{}", v),
-            Obj::Null => write!(f, "NULL"),
-            Obj::Environment(x) => write!(f, "<environment {:?}>", x.values.as_ptr()),
-            Obj::Function(formals, Expr::Primitive(primitive), _) => {
-                write!(
-                    f,
-                    "function({}) .Primitive(\"{}\")",
-                    formals,
-                    primitive.rfmt()
-                )
-            }
-            Obj::Function(formals, body, parent_env) => {
-                let parent_env = Obj::Environment(Rc::clone(parent_env));
-                write!(f, "function({}) {}\n{}", formals, body, parent_env)
+        display_value(self, f)?;
+
+        if let Some(attrs) = self.attributes() {
+            for (name, value) in attrs.iter() {
+                // dimensions are displayed by the layout of the values
+                if name == "dim" && matches!(self, Obj::Vector(_)) {
+                    continue;
+                }
+
+                // factor levels are displayed alongside the labels
+                if (name == "levels" || name == "class") && self.is_factor() {
+                    continue;
+                }
+
+                // data frames are displayed as a table of their rows
+                if (name == "row.names" || name == "class") && self.is_data_frame() {
+                    continue;
+                }
+
+                write!(f, "\nattr(,\"{name}\")\n{value}")?;
             }
-            Obj::List(vals) => display_list(vals, f, None),
-            Obj::Promise(None, expr, env) => write!(f, "{expr} @ {env}"),
-            Obj::Promise(Some(obj), ..) => write!(f, "{obj}"),
-            Obj::Expr(expr) => write!(f, "{}", expr),
         }
+
+        Ok(())
+    }
+}
+
+fn display_value(x: &Obj, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match x {
+        Obj::Vector(v) => match (x.dim(), v.factor_levels()) {
+            (Some(dim), _) if dim.len() >= 2 => display_array(v, &dim, f),
+            (_, Some(levels)) => display_factor(v, &levels, f),
+            _ => write!(f, "{}", v),
+        },
+        Obj::Null => write!(f, "NULL"),
+        Obj::Environment(x) => write!(f, "<environment {:?}>", x.values.as_ptr()),
+        Obj::Function(formals, Expr::Primitive(primitive), ..) => {
+            write!(
+                f,
+                "function({}) .Primitive(\"{}\")",
+                formals,
+                primitive.rfmt()
+            )
+        }
+        Obj::Function(formals, body, parent_env, ..) => {
+            let parent_env = Obj::Environment(Rc::clone(parent_env));
+            write!(f, "function({}) {}\n{}", formals, body, parent_env)
+        }
+        Obj::List(_) if x.is_data_frame() => display_data_frame(x, f),
+        Obj::List(vals) => display_list(vals, f, None),
+        Obj::Promise(None, expr, env) => write!(f, "{expr} @ {env}"),
+        Obj::Promise(Some(obj), ..) => write!(f, "{obj}"),
+        Obj::Expr(expr) => write!(f, "{}", expr),
     }
 }
 
+fn display_factor(x: &Vector, levels: &[Character], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let label = |level: &Character| match level {
+        OptionNA::Some(level) => level.to_string(),
+        OptionNA::NA => "<NA>".to_string(),
+    };
+
+    let labels: Vec<String> = match x.clone().as_character() {
+        Vector::Character(v) => v.iter_values().map(|l| label(&l)).collect(),
+        _ => unreachable!(),
+    };
+
+    if labels.is_empty() {
+        write!(f, "factor(0)")?;
+    }
+
+    // labels are printed unquoted and left-aligned, wrapping at 80 columns
+    let n = labels.len();
+    let nlen = n.to_string().len();
+    let width = labels.iter().map(String::len).max().unwrap_or(0);
+    let per_line = std::cmp::max(1, (80 - (nlen + 3)) / (width + 1));
+
+    for (i, line) in labels.chunks(per_line).enumerate() {
+        if i > 0 {
+            writeln!(f)?;
+        }
+
+        let index = format!("[{}]", i * per_line + 1);
+        write!(f, "{:>1$}", index, nlen + 2)?;
+        let line: Vec<String> = line.iter().map(|l| format!("{:<1$}", l, width)).collect();
+        write!(f, " {}", line.join(" ").trim_end())?;
+    }
+
+    let levels: Vec<String> = levels.iter().map(label).collect();
+    write!(f, "\nLevels: {}", levels.join(" "))
+}
+
 fn display_list(x: &List, f: &mut fmt::Formatter<'_>, bc: Option<String>) -> fmt::Result {
     if x.is_empty() {
         write!(f, "list()")?;
@@ -617,23 +884,34 @@ pub struct Frame {
     pub to: Obj,
     // The evaluation environment for the frame
     pub env: Rc<Environment>,
+    // Expressions to evaluate when the frame exits, registered by `on.exit()`
+    pub on_exit: Vec<Expr>,
+    // Whether the frame has exited. Frames that exit with an error are kept
+    // on the stack to produce a backtrace.
+    pub exited: bool,
+    // The method dispatch that introduced this frame, if any
+    pub dispatch: Option<Dispatch>,
 }
 
 impl Frame {
     pub fn new(call: Expr, mut env: Rc<Environment>) -> Frame {
         let to = match call.clone() {
-            Expr::Call(what, _) => env.eval(*what).unwrap_or_default(),
+            Expr::Call(what, _) => env.eval((*what).clone()).unwrap_or_default(),
             _ => Obj::Null,
         };
 
-        Self { call, to, env }
+        Self {
+            call,
+            to,
+            env,
+            on_exit: vec![],
+            exited: false,
+            dispatch: None,
+        }
     }
 
     pub fn new_child_env(&self) -> Box<dyn Context> {
-        Box::new(Obj::Environment(Rc::new(Environment {
-            parent: Some(self.env().clone()),
-            ..Default::default()
-        })))
+        Box::new(Obj::Environment(Environment::new(Some(self.env().clone()))))
     }
 }
 
@@ -643,10 +921,44 @@ impl Display for Frame {
     }
 }
 
+/// The state of an S3 method dispatch, as started by `UseMethod()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dispatch {
+    // The name of the generic being dispatched
+    pub generic: String,
+    // The classes yet to be searched by `NextMethod()`
+    pub classes: Vec<String>,
+    // The arguments passed to each method
+    pub args: List,
+}
+
+/// A condition handler, as established by `tryCatch()` or `withCallingHandlers()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Handler {
+    // The condition class that this handler responds to
+    pub class: String,
+    // The function called with the signaled condition
+    pub fun: Obj,
+    // Whether the handler unwinds the stack back to its establishing frame
+    pub exiting: bool,
+    // The frame depth at which the handler was established
+    pub frame: usize,
+}
+
+/// A restart, as established by `withRestarts()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Restart {
+    pub name: String,
+    pub fun: Obj,
+    pub frame: usize,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct CallStack {
     pub session: Session,
     pub frames: Vec<Frame>,
+    pub handlers: Vec<Handler>,
+    pub restarts: Vec<Restart>,
 }
 
 impl CallStack {
@@ -681,7 +993,7 @@ impl CallStack {
                     }
                     (k, v) => match self.eval_and_finalize(v) {
                         Ok(elem) => {
-                            let k = k.map_or(OptionNA::NA, OptionNA::Some);
+                            let k = Character::from(k);
                             Ok(List::from(vec![(k, elem)]).iter_pairs())
                         }
                         Err(e) => Err(e),
@@ -707,14 +1019,13 @@ impl CallStack {
 
 impl Default for CallStack {
     fn default() -> Self {
-        let global_env = Rc::new(Environment {
-            parent: Some(Environment::from_builtins()),
-            ..Default::default()
-        });
+        let global_env = Environment::new(Some(Environment::from_builtins()));
 
         CallStack {
             session: Session::default(),
             frames: vec![Frame::new(Expr::Null, global_env)],
+            handlers: vec![],
+            restarts: vec![],
         }
     }
 }
@@ -733,7 +1044,7 @@ impl CallStack {
     /// Find an object in the current environment or one of its parents and return a mutable view
     /// of the object, as well as the environment in which it was found.
     /// None is returned if the value was not found.
-    fn find(&mut self, name: String) -> Result<(Obj, Rc<Environment>), Signal> {
+    fn find(&mut self, name: Symbol) -> Result<(Obj, Rc<Environment>), Signal> {
         let mut env = self.env();
         loop {
             // search in this environment for value by name
@@ -761,11 +1072,17 @@ impl CallStack {
 
         if let Some(prim) = BUILTIN.get(name.as_str()) {
             Result::Ok((
-                Obj::Function(ExprList::new(), Expr::Primitive(prim.clone()), self.env()),
+                Obj::Function(
+                    ExprList::new(),
+                    Expr::Primitive(prim.clone()),
+                    self.env(),
+                    None,
+                    Compiled::default(),
+                ),
                 env,
             ))
         } else {
-            Result::Err(Signal::Error(Error::VariableNotFound(name)))
+            Result::Err(Signal::Error(Error::VariableNotFound(name.into())))
         }
     }
 
@@ -775,7 +1092,7 @@ impl CallStack {
     }
 
     pub fn add_child_frame(&mut self, call: Expr, env: Rc<Environment>) -> usize {
-        let local_env = Rc::new(Environment { parent: Some(env.clone()), ..Default::default() });
+        let local_env = Environment::new(Some(env.clone()));
 
         self.add_frame(call, local_env)
     }
@@ -803,14 +1120,388 @@ impl CallStack {
         }
     }
 
+    /// Find the frame which introduced the current environment
+    ///
+    /// Calls to primitives and forced promises introduce frames that share
+    /// the environment of their caller. This finds the outermost of the
+    /// most recent frames sharing the current environment.
+    pub fn env_frame(&self) -> usize {
+        let env = self.env();
+        let mut i = self.frames.len().saturating_sub(1);
+        while i > 0 && Rc::ptr_eq(&self.frames[i - 1].env, &env) {
+            i -= 1;
+        }
+        i
+    }
+
+    /// Report copies of data traced by `tracemem()`
+    ///
+    /// Each copy is reported along with the closures on the call stack,
+    /// innermost first.
+    pub fn report_traced_copies(&mut self) {
+        let copies = take_traced_copies();
+        if copies.is_empty() {
+            return;
+        }
+
+        let calls: String = self
+            .frames
+            .iter()
+            .rev()
+            .filter(|frame| !frame.exited)
+            .filter_map(|frame| match (&frame.call, &frame.to) {
+                (Expr::Call(what, _), Obj::Function(_, body, ..))
+                    if !matches!(body, Expr::Primitive(_)) =>
+                {
+                    Some(format!(" {what}"))
+                }
+                _ => None,
+            })
+            .collect();
+
+        for (from, to) in copies {
+            writeln!(
+                self.session.output,
+                "tracemem[{from:#x} -> {to:#x}]:{calls}"
+            )
+            .ok();
+        }
+    }
+
     pub fn pop_frame_and_return(&mut self, result: EvalResult) -> EvalResult {
-        match result {
-            Ok(..) => {
-                self.frames.pop();
-                result
+        self.report_traced_copies();
+
+        // frames that exited with an error are retained for backtraces, so
+        // the exiting frame is the most recent frame that has not yet exited
+        let Some(i) = self.frames.iter().rposition(|frame| !frame.exited) else {
+            return result;
+        };
+
+        self.frames[i].exited = true;
+
+        let result = match result {
+            // internal errors are signaled as conditions when they first
+            // propagate out of a frame, giving handlers a chance to act
+            Err(Signal::Error(e)) if !self.handlers.is_empty() && !e.is_condition() => {
+                let cond = e.into_condition(self.frames[i].call.clone());
+                match self.signal(cond.clone()) {
+                    Ok(()) => Error::Condition(cond).into(),
+                    Err(signal) => Err(signal),
+                }
             }
-            error => error,
+            result => result,
+        };
+
+        let result = self.run_on_exit(i, result);
+
+        if result.is_ok() {
+            self.frames.truncate(i);
         }
+
+        result
+    }
+
+    /// Evaluate the expressions registered by `on.exit()` for a frame
+    ///
+    /// The result is passed through unless an exit expression raises an
+    /// error or signal of its own.
+    pub fn run_on_exit(&mut self, i: usize, result: EvalResult) -> EvalResult {
+        let exprs = std::mem::take(&mut self.frames[i].on_exit);
+        if exprs.is_empty() {
+            return result;
+        }
+
+        // evaluate in a frame that mirrors the exiting frame, as any frames
+        // that exited with an error may still be on top of the stack
+        let frame = Frame {
+            on_exit: vec![],
+            exited: false,
+            ..self.frames[i].clone()
+        };
+        self.frames.push(frame);
+        let n = self.frames.len();
+
+        for expr in exprs {
+            if let Err(e) = self.eval_and_finalize(expr) {
+                self.frames[n - 1].exited = true;
+                return Err(e);
+            }
+        }
+
+        self.frames.truncate(n - 1);
+        result
+    }
+
+    /// Call a function object with already-evaluated arguments
+    ///
+    /// Arguments are bound in a temporary environment, such that they can be
+    /// passed to both closures and primitives.
+    pub fn call_function(&mut self, fun: Obj, args: List) -> EvalResult {
+        let env = Environment::new(Some(self.env()));
+        env.insert(Symbol::new(".fun"), fun);
+
+        let mut arg_exprs = ExprList::new();
+        for (i, (k, v)) in args.iter_pairs().enumerate() {
+            let sym = Symbol::from(format!(".arg{}", i + 1));
+            env.insert(sym, v);
+            arg_exprs.push_named(k.as_option().map(Symbol::from), Expr::Symbol(sym));
+        }
+
+        let call = Expr::Call(
+            Rc::new(Expr::Symbol(Symbol::new(".fun"))),
+            Rc::new(arg_exprs),
+        );
+        self.add_frame(call.clone(), env);
+        let result = self.eval_and_finalize(call);
+        self.pop_frame_and_return(result)
+    }
+
+    /// Find a method for a generic
+    ///
+    /// Methods are named `generic.class` and are resolved through the
+    /// current environment chain, trying each class in turn. Operators
+    /// additionally fall back to methods of their group generic, such as
+    /// `Ops.class`. Returns the name of the method, the method and the
+    /// classes following the matched class.
+    pub fn find_method(
+        &mut self,
+        generic: &str,
+        classes: &[String],
+    ) -> Option<(String, Obj, Vec<String>)> {
+        let env = self.env();
+        let generics = std::iter::once(generic).chain(group_generic(generic));
+
+        for (i, class) in classes.iter().enumerate() {
+            for generic in generics.clone() {
+                let name = format!("{generic}.{class}");
+                if let Ok((method @ Obj::Function(..), _)) = env.find(Symbol::new(&name)) {
+                    return Some((name, method, classes[i + 1..].to_vec()));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Dispatch a builtin on the class of an object
+    ///
+    /// Only objects with a `class` attribute are dispatched, and a builtin
+    /// that was itself called as the default method of a generic is not
+    /// dispatched again. Returns `None` when no method is found, in which
+    /// case the builtin's own implementation should be used.
+    pub fn dispatch_builtin(
+        &mut self,
+        generic: &str,
+        x: &Obj,
+        args: List,
+    ) -> Result<Option<Obj>, Signal> {
+        if !x.is_object() {
+            return Ok(None);
+        }
+
+        if let Some(Frame { dispatch: Some(d), .. }) = self.frames.last() {
+            if d.generic == generic {
+                return Ok(None);
+            }
+        }
+
+        let mut classes = x.class();
+        classes.push("default".to_string());
+
+        let Some((name, method, classes)) = self.find_method(generic, &classes) else {
+            return Ok(None);
+        };
+
+        let generic = generic.to_string();
+        let dispatch = Dispatch { generic, classes, args };
+        self.call_method(name, method, dispatch).map(Some)
+    }
+
+    /// Print the value of a top-level expression
+    ///
+    /// Objects with a class are printed by their `print` method, if one is
+    /// defined.
+    pub fn print_value(&mut self, x: Obj) -> Result<(), Signal> {
+        if x.is_object() {
+            let arg = Obj::Promise(Some(Box::new(x.clone())), Expr::Null, self.env());
+            let args = List::from(vec![(Character::NA, arg)]);
+            if self.dispatch_builtin("print", &x, args)?.is_some() {
+                return Ok(());
+            }
+        }
+
+        writeln!(self.session.output, "{x}").ok();
+        Ok(())
+    }
+
+    /// Call a method selected by dispatch
+    ///
+    /// The method is called with the arguments of the dispatch, already
+    /// wrapped in promises. The dispatch is recorded on the method's frame
+    /// so that `NextMethod()` can continue it.
+    pub fn call_method(&mut self, name: String, method: Obj, dispatch: Dispatch) -> EvalResult {
+        if let Obj::Function(_, Expr::Primitive(f), ..) = &method {
+            return self.call_builtin_method(f.clone(), dispatch);
+        }
+
+        let Some(env) = method.environment() else {
+            return internal_err!();
+        };
+
+        let args: ExprList = dispatch
+            .args
+            .iter_pairs()
+            .map(|(k, v)| match v {
+                Obj::Promise(_, expr, _) => (k.as_option().map(Symbol::from), expr),
+                _ => (k.as_option().map(Symbol::from), Expr::Missing),
+            })
+            .collect();
+
+        let call = Expr::Call(Rc::new(Expr::Symbol(name.into())), Rc::new(args));
+        self.add_child_frame(call, env);
+
+        let generic = Obj::Vector(vec![dispatch.generic.clone()].into());
+        self.env().insert(Symbol::new(".Generic"), generic);
+
+        let args = dispatch.args.clone();
+        if let Some(frame) = self.frames.last_mut() {
+            frame.dispatch = Some(dispatch);
+        }
+
+        let mut result = method
+            .match_args(args, self)
+            .and_then(|(args, ellipsis)| method.call_matched(args, ellipsis, self));
+
+        while let Err(Signal::Tail(expr, _vis)) = result {
+            result = self.eval(expr);
+        }
+
+        self.pop_frame_and_return(result)
+    }
+
+    /// Call a builtin as the default method of a generic
+    ///
+    /// The builtin is called in a frame that records the dispatch, such that
+    /// the builtin does not dispatch on the same generic again.
+    pub fn call_builtin_method(&mut self, f: Box<dyn Builtin>, dispatch: Dispatch) -> EvalResult {
+        let env = Environment::new(Some(self.env()));
+
+        let mut args = ExprList::new();
+        for (i, (k, v)) in dispatch.args.iter_pairs().enumerate() {
+            let sym = Symbol::from(format!(".arg{}", i + 1));
+            env.insert(sym, v);
+            args.push_named(k.as_option().map(Symbol::from), Expr::Symbol(sym));
+        }
+
+        // builtins evaluate their arguments in their parent frame, so the
+        // arguments are bound in a frame of their own
+        let call = Expr::Call(Rc::new(Expr::Primitive(f.clone())), Rc::new(args.clone()));
+        self.add_frame(call.clone(), env.clone());
+        self.add_frame(call, env);
+
+        if let Some(frame) = self.frames.last_mut() {
+            frame.dispatch = Some(dispatch);
+        }
+
+        let result = f.call(args, self);
+        let result = self.pop_frame_and_return(result);
+        self.pop_frame_and_return(result)
+    }
+
+    /// Assign using a replacement function
+    ///
+    /// Assignments of the form `f(x, ...) <- value` are evaluated as
+    /// `x <- `f<-`(x, ..., value = value)`, where `x` may itself be a call
+    /// such that replacements can be nested.
+    pub fn assign_replacement(&mut self, name: String, args: ExprList, from: Expr) -> EvalResult {
+        let value = self.eval_and_finalize(from)?;
+
+        let mut args = args.into_iter();
+        let Some((_, target)) = args.next() else {
+            return Error::IncorrectContext(format!("{name}<-")).into();
+        };
+
+        let mut fargs: Vec<(Option<String>, Obj)> = vec![(None, self.eval(target.clone())?)];
+        for (k, v) in args {
+            fargs.push((k.map(String::from), self.eval_and_finalize(v)?));
+        }
+        fargs.push((Some("value".to_string()), value.clone()));
+
+        let fun = self.get(Symbol::from(format!("{name}<-")))?;
+        let result = self.call_function(fun, List::from(fargs))?;
+        self.assign_target(target, result)?;
+
+        Ok(value)
+    }
+
+    /// Assign a new value to the target of an assignment
+    ///
+    /// The target may itself be a call, such as `x$y`, in which case the
+    /// value is assigned using that call's assignment.
+    pub fn assign_target(&mut self, target: Expr, value: Obj) -> EvalResult {
+        const TMP: &str = "*tmp*";
+
+        match target {
+            Expr::String(_) | Expr::Symbol(_) => self.assign(target, value),
+            target => {
+                let tmp = Symbol::new(TMP);
+                self.env().insert(tmp, value);
+                let assigned = self.assign_lazy(target, Expr::Symbol(tmp));
+                self.env().remove(tmp);
+                assigned
+            }
+        }
+    }
+
+    /// Signal a condition
+    ///
+    /// Established handlers are searched from most to least recently
+    /// established. Calling handlers are called in place, with only the
+    /// handlers established before them active. If an exiting handler matches,
+    /// the stack is unwound to the frame that established it.
+    pub fn signal(&mut self, cond: Obj) -> Result<(), Signal> {
+        let classes = condition_classes(&cond);
+        let mut i = self.handlers.len();
+
+        while i > 0 {
+            i -= 1;
+            let Some(handler) = self.handlers.get(i).cloned() else {
+                continue;
+            };
+
+            if !classes.contains(&handler.class) {
+                continue;
+            }
+
+            if handler.exiting {
+                return Err(Cond::Unwind(handler.frame, handler.fun, cond).into());
+            }
+
+            let masked = self.handlers.split_off(i);
+            let result = self.call_function(handler.fun, List::from(vec![(None, cond.clone())]));
+            self.handlers.extend(masked);
+            result?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The group generic that an operator belongs to
+fn group_generic(generic: &str) -> Option<&'static str> {
+    match generic {
+        "+" | "-" | "*" | "/" | "^" | "%" | "==" | "!=" | "<" | "<=" | ">=" | ">" | "&" | "|"
+        | "!" => Some("Ops"),
+        _ => None,
+    }
+}
+
+/// Retrieve the class of a condition object
+pub fn condition_classes(cond: &Obj) -> Vec<String> {
+    if cond.is_object() {
+        cond.class()
+    } else {
+        vec![]
     }
 }
 
@@ -862,19 +1553,24 @@ impl Context for CallStack {
         const LIST: &str = "list";
         let err = Err(Signal::Error(Error::IncorrectContext("<-".to_string())));
 
-        if let Expr::Call(what, mut args) = to {
-            match *what {
+        if let Expr::Call(what, args) = to {
+            match &*what {
                 // special case for list() calls
-                Expr::String(s) | Expr::Symbol(s) if s == LIST => {
+                Expr::Symbol(s) if *s == LIST => {
+                    let result = self.eval_and_finalize(from)?;
+                    return self.assign(Expr::List(args), result);
+                }
+                Expr::String(s) if s == LIST => {
                     let result = self.eval_and_finalize(from)?;
                     return self.assign(Expr::List(args), result);
                 }
-                Expr::String(s) | Expr::Symbol(s) => {
-                    args.insert(0, from);
-                    let s = format!("{}<-", s);
-                    return self.eval(Expr::Call(Box::new(Expr::Symbol(s)), args));
+                Expr::Symbol(s) => {
+                    return self.assign_replacement(s.to_string(), Rc::unwrap_or_clone(args), from);
+                }
+                Expr::String(s) => {
+                    return self.assign_replacement(s.clone(), Rc::unwrap_or_clone(args), from);
                 }
-                Expr::Primitive(p) => return p.call_assign(from, args, self),
+                Expr::Primitive(p) => return p.call_assign(from, Rc::unwrap_or_clone(args), self),
                 _ => return err,
             }
         }
@@ -887,23 +1583,42 @@ impl Context for CallStack {
         let err = Err(Signal::Error(Error::IncorrectContext("<-".to_string())));
 
         match (to, from) {
-            (Expr::String(s) | Expr::Symbol(s), from) => {
+            (to @ (Expr::String(_) | Expr::Symbol(_)), mut from) => {
+                let s = match to {
+                    Expr::Symbol(s) => s,
+                    Expr::String(s) => Symbol::from(s),
+                    _ => unreachable!(),
+                };
+
+                // lazy results are computed once they are bound to a name
+                if let Obj::Vector(v) = &mut from {
+                    if v.is_lazy() {
+                        v.materialize_computed()?;
+                    }
+                }
+
                 self.env().insert(s, from.clone());
                 Ok(from)
             }
             (Expr::List(l), Obj::List(args)) => {
                 let mut i = 1;
-                for item in l.into_iter() {
+                for item in Rc::unwrap_or_clone(l) {
                     match item {
-                        (None, Expr::String(s) | Expr::Symbol(s)) => {
+                        (None, s @ (Expr::String(_) | Expr::Symbol(_))) => {
                             let index = Obj::Vector(Vector::from(vec![i]));
                             let value = args.try_get_inner(index.try_into()?)?;
-                            self.assign(Expr::Symbol(s), value)?;
+                            self.assign(s, value)?;
                             i += 1;
                         }
                         // TODO(feature): allow arbitrary right-side expressions
                         // evaluated with list as additional data-frame
-                        (Some(n), Expr::String(s) | Expr::Symbol(s)) => {
+                        (Some(n), Expr::String(s)) => {
+                            let value =
+                                args.try_get_inner(Obj::Vector(Vector::from(vec![s])).try_into()?)?;
+                            self.assign(Expr::Symbol(n), value)?;
+                        }
+                        (Some(n), Expr::Symbol(s)) => {
+                            let s = String::from(s);
                             let value =
                                 args.try_get_inner(Obj::Vector(Vector::from(vec![s])).try_into()?)?;
                             self.assign(Expr::Symbol(n), value)?;
@@ -932,13 +1647,15 @@ impl Context for CallStack {
     fn eval(&mut self, expr: Expr) -> EvalResult {
         use Expr::*;
         match expr {
-            List(x) => self.eval_list_lazy(x),
+            List(x) => self.eval_list_lazy(Rc::unwrap_or_clone(x)),
             Symbol(s) => self.get(s),
             Call(..) => self.eval_call(expr),
             Function(formals, body) => Ok(Obj::Function(
-                assert_formals(&self.session, formals)?,
-                *body,
+                assert_formals(&self.session, Rc::unwrap_or_clone(formals))?,
+                Rc::unwrap_or_clone(body),
                 self.env().clone(),
+                None,
+                Compiled::default(),
             )),
             _ => self.last_frame().eval(expr),
         }
@@ -965,21 +1682,30 @@ impl Context for CallStack {
         }
     }
 
-    fn get(&mut self, name: String) -> EvalResult {
-        let (obj, _) = self.find(name.clone())?;
-        Ok(obj.clone())
+    fn get(&mut self, name: Symbol) -> EvalResult {
+        match self.find(name)? {
+            // promises retain their value once forced
+            (Obj::Promise(Some(obj), ..), _) => Ok(obj.as_ref().clone()),
+            (obj, _) => Ok(obj.clone()),
+        }
     }
 
-    fn get_mut(&mut self, name: String) -> EvalResult {
-        let (obj, obj_source_env) = self.find(name.clone())?;
+    fn get_mut(&mut self, name: Symbol) -> EvalResult {
+        let (obj, obj_source_env) = self.find(name)?;
 
-        let objc = match (self.env() == obj_source_env, obj) {
+        let mut objc = match (self.env() == obj_source_env, obj) {
             // when accessed mutably, promises are always masked by materialized value
             (_, Obj::Promise(Some(x), ..)) => *x.clone(),
+            (true, Obj::Vector(v)) if v.is_computed() => Obj::Vector(v),
             (true, obj) => return Ok(obj),
             (false, obj) => obj.clone(),
         };
 
+        // sequences and lazy vectors are materialized before they can be modified
+        if let Obj::Vector(v) = &mut objc {
+            v.materialize_computed()?;
+        }
+
         self.env().insert(name, objc.view_mut());
         Ok(objc)
     }
@@ -997,7 +1723,16 @@ fn eval_call(callstack: &mut CallStack, expr: Expr, mutable: bool) -> EvalResult
         return internal_err!();
     };
 
-    match *what {
+    // the argument expressions are shared with the call, so only the list is copied
+    let args = Rc::unwrap_or_clone(args);
+
+    // functions called by a string name are looked up like symbols
+    let what = match &*what {
+        Expr::String(name) => Expr::Symbol(name.into()),
+        what => what.clone(),
+    };
+
+    match what {
         Expr::Primitive(f) if f.is_transparent() => {
             if mutable {
                 f.call_mut(args, callstack)
@@ -1014,10 +1749,12 @@ fn eval_call(callstack: &mut CallStack, expr: Expr, mutable: bool) -> EvalResult
             };
             callstack.pop_frame_and_return(result)
         }
-        Expr::String(name) | Expr::Symbol(name) if BUILTIN.contains_key(name.as_str()) => {
+        Expr::Symbol(name)
+            if BUILTIN.contains_key(name.as_str()) && !callstack.env().shadows_builtin(name) =>
+        {
             let f = BUILTIN
                 .get(name.as_str())
-                .ok_or(Error::VariableNotFound(name))?;
+                .ok_or(Error::VariableNotFound(name.into()))?;
             callstack.add_frame(expr, callstack.last_frame().env().clone());
             let result = if mutable {
                 f.call_mut(args, callstack)
@@ -1026,7 +1763,7 @@ fn eval_call(callstack: &mut CallStack, expr: Expr, mutable: bool) -> EvalResult
             };
             callstack.pop_frame_and_return(result)
         }
-        Expr::String(name) | Expr::Symbol(name) => {
+        Expr::Symbol(name) => {
             if mutable {
                 // currently, things like names(x) = "a" is anyway not supported
                 return internal_err!();
@@ -1034,7 +1771,7 @@ fn eval_call(callstack: &mut CallStack, expr: Expr, mutable: bool) -> EvalResult
             use Signal::*;
 
             // look up our call target
-            let obj = callstack.env().get(name.clone())?;
+            let obj = callstack.env().get(name)?;
 
             // ensure our call target expression has an encapsulating environment
             let Some(env) = obj.environment() else {
@@ -1058,13 +1795,16 @@ fn eval_call(callstack: &mut CallStack, expr: Expr, mutable: bool) -> EvalResult
 
                     // tail is recursive call if it calls out to same object
                     // that was called to enter current frame
-                    let what_obj = callstack.eval(*what)?;
+                    let what_obj = callstack.eval((*what).clone())?;
                     if what_obj == callstack.last_frame().to {
                         // eagerly evaluate and match argument expressions in tail frame
+                        let args = Rc::unwrap_or_clone(args);
                         let args: List = callstack.eval_list_eager(args)?.try_into()?;
                         let (args, ellipsis) = what_obj.match_args(args, callstack)?;
 
-                        // pop tail frame and add a new local frame
+                        // exit and pop tail frame and add a new local frame
+                        let frame = callstack.frames.len() - 1;
+                        callstack.run_on_exit(frame, Ok(Obj::Null))?;
                         callstack.frames.pop();
                         callstack.add_child_frame(tail, env.clone());
 
@@ -1084,9 +1824,9 @@ fn eval_call(callstack: &mut CallStack, expr: Expr, mutable: bool) -> EvalResult
 
             callstack.pop_frame_and_return(result)
         }
-        _ => {
+        what => {
             callstack.add_frame(expr, callstack.last_frame().env().clone());
-            let result = (callstack.eval(*what)?).call_mut(args, callstack);
+            let result = (callstack.eval(what)?).call_mut(args, callstack);
             callstack.pop_frame_and_return(result)
         }
     }
@@ -1119,11 +1859,19 @@ impl Context for Obj {
             Expr::Inf => Ok(Obj::Vector(Vector::from(vec![OptionNA::Some(
                 f64::INFINITY,
             )]))),
+            Expr::NaN => Ok(Obj::Vector(Vector::from(vec![OptionNA::Some(f64::NAN)]))),
             Expr::Number(x) => Ok(Obj::Vector(Vector::from(vec![x]))),
             Expr::Integer(x) => Ok(Obj::Vector(Vector::from(vec![x]))),
+            Expr::Imaginary(x) => Ok(Obj::Vector(Vector::from(vec![Complex64::new(0.0, x)]))),
             Expr::Bool(x) => Ok(Obj::Vector(Vector::from(vec![OptionNA::Some(x)]))),
-            Expr::String(x) => Ok(Obj::Vector(Vector::from(vec![OptionNA::Some(x)]))),
-            Expr::Function(formals, body) => Ok(Obj::Function(formals, *body, self.env().clone())),
+            Expr::String(x) => Ok(Obj::Vector(Vector::from(vec![Character::Some(x.into())]))),
+            Expr::Function(formals, body) => Ok(Obj::Function(
+                Rc::unwrap_or_clone(formals),
+                Rc::unwrap_or_clone(body),
+                self.env().clone(),
+                None,
+                Compiled::default(),
+            )),
             Expr::Symbol(name) => self.get(name),
             Expr::Break => Err(Signal::Condition(Cond::Break)),
             Expr::Continue => Err(Signal::Condition(Cond::Continue)),
@@ -1131,6 +1879,8 @@ impl Context for Obj {
                 p.formals(),
                 Expr::Primitive(p),
                 self.environment().unwrap(),
+                None,
+                Compiled::default(),
             )),
             Expr::More => Ok(Obj::Null),
 
@@ -1141,9 +1891,12 @@ impl Context for Obj {
         }
     }
 
-    fn get(&mut self, name: String) -> EvalResult {
+    fn get(&mut self, name: Symbol) -> EvalResult {
         match self {
-            Obj::List(l) => Ok(l.try_get_inner(Obj::Vector(Vector::from(vec![name])).try_into()?)?),
+            Obj::List(l) => {
+                let name = String::from(name);
+                Ok(l.try_get_inner(Obj::Vector(Vector::from(vec![name])).try_into()?)?)
+            }
             Obj::Environment(e) => e.get(name),
             _ => unimplemented!(),
         }
@@ -1171,19 +1924,29 @@ impl Context for Rc<Environment> {
             Expr::Inf => Ok(Obj::Vector(Vector::from(vec![OptionNA::Some(
                 f64::INFINITY,
             )]))),
+            Expr::NaN => Ok(Obj::Vector(Vector::from(vec![OptionNA::Some(f64::NAN)]))),
             Expr::Number(x) => Ok(Obj::Vector(Vector::from(vec![x]))),
             Expr::Integer(x) => Ok(Obj::Vector(Vector::from(vec![x]))),
+            Expr::Imaginary(x) => Ok(Obj::Vector(Vector::from(vec![Complex64::new(0.0, x)]))),
             Expr::Bool(x) => Ok(Obj::Vector(Vector::from(vec![OptionNA::Some(x)]))),
-            Expr::String(x) => Ok(Obj::Vector(Vector::from(vec![OptionNA::Some(x)]))),
+            Expr::String(x) => Ok(Obj::Vector(Vector::from(vec![Character::Some(x.into())]))),
             Expr::Function(formals, body) => Ok(Obj::Function(
-                assert_formals(&Session::default(), formals)?,
-                *body,
+                assert_formals(&Session::default(), Rc::unwrap_or_clone(formals))?,
+                Rc::unwrap_or_clone(body),
                 self.env().clone(),
+                None,
+                Compiled::default(),
             )),
             Expr::Symbol(name) => self.get(name),
             Expr::Break => Err(Signal::Condition(Cond::Break)),
             Expr::Continue => Err(Signal::Condition(Cond::Continue)),
-            Expr::Primitive(p) => Ok(Obj::Function(p.formals(), Expr::Primitive(p), self.clone())),
+            Expr::Primitive(p) => Ok(Obj::Function(
+                p.formals(),
+                Expr::Primitive(p),
+                self.clone(),
+                None,
+                Compiled::default(),
+            )),
             Expr::More => Ok(Obj::Null),
 
             // bubbles up to where a symbol can be attached for context
@@ -1195,11 +1958,11 @@ impl Context for Rc<Environment> {
         }
     }
 
-    fn get(&mut self, name: String) -> EvalResult {
+    fn get(&mut self, name: Symbol) -> EvalResult {
         Environment::get(self, name)
     }
 
-    fn get_mut(&mut self, name: String) -> EvalResult {
+    fn get_mut(&mut self, name: Symbol) -> EvalResult {
         Environment::get_mut(self, name)
     }
 }
@@ -1259,7 +2022,7 @@ mod test {
 
     #[test]
     fn fn_rest_args() {
-        let formals = ExprList::from(vec![(None, Expr::Ellipsis(Some("a".to_string())))]);
+        let formals = ExprList::from(vec![(None, Expr::Ellipsis(Some(Symbol::new("a"))))]);
         assert_eq!(
             assert_formals(&Session::default(), formals),
             Result::Err(Signal::Error(Error::FeatureDisabledRestArgs))
@@ -1393,6 +2156,14 @@ mod test {
         "}}
     }
 
+    #[test]
+    fn forced_promises_can_be_reused() {
+        r_expect! {{"
+            f <- function(x) { x; x$a == 1 }
+            f(list(a = 1))
+        "}}
+    }
+
     #[test]
     fn dont_mutate_value_from_parent() {
         r_expect! {{"
@@ -1425,6 +2196,17 @@ mod test {
         "}}
     }
 
+    #[test]
+    fn lazy_results_are_computed_on_assignment() {
+        r_expect! {{"
+            x <- c(1, 2, 3)
+            y <- x * 2
+            x[1] <- 10
+            y[2] <- 0
+            y[1] == 2 && y[2] == 0 && y[3] == 6 && sum((x - 5) ^ 2 / 2) == 19
+        "}}
+    }
+
     #[test]
     fn nested_promises_can_be_mutated() {
         r_expect! {{"
@@ -1433,4 +2215,13 @@ mod test {
             add_two(1) == 3
         "}}
     }
+
+    #[test]
+    fn functions_shadow_builtins() {
+        r_expect! {{"
+            sum <- function(...) 0
+            c <- 1
+            sum(1, 2) == 0 && length(c(1, 2)) == 2
+        "}}
+    }
 }
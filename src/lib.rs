@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate pest_derive;
 
+pub mod bytecode;
 pub mod callable;
 pub mod cli;
 pub mod context;
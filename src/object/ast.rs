@@ -1,26 +1,33 @@
 use core::fmt;
+use std::rc::Rc;
 use std::{iter::Zip, slice::IterMut, vec::IntoIter};
 
 use crate::callable::core::Builtin;
+use crate::parser::escape;
+
+use super::Symbol;
 
 #[derive(Debug, Clone)]
 pub enum Expr {
     Null,
     NA,
     Inf,
+    NaN,
     More,
     Continue,
     Break,
-    Ellipsis(Option<String>),
+    Ellipsis(Option<Symbol>),
     Missing,
     Bool(bool),
     Number(f64),
     Integer(i32),
+    Imaginary(f64),
     String(String),
-    Symbol(String),
-    List(ExprList),
-    Function(ExprList, Box<Expr>),
-    Call(Box<Expr>, ExprList),
+    Symbol(Symbol),
+    // children are shared, such that cloning an expression is cheap
+    List(Rc<ExprList>),
+    Function(Rc<ExprList>, Rc<Expr>),
+    Call(Rc<Expr>, Rc<ExprList>),
     Primitive(Box<dyn Builtin>),
 }
 
@@ -31,6 +38,7 @@ impl PartialEq for Expr {
             (Null, Null) => true,
             (NA, NA) => true,
             (Inf, Inf) => true,
+            (NaN, NaN) => true,
             (Continue, Continue) => true,
             (Break, Break) => true,
             (Ellipsis(l), Ellipsis(r)) => l == r,
@@ -38,6 +46,7 @@ impl PartialEq for Expr {
             (Bool(l), Bool(r)) => l == r,
             (Number(l), Number(r)) => l == r,
             (Integer(l), Integer(r)) => l == r,
+            (Imaginary(l), Imaginary(r)) => l == r,
             (String(l), String(r)) => l == r,
             (Symbol(l), Symbol(r)) => l == r,
             (List(l), List(r)) => l == r,
@@ -57,12 +66,21 @@ impl Expr {
         Self::Primitive(Box::new(x))
     }
 
+    /// The name given by a symbol or a string, such as the name in `x$name`
+    pub fn as_name(&self) -> Option<&str> {
+        match self {
+            Expr::Symbol(name) => Some(name),
+            Expr::String(name) => Some(name),
+            _ => None,
+        }
+    }
+
     pub fn new_primitive_call<T>(x: T, args: ExprList) -> Self
     where
         T: Builtin + 'static,
     {
         let p = Self::as_primitive(x);
-        Self::Call(Box::new(p), args)
+        Self::Call(Rc::new(p), Rc::new(args))
     }
 }
 
@@ -77,14 +95,25 @@ impl fmt::Display for Expr {
             Expr::Bool(false) => write!(f, "FALSE"),
             Expr::Number(x) => write!(f, "{}", x),
             Expr::Integer(x) => write!(f, "{}L", x),
-            Expr::String(x) => write!(f, "\"{}\"", x),
+            Expr::Imaginary(x) => write!(f, "{}i", x),
+            Expr::String(x) => write!(f, "\"{}\"", escape(x)),
             Expr::Symbol(x) => write!(f, "{}", x),
             Expr::List(x) => write!(f, "{}", x),
             Expr::Ellipsis(None) => write!(f, "..."),
             Expr::Ellipsis(Some(s)) => write!(f, "..{s}"),
             Expr::Call(what, args) => match &**what {
                 Expr::Primitive(p) => write!(f, "{}", p.rfmt_call(args)),
-                Expr::String(s) | Expr::Symbol(s) => write!(f, "{}({})", s, args),
+                Expr::String(s) => write!(f, "{}({})", s, args),
+                // user-defined operators are written infix, as they are parsed
+                Expr::Symbol(s)
+                    if s.is_special()
+                        && args.len() == 2
+                        && args.keys[0].is_none()
+                        && args.keys[1].is_none() =>
+                {
+                    write!(f, "{} {} {}", args.values[0], s, args.values[1])
+                }
+                Expr::Symbol(s) => write!(f, "{}({})", s, args),
                 rexpr => write!(f, "{}({})", rexpr, args),
             },
             Expr::Function(head, body) => write!(f, "function({}) {}", head, body),
@@ -96,12 +125,12 @@ impl fmt::Display for Expr {
 
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct ExprList {
-    pub keys: Vec<Option<String>>, // TODO: use Vec<RExprListKey>
+    pub keys: Vec<Option<Symbol>>, // TODO: use Vec<RExprListKey>
     pub values: Vec<Expr>,
 }
 
 impl ExprList {
-    pub fn push_named(&mut self, key: Option<String>, value: Expr) {
+    pub fn push_named(&mut self, key: Option<Symbol>, value: Expr) {
         self.keys.push(key);
         self.values.push(value);
     }
@@ -125,8 +154,8 @@ impl fmt::Display for ExprList {
 }
 
 impl IntoIterator for ExprList {
-    type Item = (Option<String>, Expr);
-    type IntoIter = <Zip<IntoIter<Option<String>>, IntoIter<Expr>> as IntoIterator>::IntoIter;
+    type Item = (Option<Symbol>, Expr);
+    type IntoIter = <Zip<IntoIter<Option<Symbol>>, IntoIter<Expr>> as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
         self.keys.into_iter().zip(self.values)
@@ -134,18 +163,18 @@ impl IntoIterator for ExprList {
 }
 
 impl<'a> IntoIterator for &'a mut ExprList {
-    type Item = (&'a mut Option<String>, &'a mut Expr);
-    type IntoIter = <Zip<IterMut<'a, Option<String>>, IterMut<'a, Expr>> as IntoIterator>::IntoIter;
+    type Item = (&'a mut Option<Symbol>, &'a mut Expr);
+    type IntoIter = <Zip<IterMut<'a, Option<Symbol>>, IterMut<'a, Expr>> as IntoIterator>::IntoIter;
 
     fn into_iter(self) -> Self::IntoIter {
         self.keys.iter_mut().zip(self.values.iter_mut())
     }
 }
 
-impl FromIterator<(Option<String>, Expr)> for ExprList {
+impl FromIterator<(Option<Symbol>, Expr)> for ExprList {
     fn from_iter<T>(iter: T) -> Self
     where
-        T: IntoIterator<Item = (Option<String>, Expr)>,
+        T: IntoIterator<Item = (Option<Symbol>, Expr)>,
     {
         let (keys, values) = iter.into_iter().unzip();
         ExprList { keys, values }
@@ -167,14 +196,18 @@ impl ExprList {
         ExprList { ..Default::default() }
     }
 
-    pub fn get_named(&self, key: &String) -> Option<Expr> {
+    pub fn get_named(&self, key: &str) -> Option<Expr> {
         // self.keys.iter()
         //     .enumerate().rev()
         //     .find(move |(_, i)| *i == &Some(key.clone()))
         //     .and_then(|(idx, _)| self.values.get(self.keys.len().saturating_sub(idx)))
         //     .and_then(|expr| Some(expr.clone()))
 
-        let first_name_index = self.keys.iter().rev().position(|i| i.as_ref() == Some(key));
+        let first_name_index = self
+            .keys
+            .iter()
+            .rev()
+            .position(|i| matches!(i, Some(k) if k == key));
         match first_name_index {
             Some(index) => self.values.get(index).cloned(),
             _ => None,
@@ -189,7 +222,7 @@ impl ExprList {
         }
     }
 
-    pub fn pop(&mut self) -> Option<(Option<String>, Expr)> {
+    pub fn pop(&mut self) -> Option<(Option<Symbol>, Expr)> {
         if let Some(k) = self.keys.pop() {
             if let Some(v) = self.values.pop() {
                 return Some((k, v));
@@ -199,7 +232,7 @@ impl ExprList {
         None
     }
 
-    pub fn push(&mut self, pair: (Option<String>, Expr)) {
+    pub fn push(&mut self, pair: (Option<Symbol>, Expr)) {
         let (key, value) = pair;
         self.keys.push(key);
         self.values.push(value);
@@ -228,8 +261,11 @@ impl ExprList {
         }
     }
 
-    pub fn remove_named(&mut self, key: &str) -> Option<(Option<String>, Expr)> {
-        let first_named_index = self.keys.iter().position(|i| i == &Some(key.to_string()));
+    pub fn remove_named(&mut self, key: &str) -> Option<(Option<Symbol>, Expr)> {
+        let first_named_index = self
+            .keys
+            .iter()
+            .position(|i| matches!(i, Some(k) if k == key));
         if let Some(index) = first_named_index {
             Some((self.keys.remove(index), self.values.remove(index)))
         } else {
@@ -237,7 +273,7 @@ impl ExprList {
         }
     }
 
-    pub fn remove(&mut self, index: usize) -> Option<(Option<String>, Expr)> {
+    pub fn remove(&mut self, index: usize) -> Option<(Option<Symbol>, Expr)> {
         if index < self.keys.len() {
             Some((self.keys.remove(index), self.values.remove(index)))
         } else {
@@ -245,12 +281,12 @@ impl ExprList {
         }
     }
 
-    pub fn insert_named(&mut self, key: String, value: Expr) -> usize {
-        if let Some(index) = self.keys.iter().position(|i| i == &Some(key.clone())) {
+    pub fn insert_named(&mut self, key: Symbol, value: Expr) -> usize {
+        if let Some(index) = self.keys.iter().position(|i| i == &Some(key)) {
             self.values[index] = value;
             index
         } else {
-            self.keys.push(Some(key.to_string()));
+            self.keys.push(Some(key));
             self.values.push(value);
             self.values.len()
         }
@@ -270,7 +306,7 @@ impl ExprList {
         }
     }
 
-    pub fn binary_args(self) -> ((Option<String>, Expr), (Option<String>, Expr)) {
+    pub fn binary_args(self) -> ((Option<Symbol>, Expr), (Option<Symbol>, Expr)) {
         let mut argstream = self.into_iter();
         let Some(lhs) = argstream.next() else {
             unimplemented!()
@@ -309,7 +345,7 @@ impl ExprList {
     pub fn as_formals(self) -> ExprList {
         self.into_iter()
             .map(|(k, v)| match (k, v) {
-                (None, Expr::Symbol(param)) => (Some(param.clone()), Expr::Missing),
+                (None, Expr::Symbol(param)) => (Some(param), Expr::Missing),
                 other => other,
             })
             .collect()
@@ -337,8 +373,8 @@ impl From<Expr> for ExprList {
     }
 }
 
-impl From<Vec<(Option<String>, Expr)>> for ExprList {
-    fn from(values: Vec<(Option<String>, Expr)>) -> Self {
+impl From<Vec<(Option<Symbol>, Expr)>> for ExprList {
+    fn from(values: Vec<(Option<Symbol>, Expr)>) -> Self {
         ExprList::from_iter(values)
     }
 }
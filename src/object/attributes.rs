@@ -0,0 +1,91 @@
+use std::rc::Rc;
+
+use super::Obj;
+
+/// Object Attributes
+///
+/// An ordered collection of named objects attached to an object as metadata,
+/// such as its `class`, `dim` or `levels`. Attributes are shared between
+/// copies of an object and only cloned when modified.
+///
+/// Names of vectors and lists are stored separately as part of their
+/// [`crate::object::Naming`], but are exposed as a `names` attribute in the
+/// language.
+///
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Attributes(Rc<Vec<(String, Obj)>>);
+
+impl Attributes {
+    pub fn new() -> Self {
+        Attributes::default()
+    }
+
+    /// Get the value of an attribute by name
+    pub fn get(&self, name: &str) -> Option<&Obj> {
+        self.0.iter().find(|(k, _)| k == name).map(|(_, v)| v)
+    }
+
+    /// Set the value of an attribute
+    ///
+    /// Existing attributes keep their position. Setting an attribute to
+    /// `NULL` removes it.
+    pub fn set(&mut self, name: &str, value: Obj) {
+        let attrs = Rc::make_mut(&mut self.0);
+        let pos = attrs.iter().position(|(k, _)| k == name);
+
+        match (pos, value) {
+            (Some(i), Obj::Null) => {
+                attrs.remove(i);
+            }
+            (None, Obj::Null) => (),
+            (Some(i), value) => attrs[i].1 = value,
+            (None, value) => attrs.push((name.to_string(), value)),
+        }
+    }
+
+    /// Merge attributes, where existing attributes take precedence
+    pub fn merge(&mut self, other: &Attributes) {
+        for (k, v) in other.iter() {
+            if self.get(k).is_none() {
+                self.set(k, v.clone());
+            }
+        }
+    }
+
+    /// Whether the attributes are shared with other objects
+    pub fn is_shared(&self) -> bool {
+        Rc::strong_count(&self.0) > 1
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(String, Obj)> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Convert into an optional value, where empty attributes become `None`
+    pub fn into_option(self) -> Option<Attributes> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self)
+        }
+    }
+}
+
+impl FromIterator<(String, Obj)> for Attributes {
+    fn from_iter<I: IntoIterator<Item = (String, Obj)>>(iter: I) -> Self {
+        let mut attrs = Attributes::new();
+        for (k, v) in iter {
+            attrs.set(&k, v);
+        }
+        attrs
+    }
+}
@@ -1,5 +1,6 @@
 use std::rc::Rc;
 
+use crate::bytecode::Compiled;
 use crate::error::Error;
 use crate::internal_err;
 use crate::lang::Signal;
@@ -17,7 +18,13 @@ pub enum Obj {
     // Metaprogramming structures
     Expr(Expr),
     Promise(Option<Box<Obj>>, Expr, Rc<Environment>),
-    Function(ExprList, Expr, Rc<Environment>),
+    Function(
+        ExprList,
+        Expr,
+        Rc<Environment>,
+        Option<Attributes>,
+        Compiled,
+    ),
     Environment(Rc<Environment>),
 }
 
@@ -25,20 +32,26 @@ impl PartialEq for Obj {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Obj::Null, Obj::Null) => true,
-            (Obj::List(l), Obj::List(r)) => l
-                .pairs_ref()
-                .iter()
-                .zip(r.pairs_ref().iter())
-                .all(|((lk, lv), (rk, rv))| lk == rk && lv == rv),
+            (Obj::List(l), Obj::List(r)) => {
+                l.attributes() == r.attributes()
+                    && l.pairs_ref()
+                        .iter()
+                        .zip(r.pairs_ref().iter())
+                        .all(|((lk, lv), (rk, rv))| lk == rk && lv == rv)
+            }
             (Obj::Expr(l), Obj::Expr(r)) => l == r,
             (Obj::Promise(None, lc, lenv), Obj::Promise(None, rc, renv)) => {
                 lc == rc && lenv == renv
             }
             (Obj::Promise(Some(a), ..), Obj::Promise(Some(b), ..)) => a == b,
             (Obj::Promise(..), Obj::Promise(..)) => false,
-            (Obj::Function(largs, lbody, lenv), Obj::Function(rargs, rbody, renv)) => {
+            (
+                Obj::Function(largs, lbody, lenv, lattr, _),
+                Obj::Function(rargs, rbody, renv, rattr, _),
+            ) => {
                 largs == rargs
                     && lbody == rbody
+                    && lattr == rattr
                     && Obj::Environment(lenv.clone()) == Obj::Environment(renv.clone())
             }
             (Obj::Environment(l), Obj::Environment(r)) => {
@@ -55,6 +68,8 @@ impl PartialEq for Obj {
                 (Vector::Double(l), Vector::Double(r)) => l == r,
                 (Vector::Integer(l), Vector::Integer(r)) => l == r,
                 (Vector::Logical(l), Vector::Logical(r)) => l == r,
+                (Vector::Complex(l), Vector::Complex(r)) => l == r,
+                (Vector::Raw(l), Vector::Raw(r)) => l == r,
                 (Vector::Character(l), Vector::Character(r)) => l == r,
                 _ => false,
             },
@@ -72,8 +87,8 @@ impl TryInto<i32> for Obj {
             return internal_err!();
         };
 
-        match v.inner().clone().borrow()[..] {
-            [OptionNA::Some(i), ..] => Ok(i),
+        match v.iter_values().next() {
+            Some(OptionNA::Some(i)) => Ok(i),
             _ => Err(CannotBeCoercedToInteger.into()),
         }
     }
@@ -97,8 +112,8 @@ impl TryInto<f64> for Obj {
             return internal_err!();
         };
 
-        match v.inner().clone().borrow()[..] {
-            [OptionNA::Some(i), ..] => Ok(i),
+        match v.iter_values().next() {
+            Some(OptionNA::Some(i)) => Ok(i),
             _ => Err(CannotBeCoercedToDouble.into()),
         }
     }
@@ -116,7 +131,7 @@ impl TryInto<Vec<f64>> for Obj {
             .borrow()
             .iter()
             .map(|vi| match vi {
-                OptionNA::Some(i) => *i,
+                OptionNA::Some(i) => i,
                 OptionNA::NA => f64::NAN,
             })
             .collect())
@@ -1,6 +1,30 @@
+use hashbrown::HashMap;
+use std::any::Any;
 use std::cell::{Ref, RefCell};
 use std::iter::Iterator;
-use std::rc::Rc;
+use std::rc::{Rc, Weak};
+
+thread_local! {
+    static TRACED: RefCell<Traced> = RefCell::new(Traced::default());
+}
+
+/// Traced Data
+///
+/// Data that is traced is held weakly, such that its address isn't reused
+/// while it is traced. Copies of traced data are traced themselves and are
+/// recorded until they are reported.
+///
+#[derive(Default)]
+struct Traced {
+    data: HashMap<usize, Weak<dyn Any>>,
+    copies: Vec<(usize, usize)>,
+}
+
+/// Take the copies of traced data made since they were last taken, as pairs
+/// of the addresses of the original data and its copy
+pub fn take_traced_copies() -> Vec<(usize, usize)> {
+    TRACED.with_borrow_mut(|traced| std::mem::take(&mut traced.copies))
+}
 
 /// View an object mutably.
 /// This trait drives the assignment into vectors and lists, primarily
@@ -63,6 +87,13 @@ impl<T: Clone> CowObj<T> {
         self.borrow().clone()
     }
 
+    /// Borrow the internal data immutably.
+    pub fn borrow(&self) -> Ref<'_, Rc<T>> {
+        self.0.borrow()
+    }
+}
+
+impl<T: Clone + 'static> CowObj<T> {
     /// Get mutable access to the internal vector.
     /// In case more than one reference to the internal data exists,
     /// the vector is cloned.
@@ -72,16 +103,72 @@ impl<T: Clone> CowObj<T> {
     {
         let CowObj(x) = self;
         let x1 = &mut *x.borrow_mut();
+
+        // only traced data is referenced weakly
+        if Rc::weak_count(x1) > 0 {
+            return with_traced_mut(x1, f);
+        }
+
         let vals = Rc::make_mut(x1);
         f(vals)
     }
 
-    /// Borrow the internal data immutably.
-    pub fn borrow(&self) -> Ref<'_, Rc<T>> {
-        self.0.borrow()
+    /// Trace copies of the data, returning its address
+    pub fn trace(&self) -> usize {
+        let data = self.inner_rc();
+        TRACED.with_borrow_mut(|traced| {
+            traced.data.retain(|_, weak| weak.strong_count() > 0);
+            traced.trace(&data)
+        })
+    }
+
+    /// Stop tracing copies of the data
+    pub fn untrace(&self) {
+        let data = self.inner_rc();
+        TRACED.with_borrow_mut(|traced| traced.data.remove(&address(&data)));
+    }
+}
+
+impl Traced {
+    fn trace<T: 'static>(&mut self, data: &Rc<T>) -> usize {
+        let weak: Weak<T> = Rc::downgrade(data);
+        self.data.insert(address(data), weak as Weak<dyn Any>);
+        address(data)
     }
 }
 
+fn address<T>(data: &Rc<T>) -> usize {
+    Rc::as_ptr(data) as *const () as usize
+}
+
+/// Modify traced data, recording it if it is copied
+///
+/// Data that isn't shared is untraced while it is modified, as the weak
+/// reference that traces it would otherwise cause it to be moved.
+fn with_traced_mut<T, F, R>(data: &mut Rc<T>, f: F) -> R
+where
+    T: Clone + 'static,
+    F: FnOnce(&mut T) -> R,
+{
+    let from = address(data);
+    let copied = Rc::strong_count(data) > 1;
+
+    if !copied {
+        TRACED.with_borrow_mut(|traced| traced.data.remove(&from));
+    }
+
+    let result = f(Rc::make_mut(data));
+
+    TRACED.with_borrow_mut(|traced| {
+        let to = traced.trace(data);
+        if copied {
+            traced.copies.push((from, to));
+        }
+    });
+
+    result
+}
+
 impl<T: Clone> ViewMut for CowObj<T> {
     /// Create a mutable view on the data.
     fn view_mut(&self) -> Self {
@@ -105,8 +192,9 @@ impl<T: Clone> CowObj<Vec<T>> {
 
 #[cfg(test)]
 mod tests {
-    use super::CowObj;
+    use super::{take_traced_copies, CowObj};
     use crate::object::ViewMut;
+    use std::rc::Rc;
 
     #[test]
     fn with_inner_mut() {
@@ -116,4 +204,21 @@ mod tests {
         x.with_inner_mut(|v| v.push(1));
         assert_eq!(x.0.borrow().first().cloned().unwrap(), 1);
     }
+
+    #[test]
+    fn copies_are_traced() {
+        take_traced_copies();
+        let x = CowObj::from(vec![1]);
+        let from = x.trace();
+        let y = x.clone();
+
+        y.with_inner_mut(|v| v.push(2));
+        let to = Rc::as_ptr(&y.inner_rc()) as usize;
+        assert_eq!(take_traced_copies(), vec![(from, to)]);
+
+        // copies are traced as well, and are modified in place
+        y.with_inner_mut(|v| v.push(3));
+        assert_eq!(take_traced_copies(), vec![]);
+        assert_eq!(y.trace(), to);
+    }
 }
@@ -0,0 +1,348 @@
+use std::fmt;
+
+use super::dims::{dim_positions, integers};
+use crate::error::Error;
+use crate::internal_err;
+use crate::lang::{EvalResult, Signal};
+use crate::object::types::*;
+use crate::object::*;
+
+/// The maximum number of rows displayed when printing a data frame
+const MAX_PRINT_ROWS: usize = 20;
+
+/// The console width used to decide how many columns are displayed
+const MAX_PRINT_WIDTH: usize = 80;
+
+/// Data Frames
+///
+/// Data frames are lists of named columns of equal length, with a `class`
+/// attribute of `"data.frame"`. The number of rows is given by the
+/// `row.names` attribute, such that frames without columns can still have
+/// rows.
+///
+impl Obj {
+    /// Whether the object is a data frame
+    pub fn is_data_frame(&self) -> bool {
+        let Obj::List(_) = self else {
+            return false;
+        };
+
+        match self.get_attr("class") {
+            Some(Obj::Vector(Vector::Character(classes))) => classes
+                .iter_values()
+                .any(|class| matches!(class, OptionNA::Some(c) if c == "data.frame")),
+            _ => false,
+        }
+    }
+
+    /// The number of rows and columns of a data frame
+    pub fn frame_shape(&self) -> Option<(usize, usize)> {
+        if !self.is_data_frame() {
+            return None;
+        }
+
+        let nrow = self
+            .get_attr("row.names")
+            .and_then(|rows| rows.len())
+            .unwrap_or_default();
+
+        Some((nrow, self.len().unwrap_or_default()))
+    }
+
+    /// Build a data frame from named columns
+    ///
+    /// Columns of length one are recycled to the length of the longest
+    /// column, while columns of any other length must all be the same
+    /// length. `null` columns are omitted and unnamed columns are named by
+    /// their position.
+    pub fn new_data_frame(columns: Vec<(Character, Obj)>) -> EvalResult {
+        let columns: Vec<(Character, Obj)> = columns
+            .into_iter()
+            .filter(|(_, column)| !matches!(column, Obj::Null))
+            .collect();
+
+        if columns
+            .iter()
+            .any(|(_, column)| matches!(column, Obj::Vector(v) if v.is_unbounded()))
+        {
+            return Error::UnboundedSequence.into();
+        }
+
+        let nrow = columns
+            .iter()
+            .map(|(_, column)| column.len().unwrap_or_default())
+            .max()
+            .unwrap_or_default();
+
+        let mut frame = Vec::with_capacity(columns.len());
+        for (i, (name, column)) in columns.into_iter().enumerate() {
+            let name = match name {
+                OptionNA::Some(name) if !name.is_empty() => name,
+                _ => format!("V{}", i + 1).into(),
+            };
+
+            let column = as_column(column, nrow, |len| {
+                Error::Other(format!(
+                    "arguments imply differing number of rows: {nrow}, {len}"
+                ))
+            })?;
+
+            frame.push((OptionNA::Some(name), column));
+        }
+
+        let rows: Vec<i32> = (1..=nrow as i32).collect();
+        frame_from_columns(frame, Obj::Vector(rows.into()))
+    }
+
+    /// Index a data frame by rows and columns
+    ///
+    /// Columns may be selected by name. When `drop` is set and a single
+    /// column is selected, the column itself is returned.
+    pub fn try_get_frame(&self, rows: Option<Obj>, cols: Option<Obj>, drop: bool) -> EvalResult {
+        let (Some((nrow, ncol)), Obj::List(l)) = (self.frame_shape(), self) else {
+            return internal_err!();
+        };
+
+        let columns: Vec<(Character, Obj)> = l.iter_pairs().collect();
+        let cols = match cols {
+            None => (0..ncol).collect(),
+            Some(Obj::Vector(Vector::Character(names))) => names
+                .iter_values()
+                .map(|name| {
+                    columns
+                        .iter()
+                        .position(|(col, _)| *col == name)
+                        .ok_or_else(|| Error::Other("undefined columns selected".to_string()))
+                })
+                .collect::<Result<Vec<usize>, Error>>()?,
+            Some(index) => dim_positions(index, ncol)?,
+        };
+
+        let row_names = self.get_attr("row.names").unwrap_or(Obj::Null);
+        let (rows, row_names) = match rows {
+            None => (None, row_names),
+            Some(index) => {
+                let positions: Vec<i32> = dim_positions(index, nrow)?
+                    .into_iter()
+                    .map(|i| i as i32 + 1)
+                    .collect();
+
+                let index = Obj::Vector(positions.into());
+                let row_names = materialize(row_names.try_get(index.clone())?)?;
+                (Some(index), row_names)
+            }
+        };
+
+        let mut frame = Vec::with_capacity(cols.len());
+        for i in cols {
+            let (name, column) = columns[i].clone();
+            let column = match &rows {
+                Some(index) => materialize(column.try_get(index.clone())?)?,
+                None => column,
+            };
+
+            frame.push((name, column));
+        }
+
+        if drop && frame.len() == 1 {
+            return Ok(frame.swap_remove(0).1);
+        }
+
+        frame_from_columns(frame, row_names)
+    }
+
+    /// Replace, add or remove (when `null`) a column of a data frame
+    ///
+    /// Values of length one are recycled, otherwise values must have one
+    /// element per row.
+    pub fn try_set_column(&self, name: &str, value: Obj) -> EvalResult {
+        let (Some((nrow, _)), Obj::List(l)) = (self.frame_shape(), self) else {
+            return internal_err!();
+        };
+
+        let mut columns: Vec<(Character, Obj)> = l.iter_pairs().collect();
+        let position = columns
+            .iter()
+            .position(|(col, _)| matches!(col, OptionNA::Some(col) if col == name));
+
+        match (position, value) {
+            (Some(i), Obj::Null) => {
+                columns.remove(i);
+            }
+            (None, Obj::Null) => (),
+            (position, value) => {
+                let column = as_column(value, nrow, |len| {
+                    Error::Other(format!("replacement has {len} rows, data has {nrow}"))
+                })?;
+
+                match position {
+                    Some(i) => columns[i].1 = column,
+                    None => columns.push((OptionNA::Some(name.into()), column)),
+                }
+            }
+        }
+
+        let row_names = self.get_attr("row.names").unwrap_or(Obj::Null);
+        frame_from_columns(columns, row_names)
+    }
+
+    /// The name of a column of a data frame, given an index into its columns
+    ///
+    /// Indexing one past the last column names a new column by position.
+    pub fn frame_column_name(&self, index: &Obj) -> Result<String, Signal> {
+        let Obj::List(l) = self else {
+            return internal_err!();
+        };
+
+        let invalid = || Error::Other("invalid subscript for a data frame column".to_string());
+        let names: Vec<Character> = l.iter_names().map(|n| n.collect()).unwrap_or_default();
+
+        let index = match index {
+            Obj::Vector(v) if v.len() == 1 => v.clone(),
+            _ => return Err(invalid().into()),
+        };
+
+        match index {
+            Vector::Character(name) => match name.iter_values().next() {
+                Some(OptionNA::Some(name)) => Ok(name.into()),
+                _ => Err(invalid().into()),
+            },
+            index => match integers(index).first() {
+                Some(&OptionNA::Some(i)) if i >= 1 && i as usize <= names.len() => {
+                    Ok(names[i as usize - 1].to_string())
+                }
+                Some(&OptionNA::Some(i)) if i as usize == names.len() + 1 => Ok(format!("V{i}")),
+                _ => Err(invalid().into()),
+            },
+        }
+    }
+}
+
+/// Prepare a vector to be used as a column with `nrow` rows
+///
+/// Raises the error built by `mismatch` from the length of the vector when
+/// it can't be used as a column.
+fn as_column(x: Obj, nrow: usize, mismatch: impl FnOnce(usize) -> Error) -> EvalResult {
+    let len = x.len().unwrap_or_default();
+    match x {
+        Obj::Vector(v) if v.is_unbounded() => Error::UnboundedSequence.into(),
+        Obj::Vector(v) if len == nrow => Ok(Obj::Vector(v.materialize()?)),
+        Obj::Vector(_) if len == 1 => {
+            let recycled = Obj::Vector(vec![1; nrow].into());
+            materialize(x.try_get(recycled)?)
+        }
+        _ => mismatch(len).into(),
+    }
+}
+
+fn materialize(x: Obj) -> EvalResult {
+    match x {
+        Obj::Vector(v) => Ok(Obj::Vector(v.materialize()?)),
+        x => Ok(x),
+    }
+}
+
+fn frame_from_columns(columns: Vec<(Character, Obj)>, row_names: Obj) -> EvalResult {
+    Obj::List(List::from(columns))
+        .set_attr("row.names", row_names)?
+        .set_attr("class", Obj::Vector(vec!["data.frame".to_string()].into()))
+}
+
+/// Display a data frame as a table of aligned columns
+///
+/// Only as many rows and columns as fit in the console are displayed,
+/// followed by a note of what was omitted.
+pub fn display_data_frame(x: &Obj, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let (Some((nrow, ncol)), Obj::List(l)) = (x.frame_shape(), x) else {
+        return Ok(());
+    };
+
+    if ncol == 0 {
+        return write!(f, "data frame with 0 columns and {nrow} rows");
+    }
+
+    let shown = nrow.min(MAX_PRINT_ROWS);
+    let row_names: Vec<String> = match x.get_attr("row.names") {
+        Some(Obj::Vector(v)) => cells(&v).into_iter().take(shown).collect(),
+        _ => (1..=shown).map(|i| i.to_string()).collect(),
+    };
+
+    let gutter = row_names.iter().map(String::len).max().unwrap_or_default();
+    let mut width = gutter;
+    let mut columns: Vec<(String, Vec<String>, usize)> = vec![];
+    let mut omitted: Vec<String> = vec![];
+
+    for (name, column) in l.iter_pairs() {
+        let name = name.to_string();
+        let values: Vec<String> = match column {
+            Obj::Vector(v) => cells(&v).into_iter().take(shown).collect(),
+            _ => vec![],
+        };
+
+        let col_width = values
+            .iter()
+            .chain([&name])
+            .map(String::len)
+            .max()
+            .unwrap_or(0);
+        if !omitted.is_empty() || (!columns.is_empty() && width + 1 + col_width > MAX_PRINT_WIDTH) {
+            omitted.push(name);
+            continue;
+        }
+
+        width += 1 + col_width;
+        columns.push((name, values, col_width));
+    }
+
+    write!(f, "{:gutter$}", "")?;
+    for (name, _, width) in &columns {
+        write!(f, " {name:>width$}")?;
+    }
+
+    if nrow == 0 {
+        write!(f, "\n<0 rows>")?;
+    }
+
+    for (i, row_name) in row_names.iter().enumerate() {
+        write!(f, "\n{row_name:<gutter$}")?;
+        for (_, values, width) in &columns {
+            write!(f, " {:>width$}", values[i])?;
+        }
+    }
+
+    if nrow > shown {
+        write!(f, "\n[ omitting {} rows ]", nrow - shown)?;
+    }
+
+    if !omitted.is_empty() {
+        write!(
+            f,
+            "\n[ omitting {} columns: {} ]",
+            omitted.len(),
+            omitted.join(", ")
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Format the values of a column, displaying factors by their labels
+fn cells(x: &Vector) -> Vec<String> {
+    let na = |x: Character| match x {
+        OptionNA::Some(x) => x.into(),
+        OptionNA::NA => "<NA>".to_string(),
+    };
+
+    match x {
+        Vector::Character(v) => v.iter_values().map(na).collect(),
+        v if v.factor_levels().is_some() => match v.clone().as_character() {
+            Vector::Character(v) => v.iter_values().map(na).collect(),
+            _ => unreachable!(),
+        },
+        Vector::Double(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+        Vector::Integer(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+        Vector::Logical(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+        Vector::Complex(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+        Vector::Raw(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+    }
+}
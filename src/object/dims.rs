@@ -0,0 +1,426 @@
+use std::fmt;
+
+use crate::error::Error;
+use crate::internal_err;
+use crate::lang::{EvalResult, Signal};
+use crate::object::types::*;
+use crate::object::*;
+
+/// Matrices and Arrays
+///
+/// Matrices and arrays are vectors with a `dim` attribute, giving the extent
+/// of each dimension. Values are stored in column-major order, such that the
+/// first dimension varies fastest.
+///
+impl Obj {
+    /// The extents of each dimension, if the object has a `dim` attribute
+    pub fn dim(&self) -> Option<Vec<usize>> {
+        // data frames are indexed by rows and columns, like matrices
+        if let Some((nrow, ncol)) = self.frame_shape() {
+            return Some(vec![nrow, ncol]);
+        }
+
+        match self.get_attr("dim")? {
+            Obj::Vector(v) => Some(
+                integers(v)
+                    .into_iter()
+                    .map(|i| match i {
+                        OptionNA::Some(i) => i.max(0) as usize,
+                        OptionNA::NA => 0,
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Set the `dim` attribute, validating it against the length of the object
+    pub fn set_dim(self, dim: Obj) -> EvalResult {
+        let Obj::Vector(dim) = dim.as_integer()? else {
+            return Error::Other("invalid 'dim' value".to_string()).into();
+        };
+
+        let dim = integers(dim);
+        let mut n = 1;
+        for extent in dim.iter() {
+            match extent {
+                OptionNA::Some(i) if *i >= 0 => n *= *i as usize,
+                _ => {
+                    return Error::Other("the dims contain missing or negative values".to_string())
+                        .into()
+                }
+            }
+        }
+
+        let len = self.len().unwrap_or_default();
+        if dim.is_empty() || n != len {
+            return Error::Other(format!(
+                "dims [product {n}] do not match the length of object [{len}]"
+            ))
+            .into();
+        }
+
+        let x = match self {
+            Obj::Vector(v) => Obj::Vector(v.materialize()?),
+            Obj::List(l) => Obj::List(l.materialize()?),
+            x => x,
+        };
+
+        let mut attrs = x.attributes().unwrap_or_default();
+        attrs.set("dim", Obj::Vector(dim.into()));
+        Ok(x.with_attributes(Some(attrs)))
+    }
+
+    /// Used for `[` syntax with an index per dimension
+    ///
+    /// Omitted indices, given as `None`, select the entire dimension. When
+    /// `drop` is `true`, dimensions of extent one are removed from the
+    /// result, returning a plain vector if fewer than two dimensions remain.
+    pub fn try_get_dims(&self, indices: Vec<Option<Obj>>, drop: bool) -> EvalResult {
+        if let (true, [rows, cols]) = (self.is_data_frame(), indices.as_slice()) {
+            return self.try_get_frame(rows.clone(), cols.clone(), drop);
+        }
+
+        let (positions, extents) = self.dims_positions(indices)?;
+        let x = self.try_get(Obj::Vector(positions.into()))?;
+
+        let extents: Vec<usize> = match drop {
+            true => extents.into_iter().filter(|&i| i != 1).collect(),
+            false => extents,
+        };
+
+        if drop && extents.len() < 2 {
+            return Ok(match x {
+                Obj::Vector(v) => Obj::Vector(v.materialize()?),
+                x => x,
+            });
+        }
+
+        x.set_dim(Obj::Vector(extents_vector(&extents)))
+    }
+
+    /// Used for assignment to `[` with an index per dimension
+    pub fn try_get_dims_mut(&self, indices: Vec<Option<Obj>>) -> EvalResult {
+        if self.is_data_frame() {
+            return Error::Other(
+                "data frames can only be assigned to by column, as in `x$col <- value`".to_string(),
+            )
+            .into();
+        }
+
+        let (positions, _) = self.dims_positions(indices)?;
+        self.try_get(Obj::Vector(positions.into()))
+    }
+
+    /// Convert an index per dimension into positions in the underlying vector
+    ///
+    /// Returns the (1-based) positions of the selected elements in
+    /// column-major order, along with the extent of each selected dimension.
+    fn dims_positions(&self, indices: Vec<Option<Obj>>) -> Result<(Vec<i32>, Vec<usize>), Signal> {
+        let dim = match self.dim() {
+            Some(dim) if dim.len() == indices.len() => dim,
+            _ => return Error::Other("incorrect number of dimensions".to_string()).into(),
+        };
+
+        let mut selected: Vec<Vec<usize>> = Vec::with_capacity(dim.len());
+        for (&extent, index) in dim.iter().zip(indices) {
+            let index = match index {
+                None => {
+                    selected.push((0..extent).collect());
+                    continue;
+                }
+                Some(Obj::Vector(Vector::Character(_))) => {
+                    return Error::Other("no 'dimnames' attribute for array".to_string()).into()
+                }
+                Some(index) => index,
+            };
+
+            selected.push(dim_positions(index, extent)?);
+        }
+
+        let mut positions = vec![0];
+        let mut stride = 1;
+        for (extent, indices) in dim.iter().zip(selected.iter()) {
+            let prev = std::mem::take(&mut positions);
+            positions = indices
+                .iter()
+                .flat_map(|i| prev.iter().map(move |p| p + i * stride))
+                .collect();
+            stride *= extent;
+        }
+
+        let positions = positions.into_iter().map(|p| p as i32 + 1).collect();
+        let extents = selected.iter().map(Vec::len).collect();
+        Ok((positions, extents))
+    }
+
+    /// Transpose a matrix, treating vectors as a single column
+    pub fn transpose(&self) -> EvalResult {
+        if matches!(self, Obj::Vector(v) if v.is_unbounded()) {
+            return Error::UnboundedSequence.into();
+        }
+
+        let (nrow, ncol) = match self.dim().as_deref() {
+            Some(&[nrow, ncol]) => (nrow, ncol),
+            None => (self.len().unwrap_or_default(), 1),
+            Some(_) => return Error::Other("argument is not a matrix".to_string()).into(),
+        };
+
+        let positions: Vec<i32> = (0..nrow)
+            .flat_map(|i| (0..ncol).map(move |j| (i + j * nrow) as i32 + 1))
+            .collect();
+
+        let x = self.try_get(Obj::Vector(positions.into()))?;
+        x.set_dim(Obj::Vector(extents_vector(&[ncol, nrow])))
+    }
+
+    /// Matrix multiplication
+    ///
+    /// Vectors are promoted to either a row or column matrix, whichever
+    /// makes the arguments conformable. Two vectors of the same length
+    /// produce their inner product.
+    pub fn matmul(&self, other: &Obj) -> EvalResult {
+        let nonconformable = || Error::Other("non-conformable arguments".to_string()).into();
+        let shape = |x: &Obj| match x.dim().as_deref() {
+            Some(&[nrow, ncol]) => Ok(Some((nrow, ncol))),
+            Some(_) => Err(Error::Other(
+                "arguments must be matrices or vectors".to_string(),
+            )),
+            None => Ok(None),
+        };
+
+        let nx = self.len().unwrap_or_default();
+        let ny = other.len().unwrap_or_default();
+        let ((xr, xc), (yr, yc)) = match (shape(self)?, shape(other)?) {
+            (Some(x), Some(y)) => (x, y),
+            (None, Some(y)) if nx == y.0 => ((1, nx), y),
+            (None, Some(y)) => ((nx, 1), y),
+            (Some(x), None) if ny == x.1 => (x, (ny, 1)),
+            (Some(x), None) => (x, (1, ny)),
+            (None, None) if nx == ny => ((1, nx), (ny, 1)),
+            (None, None) if nx == 1 || ny == 1 => ((nx, 1), (1, ny)),
+            (None, None) => return nonconformable(),
+        };
+
+        if xc != yr {
+            return nonconformable();
+        }
+
+        let x = doubles(self)?;
+        let y = doubles(other)?;
+
+        let mut result: Vec<Double> = Vec::with_capacity(xr * yc);
+        for j in 0..yc {
+            for i in 0..xr {
+                let value =
+                    (0..xc).try_fold(0.0, |acc, k| match (&x[i + k * xr], &y[k + j * yr]) {
+                        (OptionNA::Some(xi), OptionNA::Some(yi)) => Some(acc + xi * yi),
+                        _ => None,
+                    });
+
+                result.push(value.map_or(OptionNA::NA, OptionNA::Some));
+            }
+        }
+
+        Obj::Vector(result.into()).set_dim(Obj::Vector(extents_vector(&[xr, yc])))
+    }
+}
+
+/// Resolve the (0-based) positions selected by an index into one dimension
+pub(super) fn dim_positions(index: Obj, extent: usize) -> Result<Vec<usize>, Signal> {
+    let out_of_bounds = || Error::Other("subscript out of bounds".to_string()).into();
+
+    let index = match index.as_vector()? {
+        // reuse vector indexing to resolve logical indices
+        mask @ Obj::Vector(Vector::Logical(_)) => {
+            let along = Obj::Vector(Vector::from((1..=extent as i32).collect::<Vec<i32>>()));
+            let Obj::Vector(along) = along.try_get(mask)? else {
+                return internal_err!();
+            };
+            integers(along)
+        }
+        Obj::Vector(v) => integers(v),
+        _ => return internal_err!(),
+    };
+
+    let mut positive = vec![];
+    let mut excluded = vec![];
+    for i in index {
+        match i {
+            OptionNA::Some(i) if i > 0 && i as usize <= extent => positive.push(i as usize - 1),
+            OptionNA::Some(i) if i < 0 && i.unsigned_abs() as usize <= extent => {
+                excluded.push(i.unsigned_abs() as usize - 1)
+            }
+            OptionNA::Some(0) => (),
+            _ => return out_of_bounds(),
+        }
+    }
+
+    match (positive.is_empty(), excluded.is_empty()) {
+        (_, true) => Ok(positive),
+        (true, false) => Ok((0..extent).filter(|i| !excluded.contains(i)).collect()),
+        (false, false) => {
+            Error::Other("can't mix positive and negative subscripts".to_string()).into()
+        }
+    }
+}
+
+pub(super) fn integers(v: Vector) -> Vec<Integer> {
+    match v.as_integer() {
+        Vector::Integer(v) => v.iter_values().collect(),
+        _ => unreachable!(),
+    }
+}
+
+fn doubles(x: &Obj) -> Result<Vec<Double>, Signal> {
+    match x {
+        Obj::Vector(Vector::Character(_)) => (),
+        Obj::Vector(v) => match v.clone().as_double() {
+            Vector::Double(v) => return Ok(v.iter_values().collect()),
+            _ => unreachable!(),
+        },
+        _ => (),
+    };
+
+    Error::Other("requires numeric/complex matrix/vector arguments".to_string()).into()
+}
+
+fn extents_vector(extents: &[usize]) -> Vector {
+    Vector::from(extents.iter().map(|&i| i as i32).collect::<Vec<i32>>())
+}
+
+/// Display a vector as a grid, given the extents of its dimensions
+///
+/// Arrays of more than two dimensions are displayed as a series of matrices,
+/// one for each combination of indices into the trailing dimensions.
+pub fn display_array(x: &Vector, dim: &[usize], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let values: Vec<String> = match x {
+        Vector::Double(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+        Vector::Integer(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+        Vector::Logical(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+        Vector::Complex(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+        Vector::Raw(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+        Vector::Character(v) => v.iter_values().map(|i| format!("{i:?}")).collect(),
+    };
+
+    let left_align = matches!(x, Vector::Character(_));
+    let (nrow, ncol) = (dim[0], dim[1]);
+    let trailing = &dim[2..];
+
+    if dim.len() == 2 {
+        return display_matrix(&values, nrow, ncol, left_align, f);
+    }
+
+    let size = nrow * ncol;
+    let nslices: usize = trailing.iter().product();
+    if size == 0 || nslices == 0 {
+        let extents: Vec<String> = dim.iter().map(usize::to_string).collect();
+        return write!(f, "<{} array>", extents.join(" x "));
+    }
+
+    for slice in 0..nslices {
+        if slice > 0 {
+            write!(f, "\n\n")?;
+        }
+
+        let mut rest = slice;
+        let labels: Vec<String> = trailing
+            .iter()
+            .map(|extent| {
+                let label = rest % extent + 1;
+                rest /= extent;
+                label.to_string()
+            })
+            .collect();
+
+        writeln!(f, ", , {}\n", labels.join(", "))?;
+        let slice_values = &values[slice * size..(slice + 1) * size];
+        display_matrix(slice_values, nrow, ncol, left_align, f)?;
+    }
+
+    Ok(())
+}
+
+fn display_matrix(
+    values: &[String],
+    nrow: usize,
+    ncol: usize,
+    left_align: bool,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    if nrow == 0 || ncol == 0 {
+        return write!(f, "<{nrow} x {ncol} matrix>");
+    }
+
+    let row_labels: Vec<String> = (1..=nrow).map(|i| format!("[{i},]")).collect();
+    let label_width = row_labels.iter().map(String::len).max().unwrap_or(0);
+
+    let columns: Vec<(String, usize)> = (0..ncol)
+        .map(|j| {
+            let header = format!("[,{}]", j + 1);
+            let column = &values[j * nrow..(j + 1) * nrow];
+            let width = column.iter().map(|v| v.chars().count()).max().unwrap_or(0);
+            let width = width.max(header.len());
+            (header, width)
+        })
+        .collect();
+
+    let align = |s: &str, width: usize| match left_align {
+        true => format!("{s:<width$}"),
+        false => format!("{s:>width$}"),
+    };
+
+    // columns are wrapped into blocks which fit within the console width
+    let mut start = 0;
+    while start < ncol {
+        let mut end = start;
+        let mut line_width = label_width;
+        while end < ncol && (end == start || line_width + 1 + columns[end].1 <= 80) {
+            line_width += 1 + columns[end].1;
+            end += 1;
+        }
+
+        if start > 0 {
+            writeln!(f)?;
+        }
+
+        write!(f, "{:label_width$}", "")?;
+        for (header, width) in &columns[start..end] {
+            write!(f, " {}", align(header, *width))?;
+        }
+
+        for (i, label) in row_labels.iter().enumerate() {
+            write!(f, "\n{label:>label_width$}")?;
+            for (j, (_, width)) in columns.iter().enumerate().take(end).skip(start) {
+                write!(f, " {}", align(&values[i + j * nrow], *width))?;
+            }
+        }
+
+        start = end;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::r;
+
+    #[test]
+    fn display_matrix() {
+        let x = r! { matrix([1, 20, 3, 4], nrow = 2) }.unwrap();
+        assert_eq!(
+            format!("{x}"),
+            "     [,1] [,2]\n[1,]    1    3\n[2,]   20    4"
+        );
+    }
+
+    #[test]
+    fn display_array() {
+        let x = r! { array(1:4, [1, 2, 2]) }.unwrap();
+        assert_eq!(
+            format!("{x}"),
+            ", , 1\n\n     [,1] [,2]\n[1,]    1    2\n\n, , 2\n\n     [,1] [,2]\n[1,]    3    4"
+        );
+    }
+}
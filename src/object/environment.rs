@@ -1,9 +1,10 @@
 use core::fmt;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use std::cell::RefCell;
 use std::fmt::Display;
 use std::rc::Rc;
 
+use crate::bytecode::Compiled;
 use crate::callable::builtins::BUILTIN;
 use crate::context::Context;
 use crate::error::Error;
@@ -11,14 +12,166 @@ use crate::lang::{EvalResult, Signal};
 use crate::object::types::Character;
 use crate::object::ViewMut;
 
-use super::{Expr, ExprList, List, Obj};
+use super::{register, Expr, ExprList, List, Namespace, Obj, Symbol};
+
+thread_local! {
+    /// Names of builtins which have been bound to a closure in any environment
+    static SHADOWED_BUILTINS: RefCell<HashSet<Symbol>> = Default::default();
+}
+
+/// Record bindings of closures to the names of builtins, such that only
+/// calls by those names need to look for a closure before the builtin
+fn note_shadowing(name: Symbol, value: &Obj) {
+    if let Obj::Function(_, body, ..) = value {
+        if !matches!(body, Expr::Primitive(_)) && BUILTIN.contains_key(name.as_str()) {
+            SHADOWED_BUILTINS.with_borrow_mut(|names| names.insert(name));
+        }
+    }
+}
 
 #[derive(Default, Clone, PartialEq)]
 pub struct Environment {
-    pub values: RefCell<HashMap<String, Obj>>,
+    pub values: RefCell<Bindings>,
     pub parent: Option<Rc<Environment>>,
 }
 
+/// The Bindings of an Environment
+///
+/// Bindings are stored in slots, which are indexed by name. A name keeps its
+/// slot for the lifetime of the environment, even once it is removed, such
+/// that compiled code can address variables by their slot instead of by
+/// their name.
+///
+/// The environment of a compiled closure call reserves slots for the
+/// closure's local variables up front and is _sealed_, meaning that no other
+/// variables are bound in it. Binding any new name unseals the environment,
+/// invalidating assumptions that compiled code makes about where variables
+/// are found.
+///
+#[derive(Default, Clone, PartialEq)]
+pub struct Bindings {
+    index: HashMap<Symbol, usize>,
+    slots: Vec<(Symbol, Option<Obj>)>,
+    sealed: bool,
+}
+
+impl Bindings {
+    pub fn get(&self, name: &Symbol) -> Option<&Obj> {
+        let slot = *self.index.get(name)?;
+        self.slots[slot].1.as_ref()
+    }
+
+    pub fn insert(&mut self, name: Symbol, value: Obj) -> Option<Obj> {
+        note_shadowing(name, &value);
+        match self.index.get(&name) {
+            Some(&slot) => self.slots[slot].1.replace(value),
+            None => {
+                self.sealed = false;
+                self.index.insert(name, self.slots.len());
+                self.slots.push((name, Some(value)));
+                None
+            }
+        }
+    }
+
+    pub fn remove(&mut self, name: &Symbol) -> Option<Obj> {
+        let slot = *self.index.get(name)?;
+        self.slots[slot].1.take()
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.iter().filter(|(_, v)| v.is_some()).count()
+    }
+
+    /// The values of bound variables
+    pub fn values(&self) -> impl Iterator<Item = &Obj> {
+        self.slots.iter().filter_map(|(_, v)| v.as_ref())
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The names of bound variables
+    pub fn keys(&self) -> impl Iterator<Item = &Symbol> {
+        self.slots
+            .iter()
+            .filter(|(_, v)| v.is_some())
+            .map(|(k, _)| k)
+    }
+
+    /// The slot of a name, whether or not it is bound
+    pub fn slot(&self, name: &Symbol) -> Option<usize> {
+        self.index.get(name).copied()
+    }
+
+    /// Get the value in a slot, provided that it is bound to `name`
+    pub fn get_slot(&self, slot: usize, name: Symbol) -> Option<&Obj> {
+        match self.slots.get(slot) {
+            Some((n, value)) if *n == name => value.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Set the value in a slot, handing the value back if the slot is not
+    /// `name`'s
+    pub fn set_slot(&mut self, slot: usize, name: Symbol, value: Obj) -> Result<(), Obj> {
+        match self.slots.get_mut(slot) {
+            Some((n, binding)) if *n == name => {
+                note_shadowing(name, &value);
+                *binding = Some(value);
+                Ok(())
+            }
+            _ => Err(value),
+        }
+    }
+
+    /// Whether only reserved names are bound
+    pub fn is_sealed(&self) -> bool {
+        self.sealed
+    }
+
+    /// Reserve the first slots for a layout of names and seal the bindings
+    ///
+    /// Names that are already bound keep their values and are moved into
+    /// their reserved slot, while any others follow the reserved slots.
+    ///
+    pub fn reserve(&mut self, layout: &[Symbol]) {
+        let mut slots: Vec<(Symbol, Option<Obj>)> = layout
+            .iter()
+            .map(|name| (*name, self.remove(name)))
+            .collect();
+
+        for (name, value) in self.slots.drain(..) {
+            if value.is_some() {
+                slots.push((name, value));
+            }
+        }
+
+        self.index = slots
+            .iter()
+            .enumerate()
+            .map(|(i, (name, _))| (*name, i))
+            .collect();
+
+        self.slots = slots;
+        self.sealed = true;
+    }
+}
+
+impl fmt::Debug for Bindings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(
+                self.slots
+                    .iter()
+                    .filter_map(|(k, v)| v.as_ref().map(|v| (k, v))),
+            )
+            .finish()
+    }
+}
+
 impl fmt::Debug for Environment {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Environment")
@@ -28,17 +181,29 @@ impl fmt::Debug for Environment {
 }
 
 impl Environment {
+    /// Create a new environment, registered for cycle collection
+    pub fn new(parent: Option<Rc<Environment>>) -> Rc<Environment> {
+        let env = Rc::new(Environment { parent, ..Default::default() });
+        register(&env);
+        env
+    }
+
     pub fn from_builtins() -> Rc<Environment> {
-        let env = Rc::new(Environment::default());
+        let env = Environment::new(None);
         for (name, builtin) in BUILTIN.iter() {
             let builtin_fn = Obj::Function(
                 ExprList::new(),
                 Expr::Primitive(builtin.clone()),
                 env.clone(),
+                None,
+                Compiled::default(),
             );
 
-            env.insert(String::from(*name), builtin_fn);
+            env.insert(Symbol::new(name), builtin_fn);
         }
+
+        let exports = BUILTIN.keys().map(|name| Symbol::new(name));
+        Namespace::new("base", env.clone(), exports).register();
         env
     }
 
@@ -51,26 +216,60 @@ impl Environment {
         self.len() == 0
     }
 
-    pub fn insert(&self, name: String, value: Obj) {
+    pub fn insert(&self, name: Symbol, value: Obj) {
         self.values.borrow_mut().insert(name, value);
     }
 
+    /// Reserve slots for the local variables of a compiled closure
+    pub fn reserve(&self, layout: &[Symbol]) {
+        self.values.borrow_mut().reserve(layout);
+    }
+
+    pub fn remove(&self, name: Symbol) -> Option<Obj> {
+        self.values.borrow_mut().remove(&name)
+    }
+
     pub fn append(&self, l: List) {
         for (key, value) in l.pairs_ref().iter() {
             if let Character::Some(name) = key {
-                self.values.borrow_mut().insert(name.clone(), value.clone());
+                self.values.borrow_mut().insert(name.into(), value.clone());
+            }
+        }
+    }
+
+    /// Whether a builtin is shadowed by a function of the same name
+    ///
+    /// Only functions shadow builtins when called, such that `c(1, 2)` still
+    /// calls the builtin after `c <- 1`. Environments are only searched for
+    /// names which were ever bound to a closure.
+    pub fn shadows_builtin(&self, name: Symbol) -> bool {
+        if !SHADOWED_BUILTINS.with_borrow(|names| names.contains(&name)) {
+            return false;
+        }
+
+        let mut env = self;
+        loop {
+            match env.values.borrow().get(&name) {
+                Some(Obj::Function(_, Expr::Primitive(_), ..)) => return false,
+                Some(Obj::Function(..)) => return true,
+                _ => (),
+            }
+
+            match &env.parent {
+                Some(parent) => env = parent,
+                None => return false,
             }
         }
     }
 
-    pub fn get(&self, name: String) -> EvalResult {
-        let (x, _) = self.find(name.clone())?;
+    pub fn get(&self, name: Symbol) -> EvalResult {
+        let (x, _) = self.find(name)?;
         EvalResult::Ok(x.clone())
     }
 
     /// Find a variable in the environment or one of its parents.
     /// If the variable is found, a mutable view on it is returned.
-    pub fn find(&self, name: String) -> Result<(Obj, Rc<Environment>), Signal> {
+    pub fn find(&self, name: Symbol) -> Result<(Obj, Rc<Environment>), Signal> {
         let mut env = self;
 
         loop {
@@ -96,19 +295,34 @@ impl Environment {
                     ExprList::new(),
                     Expr::Primitive(prim.clone()),
                     Rc::new(self.clone()), // TODO(bug): will this retain shared ref?
+                    None,
+                    Compiled::default(),
                 );
 
                 return Result::Ok((x, Rc::new(env.clone())));
 
             // otherwise, throw error
             } else {
-                return Result::Err(Signal::Error(Error::VariableNotFound(name)));
+                return Result::Err(Signal::Error(Error::VariableNotFound(name.into())));
             }
         }
     }
 
-    pub fn get_mut(&self, name: String) -> EvalResult {
-        let (x, env) = self.find(name.clone())?;
+    pub fn get_mut(&self, name: Symbol) -> EvalResult {
+        let (x, env) = self.find(name)?;
+
+        // sequences and lazy vectors are materialized before they are modified,
+        // replacing the computed vector in the environment where it is modified
+        if let Obj::Vector(v) = &x {
+            if v.is_computed() {
+                let mut v = v.clone();
+                v.materialize_computed()?;
+                let x = Obj::Vector(v);
+                self.insert(name, x.clone());
+                return EvalResult::Ok(x.view_mut());
+            }
+        }
+
         if *self == *env {
             return EvalResult::Ok(x.view_mut());
         }
@@ -149,8 +363,29 @@ impl Display for Environment {
 #[cfg(test)]
 
 mod tests {
+    use super::Bindings;
+    use crate::object::{Obj, Symbol};
     use crate::{r, r_expect};
 
+    #[test]
+    fn reserved_bindings() {
+        let (x, y, z) = (Symbol::new("x"), Symbol::new("y"), Symbol::new("z"));
+        let mut bindings = Bindings::default();
+        bindings.insert(z, Obj::Null);
+        bindings.reserve(&[x, y]);
+
+        assert!(bindings.is_sealed());
+        assert_eq!(bindings.slot(&y), Some(1));
+        assert_eq!(bindings.get_slot(2, z), Some(&Obj::Null));
+        assert_eq!(bindings.get_slot(0, x), None);
+
+        // reserved names don't unseal the bindings, but any others do
+        bindings.insert(x, Obj::Null);
+        assert!(bindings.is_sealed());
+        bindings.insert(Symbol::new("w"), Obj::Null);
+        assert!(!bindings.is_sealed());
+    }
+
     #[test]
     fn dollar() {
         r_expect! {{"
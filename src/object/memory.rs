@@ -0,0 +1,389 @@
+use hashbrown::{HashMap, HashSet};
+use std::cell::RefCell;
+use std::mem::size_of;
+use std::rc::{Rc, Weak};
+
+use super::rep::Rep;
+use super::types::Character;
+use super::*;
+
+thread_local! {
+    static ENVIRONMENTS: RefCell<Registry> = RefCell::new(Registry::default());
+}
+
+/// The number of registered environments before the first collection
+const MIN_THRESHOLD: usize = 1024;
+
+/// Registered Environments
+///
+/// Every environment created through [Environment::new] is registered, such
+/// that reference cycles between environments and the closures, promises
+/// and environments bound within them can be found and collected.
+///
+#[derive(Default)]
+struct Registry {
+    environments: Vec<Weak<Environment>>,
+    threshold: usize,
+    reclaimed: usize,
+}
+
+/// The outcome of a garbage collection
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Collection {
+    /// Environments reclaimed since the last reported collection
+    pub reclaimed: usize,
+    /// Environments still alive after the collection
+    pub environments: usize,
+}
+
+/// Register an environment for cycle collection
+///
+/// Once the number of registered environments has doubled since the last
+/// collection, unreachable cycles are collected.
+///
+pub fn register(env: &Rc<Environment>) {
+    let full = ENVIRONMENTS.with_borrow_mut(|registry| {
+        registry.environments.push(Rc::downgrade(env));
+        registry.environments.len() >= registry.threshold.max(MIN_THRESHOLD)
+    });
+
+    if full {
+        collect_cycles();
+    }
+}
+
+/// Collect unreachable cycles of environments
+///
+/// Reports the environments reclaimed since the last call to `collect`,
+/// including those reclaimed by collections triggered in the meantime.
+///
+pub fn collect() -> Collection {
+    collect_cycles();
+    ENVIRONMENTS.with_borrow_mut(|registry| Collection {
+        reclaimed: std::mem::take(&mut registry.reclaimed),
+        environments: registry.environments.len(),
+    })
+}
+
+/// Find cycles by trial deletion
+///
+/// References to an environment which are held by other registered
+/// environments are counted. Environments with more references than that are
+/// referenced from elsewhere, such as the call stack or a value being
+/// evaluated, and are alive, as is everything reachable from them. The
+/// remaining environments are only referenced from within unreachable cycles,
+/// which are broken by clearing their bindings.
+///
+/// Only references held by values that an environment owns exclusively are
+/// counted, such that values shared with anything else keep the environments
+/// they reference alive.
+///
+fn collect_cycles() {
+    let environments: Vec<Rc<Environment>> = ENVIRONMENTS.with_borrow_mut(|registry| {
+        registry.environments.retain(|env| env.strong_count() > 0);
+        registry
+            .environments
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect()
+    });
+
+    let index: HashMap<*const Environment, usize> = environments
+        .iter()
+        .enumerate()
+        .map(|(i, env)| (Rc::as_ptr(env), i))
+        .collect();
+
+    let mut internal = vec![0; environments.len()];
+    for env in environments.iter() {
+        let complete = visit_environment(env, true, &mut |to| {
+            if let Some(&i) = index.get(&Rc::as_ptr(to)) {
+                internal[i] += 1;
+            }
+        });
+
+        // bindings that are being modified can't be traced
+        if !complete {
+            return;
+        }
+    }
+
+    // the collector holds one reference to each environment itself
+    let mut reachable: Vec<bool> = environments
+        .iter()
+        .zip(internal.iter())
+        .map(|(env, &n)| Rc::strong_count(env) - 1 > n)
+        .collect();
+
+    let mut stack: Vec<usize> = (0..environments.len()).filter(|&i| reachable[i]).collect();
+    while let Some(i) = stack.pop() {
+        visit_environment(&environments[i], false, &mut |to| {
+            if let Some(&j) = index.get(&Rc::as_ptr(to)) {
+                if !reachable[j] {
+                    reachable[j] = true;
+                    stack.push(j);
+                }
+            }
+        });
+    }
+
+    // bindings are dropped only once no environment is borrowed
+    let garbage: Vec<Bindings> = environments
+        .iter()
+        .zip(reachable.iter())
+        .filter(|(_, &reachable)| !reachable)
+        .filter_map(|(env, _)| {
+            env.values
+                .try_borrow_mut()
+                .ok()
+                .map(|mut v| std::mem::take(&mut *v))
+        })
+        .collect();
+
+    let reclaimed = garbage.len();
+    drop(garbage);
+    drop(environments);
+
+    ENVIRONMENTS.with_borrow_mut(|registry| {
+        registry.environments.retain(|env| env.strong_count() > 0);
+        registry.threshold = 2 * registry.environments.len();
+        registry.reclaimed += reclaimed;
+    });
+}
+
+/// Visit the environments referenced by an environment
+///
+/// When `owned`, only references held exclusively by the environment are
+/// visited. Returns `false` if the environment's bindings are borrowed
+/// mutably and couldn't be visited.
+///
+fn visit_environment<F>(env: &Environment, owned: bool, f: &mut F) -> bool
+where
+    F: FnMut(&Rc<Environment>),
+{
+    if let Some(parent) = &env.parent {
+        f(parent);
+    }
+
+    let Ok(values) = env.values.try_borrow() else {
+        return false;
+    };
+
+    for value in values.values() {
+        visit(value, owned, f);
+    }
+
+    true
+}
+
+fn visit<F>(obj: &Obj, owned: bool, f: &mut F)
+where
+    F: FnMut(&Rc<Environment>),
+{
+    match obj {
+        Obj::Environment(env) => f(env),
+        Obj::Function(_, _, env, attrs, _) => {
+            f(env);
+            visit_attributes(attrs.as_ref(), owned, f);
+        }
+        Obj::Promise(value, _, env) => {
+            f(env);
+            if let Some(value) = value {
+                visit(value, owned, f);
+            }
+        }
+        Obj::List(Rep::Subset(values, _, _, attrs)) => {
+            let shared = Rc::strong_count(&values.0) > 1 || Rc::strong_count(&values.borrow()) > 1;
+            if !(owned && shared) {
+                for value in values.borrow().as_slice() {
+                    visit(value, owned, f);
+                }
+            }
+            visit_attributes(attrs.as_ref(), owned, f);
+        }
+        Obj::List(_) => (),
+        Obj::Vector(v) => visit_attributes(vector_attributes(v), owned, f),
+        Obj::Null | Obj::Expr(_) => (),
+    }
+}
+
+fn visit_attributes<F>(attrs: Option<&Attributes>, owned: bool, f: &mut F)
+where
+    F: FnMut(&Rc<Environment>),
+{
+    match attrs {
+        Some(attrs) if !(owned && attrs.is_shared()) => {
+            for (_, value) in attrs.iter() {
+                visit(value, owned, f);
+            }
+        }
+        _ => (),
+    }
+}
+
+fn vector_attributes(v: &Vector) -> Option<&Attributes> {
+    fn attributes<T: Element>(rep: &Rep<T>) -> Option<&Attributes> {
+        match rep {
+            Rep::Subset(.., attrs) | Rep::Seq(_, attrs) | Rep::Lazy(_, attrs) => attrs.as_ref(),
+        }
+    }
+
+    match v {
+        Vector::Double(rep) => attributes(rep),
+        Vector::Integer(rep) => attributes(rep),
+        Vector::Logical(rep) => attributes(rep),
+        Vector::Complex(rep) => attributes(rep),
+        Vector::Raw(rep) => attributes(rep),
+        Vector::Character(rep) => attributes(rep),
+    }
+}
+
+/// The approximate size of an object in bytes
+///
+/// Includes the data of vectors and lists, even when it is shared with
+/// other objects, as well as their names and attributes. Environments are
+/// counted without the values bound within them.
+///
+pub fn object_size(obj: &Obj) -> usize {
+    size_of::<Obj>()
+        + match obj {
+            Obj::Null => 0,
+            Obj::Vector(v) => vector_size(v),
+            Obj::List(rep) => rep_size(rep, |values| {
+                values.as_slice().iter().map(object_size).sum::<usize>()
+            }),
+            Obj::Expr(expr) => expr_size(expr),
+            Obj::Promise(value, expr, _) => {
+                expr_size(expr) + value.as_ref().map_or(0, |value| object_size(value))
+            }
+            Obj::Function(formals, body, _, attrs, _) => {
+                exprlist_size(formals) + expr_size(body) + attributes_size(attrs.as_ref())
+            }
+            Obj::Environment(_) => size_of::<Environment>(),
+        }
+}
+
+fn vector_size(v: &Vector) -> usize {
+    fn dense<T: Element>(values: &Values<T>) -> usize {
+        let mask = if values.has_na() {
+            values.len().div_ceil(64) * 8
+        } else {
+            0
+        };
+        values.len() * size_of::<T::Dense>() + mask
+    }
+
+    match v {
+        Vector::Double(rep) => rep_size(rep, dense),
+        Vector::Integer(rep) => rep_size(rep, dense),
+        Vector::Logical(rep) => rep_size(rep, dense),
+        Vector::Complex(rep) => rep_size(rep, dense),
+        Vector::Raw(rep) => rep_size(rep, dense),
+        Vector::Character(rep) => {
+            rep_size(rep, |values| dense(values) + strings_size(values.dense()))
+        }
+    }
+}
+
+/// The size of a vector's values, names and attributes
+fn rep_size<T, F>(rep: &Rep<T>, values_size: F) -> usize
+where
+    T: Element,
+    F: Fn(&Values<T>) -> usize,
+{
+    match rep {
+        Rep::Subset(values, _, names, attrs) => {
+            let names = names.as_ref().map_or(0, |naming| {
+                let names = naming.names.borrow();
+                names.len() * size_of::<Character>()
+                    + strings_size(names.iter().filter_map(|name| match name {
+                        OptionNA::Some(name) => Some(name),
+                        OptionNA::NA => None,
+                    }))
+            });
+
+            values_size(&values.borrow()) + names + attributes_size(attrs.as_ref())
+        }
+        Rep::Seq(_, attrs) | Rep::Lazy(_, attrs) => attributes_size(attrs.as_ref()),
+    }
+}
+
+/// The size of pooled strings, counting each distinct string once
+fn strings_size<'a>(strings: impl IntoIterator<Item = &'a Str>) -> usize {
+    let mut seen = HashSet::new();
+    strings
+        .into_iter()
+        .filter(|s| seen.insert(s.as_ptr()))
+        .map(|s| s.len())
+        .sum()
+}
+
+fn attributes_size(attrs: Option<&Attributes>) -> usize {
+    attrs.map_or(0, |attrs| {
+        attrs
+            .iter()
+            .map(|(name, value)| name.len() + object_size(value))
+            .sum()
+    })
+}
+
+fn expr_size(expr: &Expr) -> usize {
+    size_of::<Expr>()
+        + match expr {
+            Expr::String(s) => s.len(),
+            Expr::List(args) => exprlist_size(args),
+            Expr::Function(formals, body) => exprlist_size(formals) + expr_size(body),
+            Expr::Call(what, args) => expr_size(what) + exprlist_size(args),
+            _ => 0,
+        }
+}
+
+fn exprlist_size(exprs: &ExprList) -> usize {
+    exprs.keys.len() * size_of::<Option<Symbol>>()
+        + exprs.values.iter().map(expr_size).sum::<usize>()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{r, r_expect};
+
+    #[test]
+    fn cycles_are_collected() {
+        let parent = Environment::new(None);
+        let env = Environment::new(Some(parent.clone()));
+        let weak = Rc::downgrade(&env);
+
+        env.insert(Symbol::new("self"), Obj::Environment(env.clone()));
+        drop(env);
+        assert!(weak.upgrade().is_some());
+
+        collect();
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn referenced_environments_are_kept() {
+        let env = Environment::new(None);
+        env.insert(Symbol::new("self"), Obj::Environment(env.clone()));
+        collect();
+        assert_eq!(env.len(), 1);
+    }
+
+    #[test]
+    fn closures_are_collected() {
+        r_expect! {{"
+            f <- function() { g <- function() 1; g }
+            for (i in 1:10) f()
+            gc()[['reclaimed']] >= 10
+        "}}
+    }
+
+    #[test]
+    fn object_sizes() {
+        assert_eq!(r!(object.size(c(1, 2, 3)) - object.size(c(1, 2))), r!(8));
+        r_expect! {{r#"
+            object.size(c("a", "b")) > object.size(c("a", "a"))
+        "#}}
+    }
+}
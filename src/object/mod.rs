@@ -4,6 +4,9 @@ pub use core::*;
 mod ast;
 pub use ast::*;
 
+mod symbol;
+pub use symbol::*;
+
 mod environment;
 pub use environment::*;
 
@@ -15,3 +18,18 @@ pub use list::*;
 
 mod cow;
 pub use cow::*;
+
+mod attributes;
+pub use attributes::*;
+
+mod dims;
+pub use dims::*;
+
+mod dataframe;
+pub use dataframe::*;
+
+mod memory;
+pub use memory::*;
+
+mod namespace;
+pub use namespace::*;
@@ -0,0 +1,114 @@
+use hashbrown::{HashMap, HashSet};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::error::Error;
+use crate::lang::EvalResult;
+
+use super::{Environment, Symbol};
+
+thread_local! {
+    static NAMESPACES: RefCell<HashMap<Symbol, Rc<Namespace>>> = Default::default();
+}
+
+/// Namespaces
+///
+/// A namespace is an environment holding the bindings of a package, along
+/// with the names of the bindings it exports. Exported bindings are accessed
+/// as `pkg::name`, while `pkg:::name` also reaches internal bindings.
+/// Namespaces are registered by name, starting with the `base` namespace of
+/// builtins, such that `base::sum` is found even when `sum` is shadowed.
+///
+#[derive(Debug)]
+pub struct Namespace {
+    pub name: Symbol,
+    pub env: Rc<Environment>,
+    pub exports: HashSet<Symbol>,
+}
+
+impl Namespace {
+    pub fn new<I>(name: &str, env: Rc<Environment>, exports: I) -> Namespace
+    where
+        I: IntoIterator<Item = Symbol>,
+    {
+        Namespace {
+            name: Symbol::new(name),
+            env,
+            exports: exports.into_iter().collect(),
+        }
+    }
+
+    /// Register the namespace, replacing any namespace of the same name
+    pub fn register(self) -> Rc<Namespace> {
+        let namespace = Rc::new(self);
+        NAMESPACES.with_borrow_mut(|namespaces| {
+            namespaces.insert(namespace.name, namespace.clone());
+        });
+        namespace
+    }
+
+    /// Find a registered namespace by name
+    pub fn find(name: Symbol) -> Result<Rc<Namespace>, Error> {
+        NAMESPACES
+            .with_borrow(|namespaces| namespaces.get(&name).cloned())
+            .ok_or_else(|| Error::NamespaceNotFound(name.to_string()))
+    }
+
+    /// Get an exported binding, as accessed by `pkg::name`
+    pub fn get_exported(&self, name: Symbol) -> EvalResult {
+        if !self.exports.contains(&name) {
+            let namespace = self.name.to_string();
+            return Error::NotExported(name.to_string(), namespace).into();
+        }
+
+        self.get_internal(name)
+    }
+
+    /// Get any binding of the namespace, as accessed by `pkg:::name`
+    pub fn get_internal(&self, name: Symbol) -> EvalResult {
+        if self.env.values.borrow().get(&name).is_none() {
+            return Error::VariableNotFound(name.to_string()).into();
+        }
+
+        self.env.get(name)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::object::Obj;
+    use crate::{r, r_expect};
+
+    #[test]
+    fn base_functions_are_exported() {
+        r_expect! {{"
+            sum <- function(...) 0
+            base::sum(1, 2) == 3
+        "}}
+    }
+
+    #[test]
+    fn internal_bindings_are_not_exported() {
+        let env = Environment::new(None);
+        env.insert(Symbol::new("api"), Obj::Null);
+        env.insert(Symbol::new("helper"), Obj::Null);
+        let namespace = Namespace::new("pkg", env, [Symbol::new("api")]);
+
+        assert!(namespace.get_exported(Symbol::new("api")).is_ok());
+        assert!(namespace.get_exported(Symbol::new("helper")).is_err());
+        assert!(namespace.get_internal(Symbol::new("helper")).is_ok());
+        assert!(namespace.get_internal(Symbol::new("missing")).is_err());
+    }
+
+    #[test]
+    fn namespaces_are_registered() {
+        let env = Environment::new(None);
+        env.insert(Symbol::new("helper"), Obj::Null);
+        Namespace::new("registered", env, []).register();
+
+        assert_eq!(r!(registered:::helper), r!(NULL));
+        assert!(r!(registered::helper).is_err());
+        assert!(r!(unregistered::helper).is_err());
+    }
+}
@@ -1,6 +1,9 @@
+use hashbrown::HashMap;
 use std::str::FromStr;
 
+use super::complex::Complex64;
 use super::OptionNA;
+use super::Str;
 
 pub trait AtomicMode {
     fn is_double() -> bool {
@@ -12,6 +15,12 @@ pub trait AtomicMode {
     fn is_logical() -> bool {
         false
     }
+    fn is_complex() -> bool {
+        false
+    }
+    fn is_raw() -> bool {
+        false
+    }
     fn is_character() -> bool {
         false
     }
@@ -77,9 +86,9 @@ impl CoercibleInto<OptionNA<bool>> for bool {
     }
 }
 
-impl CoercibleInto<OptionNA<String>> for String {
+impl CoercibleInto<OptionNA<Str>> for Str {
     #[inline]
-    fn coerce_into(self) -> OptionNA<String> {
+    fn coerce_into(self) -> OptionNA<Str> {
         OptionNA::Some(self)
     }
 }
@@ -98,31 +107,31 @@ impl CoercibleInto<f64> for i32 {
     }
 }
 
-impl CoercibleInto<String> for String {
+impl CoercibleInto<Str> for Str {
     #[inline]
-    fn coerce_into(self) -> String {
+    fn coerce_into(self) -> Str {
         self
     }
 }
 
-impl CoercibleInto<String> for bool {
+impl CoercibleInto<Str> for bool {
     #[inline]
-    fn coerce_into(self) -> String {
-        self.to_string()
+    fn coerce_into(self) -> Str {
+        self.to_string().into()
     }
 }
 
-impl CoercibleInto<String> for i32 {
+impl CoercibleInto<Str> for i32 {
     #[inline]
-    fn coerce_into(self) -> String {
-        self.to_string()
+    fn coerce_into(self) -> Str {
+        self.to_string().into()
     }
 }
 
-impl CoercibleInto<String> for f64 {
+impl CoercibleInto<Str> for f64 {
     #[inline]
-    fn coerce_into(self) -> String {
-        self.to_string()
+    fn coerce_into(self) -> Str {
+        self.to_string().into()
     }
 }
 
@@ -150,7 +159,10 @@ impl CoercibleInto<OptionNA<i32>> for OptionNA<i32> {
 impl CoercibleInto<OptionNA<i32>> for OptionNA<f64> {
     #[inline]
     fn coerce_into(self) -> OptionNA<i32> {
-        self.map(|i| i as i32)
+        match self {
+            OptionNA::Some(x) => truncate_to_integer(x).into(),
+            OptionNA::NA => OptionNA::NA,
+        }
     }
 }
 
@@ -203,7 +215,7 @@ impl CoercibleInto<OptionNA<bool>> for OptionNA<i32> {
     }
 }
 
-impl<T> CoercibleInto<OptionNA<T>> for OptionNA<String>
+impl<T> CoercibleInto<OptionNA<T>> for OptionNA<Str>
 where
     T: FromStr,
 {
@@ -217,24 +229,262 @@ where
     }
 }
 
-impl CoercibleInto<OptionNA<String>> for OptionNA<bool> {
-    fn coerce_into(self) -> OptionNA<String> {
-        self.map(|i| format!("{}", i))
+impl CoercibleInto<OptionNA<Str>> for OptionNA<bool> {
+    fn coerce_into(self) -> OptionNA<Str> {
+        self.map(|i| format!("{}", i).into())
+    }
+}
+
+impl CoercibleInto<OptionNA<Str>> for OptionNA<i32> {
+    fn coerce_into(self) -> OptionNA<Str> {
+        self.map(|i| format!("{}", i).into())
+    }
+}
+
+impl CoercibleInto<OptionNA<Str>> for OptionNA<f64> {
+    fn coerce_into(self) -> OptionNA<Str> {
+        self.map(|i| format!("{}", i).into())
+    }
+}
+
+impl CoercibleInto<Complex64> for Complex64 {
+    #[inline]
+    fn coerce_into(self) -> Complex64 {
+        self
+    }
+}
+
+impl CoercibleInto<Complex64> for bool {
+    #[inline]
+    fn coerce_into(self) -> Complex64 {
+        Complex64::from(self as i32 as f64)
+    }
+}
+
+impl CoercibleInto<Complex64> for i32 {
+    #[inline]
+    fn coerce_into(self) -> Complex64 {
+        Complex64::from(self as f64)
+    }
+}
+
+impl CoercibleInto<Complex64> for f64 {
+    #[inline]
+    fn coerce_into(self) -> Complex64 {
+        Complex64::from(self)
+    }
+}
+
+impl CoercibleInto<Str> for Complex64 {
+    #[inline]
+    fn coerce_into(self) -> Str {
+        self.to_string().into()
+    }
+}
+
+impl CoercibleInto<OptionNA<Complex64>> for Complex64 {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<Complex64> {
+        OptionNA::Some(self)
+    }
+}
+
+impl CoercibleInto<OptionNA<Complex64>> for OptionNA<Complex64> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<Complex64> {
+        self
+    }
+}
+
+impl CoercibleInto<OptionNA<Complex64>> for OptionNA<bool> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<Complex64> {
+        self.map(|i| i.coerce_into())
+    }
+}
+
+impl CoercibleInto<OptionNA<Complex64>> for OptionNA<i32> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<Complex64> {
+        self.map(|i| i.coerce_into())
+    }
+}
+
+impl CoercibleInto<OptionNA<Complex64>> for OptionNA<f64> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<Complex64> {
+        self.map(|i| i.coerce_into())
+    }
+}
+
+// coercing complex values into real values discards their imaginary part
+
+impl CoercibleInto<OptionNA<f64>> for OptionNA<Complex64> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<f64> {
+        self.map(|i| i.re)
+    }
+}
+
+impl CoercibleInto<OptionNA<i32>> for OptionNA<Complex64> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<i32> {
+        CoercibleInto::<OptionNA<i32>>::coerce_into(self.map(|i| i.re))
+    }
+}
+
+impl CoercibleInto<OptionNA<bool>> for OptionNA<Complex64> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<bool> {
+        self.map(|i| i != Complex64::default())
+    }
+}
+
+impl CoercibleInto<OptionNA<Str>> for OptionNA<Complex64> {
+    fn coerce_into(self) -> OptionNA<Str> {
+        self.map(|i| format!("{}", i).into())
+    }
+}
+
+impl CoercibleInto<u8> for u8 {
+    #[inline]
+    fn coerce_into(self) -> u8 {
+        self
+    }
+}
+
+impl CoercibleInto<OptionNA<u8>> for u8 {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<u8> {
+        OptionNA::Some(self)
+    }
+}
+
+impl CoercibleInto<OptionNA<u8>> for OptionNA<u8> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<u8> {
+        self
+    }
+}
+
+// raw values are never missing; missing and out of range values coerce to 0
+
+impl CoercibleInto<OptionNA<u8>> for OptionNA<i32> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<u8> {
+        match self {
+            OptionNA::Some(i) => OptionNA::Some(u8::try_from(i).unwrap_or_default()),
+            OptionNA::NA => OptionNA::Some(0),
+        }
+    }
+}
+
+impl CoercibleInto<OptionNA<u8>> for OptionNA<f64> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<u8> {
+        match self {
+            OptionNA::Some(i) if (0.0..256.0).contains(&i) => OptionNA::Some(i as u8),
+            _ => OptionNA::Some(0),
+        }
+    }
+}
+
+impl CoercibleInto<OptionNA<u8>> for OptionNA<bool> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<u8> {
+        OptionNA::Some(matches!(self, OptionNA::Some(true)) as u8)
+    }
+}
+
+impl CoercibleInto<OptionNA<u8>> for OptionNA<Complex64> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<u8> {
+        CoercibleInto::<OptionNA<u8>>::coerce_into(self.map(|i| i.re))
     }
 }
 
-impl CoercibleInto<OptionNA<String>> for OptionNA<i32> {
-    fn coerce_into(self) -> OptionNA<String> {
-        self.map(|i| format!("{}", i))
+impl CoercibleInto<OptionNA<i32>> for OptionNA<u8> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<i32> {
+        self.map(|i| i as i32)
     }
 }
 
-impl CoercibleInto<OptionNA<String>> for OptionNA<f64> {
-    fn coerce_into(self) -> OptionNA<String> {
-        self.map(|i| format!("{}", i))
+impl CoercibleInto<OptionNA<f64>> for OptionNA<u8> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<f64> {
+        self.map(|i| i as f64)
     }
 }
 
+impl CoercibleInto<OptionNA<bool>> for OptionNA<u8> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<bool> {
+        self.map(|i| i != 0)
+    }
+}
+
+impl CoercibleInto<OptionNA<Complex64>> for OptionNA<u8> {
+    #[inline]
+    fn coerce_into(self) -> OptionNA<Complex64> {
+        self.map(|i| Complex64::from(i as f64))
+    }
+}
+
+impl CoercibleInto<OptionNA<Str>> for OptionNA<u8> {
+    fn coerce_into(self) -> OptionNA<Str> {
+        self.map(|i| format!("{:02x}", i).into())
+    }
+}
+
+/// Truncate a double to an integer, or `None` if it is `NaN` or outside
+/// the range of integers
+#[inline]
+pub fn truncate_to_integer(x: f64) -> Option<i32> {
+    let x = x.trunc();
+    (x >= i32::MIN as f64 && x <= i32::MAX as f64).then_some(x as i32)
+}
+
+/// Coerce factor codes into their levels
+///
+/// Factors store `integer` codes indexing into a `character` vector of
+/// levels. Codes which are missing or out of range produce `NA`.
+pub fn factor_labels(codes: &[OptionNA<i32>], levels: &[OptionNA<Str>]) -> Vec<OptionNA<Str>> {
+    codes
+        .iter()
+        .map(|code| match code {
+            OptionNA::Some(i) if *i >= 1 => {
+                levels.get(*i as usize - 1).cloned().unwrap_or_default()
+            }
+            _ => OptionNA::NA,
+        })
+        .collect()
+}
+
+/// Coerce values into factor codes, given the levels of a factor
+///
+/// Values which are not one of the levels produce `NA`.
+pub fn factor_codes(values: &[OptionNA<Str>], levels: &[OptionNA<Str>]) -> Vec<OptionNA<i32>> {
+    let positions: HashMap<&Str, i32> = levels
+        .iter()
+        .enumerate()
+        .filter_map(|(i, level)| match level {
+            OptionNA::Some(level) => Some((level, i as i32 + 1)),
+            OptionNA::NA => None,
+        })
+        .collect();
+
+    values
+        .iter()
+        .map(|value| match value {
+            OptionNA::Some(value) => positions
+                .get(value)
+                .map_or(OptionNA::NA, |&i| OptionNA::Some(i)),
+            OptionNA::NA => OptionNA::NA,
+        })
+        .collect()
+}
+
 pub trait MinimallyNumeric {
     type As;
 }
@@ -255,6 +505,9 @@ impl MinimallyNumeric for i32 {
 impl MinimallyNumeric for f64 {
     type As = f64;
 }
+impl MinimallyNumeric for Complex64 {
+    type As = Complex64;
+}
 impl<T, U> MinimallyNumeric for OptionNA<T>
 where
     T: MinimallyNumeric<As = U>,
@@ -435,14 +688,24 @@ register!(CommonNum, f64 => f64);
 register!(CommonNum, (bool, i32) => i32);
 register!(CommonNum, (bool, f64) => f64);
 register!(CommonNum, (i32 , f64) => f64);
+register!(CommonNum, Complex64 => Complex64);
+register!(CommonNum, (bool, Complex64) => Complex64);
+register!(CommonNum, (i32, Complex64) => Complex64);
+register!(CommonNum, (f64, Complex64) => Complex64);
 
 register!(CommonCmp, bool => bool);
 register!(CommonCmp, i32 => i32);
 register!(CommonCmp, f64 => f64);
-register!(CommonCmp, String => String);
+register!(CommonCmp, Str => Str);
 register!(CommonCmp, (bool, i32) => i32);
 register!(CommonCmp, (bool, f64) => f64);
 register!(CommonCmp, (i32, f64) => f64);
-register!(CommonCmp, (String, bool) => String);
-register!(CommonCmp, (String, i32) => String);
-register!(CommonCmp, (String, f64) => String);
+register!(CommonCmp, (Str, bool) => Str);
+register!(CommonCmp, (Str, i32) => Str);
+register!(CommonCmp, (Str, f64) => Str);
+register!(CommonCmp, u8 => u8);
+register!(CommonCmp, Complex64 => Complex64);
+register!(CommonCmp, (bool, Complex64) => Complex64);
+register!(CommonCmp, (i32, Complex64) => Complex64);
+register!(CommonCmp, (f64, Complex64) => Complex64);
+register!(CommonCmp, (Str, Complex64) => Str);
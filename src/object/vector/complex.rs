@@ -0,0 +1,195 @@
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A Complex Number
+///
+/// The scalar type of `complex` vectors, storing the real and imaginary
+/// parts as double-precision floats.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex64 {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex64 {
+    pub fn new(re: f64, im: f64) -> Self {
+        Complex64 { re, im }
+    }
+
+    pub fn from_polar(modulus: f64, argument: f64) -> Self {
+        Complex64::new(modulus * argument.cos(), modulus * argument.sin())
+    }
+
+    /// The modulus, or absolute value
+    pub fn modulus(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    /// The argument, or angle from the positive real axis
+    pub fn argument(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn conj(self) -> Self {
+        Complex64::new(self.re, -self.im)
+    }
+
+    /// The principal square root
+    pub fn sqrt(self) -> Self {
+        if self.re == 0.0 && self.im == 0.0 {
+            return Complex64::default();
+        }
+
+        // computed without trigonometry, such that the square roots of real
+        // numbers have exactly zero real or imaginary parts
+        let t = ((self.modulus() + self.re.abs()) / 2.0).sqrt();
+        if self.re >= 0.0 {
+            Complex64::new(t, self.im / (2.0 * t))
+        } else {
+            Complex64::new(self.im.abs() / (2.0 * t), t.copysign(self.im))
+        }
+    }
+
+    /// The principal natural logarithm
+    pub fn ln(self) -> Self {
+        Complex64::new(self.modulus().ln(), self.argument())
+    }
+
+    pub fn exp(self) -> Self {
+        Complex64::from_polar(self.re.exp(), self.im)
+    }
+}
+
+impl From<f64> for Complex64 {
+    fn from(re: f64) -> Self {
+        Complex64::new(re, 0.0)
+    }
+}
+
+impl std::ops::Add for Complex64 {
+    type Output = Complex64;
+    fn add(self, rhs: Self) -> Self::Output {
+        Complex64::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl std::ops::Sub for Complex64 {
+    type Output = Complex64;
+    fn sub(self, rhs: Self) -> Self::Output {
+        Complex64::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl std::ops::Mul for Complex64 {
+    type Output = Complex64;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Complex64::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl std::ops::Div for Complex64 {
+    type Output = Complex64;
+    fn div(self, rhs: Self) -> Self::Output {
+        let denom = rhs.re * rhs.re + rhs.im * rhs.im;
+        Complex64::new(
+            (self.re * rhs.re + self.im * rhs.im) / denom,
+            (self.im * rhs.re - self.re * rhs.im) / denom,
+        )
+    }
+}
+
+impl std::ops::Neg for Complex64 {
+    type Output = Complex64;
+    fn neg(self) -> Self::Output {
+        Complex64::new(-self.re, -self.im)
+    }
+}
+
+// Complex numbers have no natural ordering. Ordering the real parts before
+// the imaginary parts is only used to determine equality.
+impl PartialOrd for Complex64 {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        match self.re.partial_cmp(&other.re) {
+            Some(std::cmp::Ordering::Equal) => self.im.partial_cmp(&other.im),
+            ordering => ordering,
+        }
+    }
+}
+
+impl Display for Complex64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // avoid displaying negative zeros, as in `-3i`
+        let re = if self.re == 0.0 { 0.0 } else { self.re };
+        let sign = if self.im.is_sign_negative() { "-" } else { "+" };
+        write!(f, "{}{}{}i", re, sign, self.im.abs())
+    }
+}
+
+impl FromStr for Complex64 {
+    type Err = ();
+
+    /// Parse complex numbers of the form `a`, `bi` or `a+bi`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some(body) = s.strip_suffix('i') else {
+            return s.parse::<f64>().map(Complex64::from).map_err(|_| ());
+        };
+
+        // split at the sign preceding the imaginary part, if any
+        let split = body
+            .char_indices()
+            .skip(1)
+            .filter(|&(i, c)| (c == '+' || c == '-') && !body[..i].ends_with(['e', 'E']))
+            .last()
+            .map(|(i, _)| i);
+
+        let (re, im) = match split {
+            Some(i) => (body[..i].parse().map_err(|_| ())?, &body[i..]),
+            None => (0.0, body),
+        };
+
+        let im = match im {
+            "" | "+" => 1.0,
+            "-" => -1.0,
+            im => im.parse().map_err(|_| ())?,
+        };
+
+        Ok(Complex64::new(re, im))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Complex64;
+
+    #[test]
+    fn complex_arithmetic() {
+        let x = Complex64::new(1.0, 2.0);
+        let y = Complex64::new(3.0, -1.0);
+        assert_eq!(x + y, Complex64::new(4.0, 1.0));
+        assert_eq!(x * y, Complex64::new(5.0, 5.0));
+        assert_eq!((x * y) / y, x);
+        assert_eq!(Complex64::from(-4.0).sqrt(), Complex64::new(0.0, 2.0));
+        assert_eq!(Complex64::new(3.0, 4.0).sqrt(), Complex64::new(2.0, 1.0));
+    }
+
+    #[test]
+    fn complex_parsing() {
+        assert_eq!("1+2i".parse(), Ok(Complex64::new(1.0, 2.0)));
+        assert_eq!("-3i".parse(), Ok(Complex64::new(0.0, -3.0)));
+        assert_eq!("1e-2-1i".parse(), Ok(Complex64::new(0.01, -1.0)));
+        assert_eq!("4".parse(), Ok(Complex64::new(4.0, 0.0)));
+        assert_eq!("a+bi".parse::<Complex64>(), Err(()));
+    }
+
+    #[test]
+    fn complex_display() {
+        assert_eq!(Complex64::new(1.0, -2.0).to_string(), "1-2i");
+        assert_eq!(Complex64::new(0.0, 1.5).to_string(), "0+1.5i");
+        assert_eq!(Complex64::new(-0.0, -3.0).to_string(), "0-3i");
+    }
+}
@@ -4,14 +4,18 @@ use std::fmt::Display;
 use crate::error::Error;
 use crate::lang::EvalResult;
 use crate::lang::Signal;
+use crate::object::Attributes;
 use crate::object::CowObj;
 use crate::object::Obj;
+use crate::parser::escape;
 
-use super::coercion::CoercibleInto;
+use super::coercion::{factor_labels, CoercibleInto};
+use super::complex::Complex64;
 use super::rep::IterableValues;
-use super::rep::Rep;
+use super::rep::{try_binary_raw_op, Rep};
 use super::subset::Subset;
 use super::types::*;
+use super::values::Values;
 
 #[derive(Default, Clone, PartialEq, Eq)]
 pub enum OptionNA<T> {
@@ -56,9 +60,9 @@ pub enum Vector {
     Double(Rep<Double>),
     Integer(Rep<Integer>),
     Logical(Rep<Logical>),
+    Complex(Rep<Complex>),
     Character(Rep<Character>),
-    // Complex(Complex),
-    // Raw(Raw),
+    Raw(Rep<Raw>),
 }
 
 // TODO: Implement vector more like Rep<T>
@@ -71,6 +75,8 @@ impl Clone for Vector {
             Vector::Character(v) => Vector::Character(v.clone()),
             Vector::Integer(v) => Vector::Integer(v.clone()),
             Vector::Logical(v) => Vector::Logical(v.clone()),
+            Vector::Complex(v) => Vector::Complex(v.clone()),
+            Vector::Raw(v) => Vector::Raw(v.clone()),
         }
     }
 }
@@ -86,7 +92,9 @@ impl Vector {
             Double(x) => x.get(index).map(Double),
             Integer(x) => x.get(index).map(Integer),
             Logical(x) => x.get(index).map(Logical),
+            Complex(x) => x.get(index).map(Complex),
             Character(x) => x.get(index).map(Character),
+            Raw(x) => x.get(index).map(Raw),
         }
     }
 
@@ -105,6 +113,12 @@ impl Vector {
             Logical(x) => x
                 .set_subset(subset, value.try_into()?)
                 .map(|x| Logical(Rep::from(vec![x]))),
+            Complex(x) => x
+                .set_subset(subset, value.try_into()?)
+                .map(|x| Complex(Rep::from(vec![x]))),
+            Raw(x) => x
+                .set_subset(subset, value.try_into()?)
+                .map(|x| Raw(Rep::from(vec![x]))),
         }
     }
 
@@ -114,7 +128,9 @@ impl Vector {
             Double(x) => x.iter_names(),
             Integer(x) => x.iter_names(),
             Logical(x) => x.iter_names(),
+            Complex(x) => x.iter_names(),
             Character(x) => x.iter_names(),
+            Raw(x) => x.iter_names(),
         }
     }
 
@@ -124,7 +140,9 @@ impl Vector {
             Double(x) => x.is_named(),
             Integer(x) => x.is_named(),
             Logical(x) => x.is_named(),
+            Complex(x) => x.is_named(),
             Character(x) => x.is_named(),
+            Raw(x) => x.is_named(),
         }
     }
 
@@ -134,7 +152,9 @@ impl Vector {
             Double(x) => x.names(),
             Integer(x) => x.names(),
             Logical(x) => x.names(),
+            Complex(x) => x.names(),
             Character(x) => x.names(),
+            Raw(x) => x.names(),
         }
     }
 
@@ -143,11 +163,87 @@ impl Vector {
         match self {
             Character(x) => Character(x.set_names(names)),
             Logical(x) => Logical(x.set_names(names)),
+            Complex(x) => Complex(x.set_names(names)),
+            Raw(x) => Raw(x.set_names(names)),
             Integer(x) => Integer(x.set_names(names)),
             Double(x) => Double(x.set_names(names)),
         }
     }
 
+    pub fn attributes(&self) -> Option<Attributes> {
+        use Vector::*;
+        match self {
+            Double(x) => x.attributes(),
+            Integer(x) => x.attributes(),
+            Logical(x) => x.attributes(),
+            Complex(x) => x.attributes(),
+            Character(x) => x.attributes(),
+            Raw(x) => x.attributes(),
+        }
+    }
+
+    pub fn with_attributes(self, attrs: Option<Attributes>) -> Self {
+        use Vector::*;
+        match self {
+            Double(x) => Double(x.with_attributes(attrs)),
+            Integer(x) => Integer(x.with_attributes(attrs)),
+            Logical(x) => Logical(x.with_attributes(attrs)),
+            Complex(x) => Complex(x.with_attributes(attrs)),
+            Character(x) => Character(x.with_attributes(attrs)),
+            Raw(x) => Raw(x.with_attributes(attrs)),
+        }
+    }
+
+    /// The levels of a factor, or `None` if the vector is not a factor
+    ///
+    /// Factors are `integer` vectors with a `levels` attribute and a
+    /// `class` attribute including `"factor"`.
+    pub fn factor_levels(&self) -> Option<Vec<Character>> {
+        let Vector::Integer(_) = self else {
+            return None;
+        };
+
+        let attrs = self.attributes()?;
+        let is_factor = match attrs.get("class")? {
+            Obj::Vector(Vector::Character(classes)) => classes
+                .iter_values()
+                .any(|class| matches!(class, OptionNA::Some(c) if c == "factor")),
+            _ => false,
+        };
+
+        match attrs.get("levels")? {
+            Obj::Vector(Vector::Character(levels)) if is_factor => {
+                Some(levels.iter_values().collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// Reapply the attributes of a factor to a subset of its codes
+    pub fn with_factor_attributes(self, factor: &Vector) -> Self {
+        let attrs = factor.attributes().map(|attrs| {
+            attrs
+                .iter()
+                .filter(|(name, _)| name == "levels" || name == "class")
+                .cloned()
+                .collect::<Attributes>()
+        });
+
+        self.with_attributes(attrs)
+    }
+
+    pub fn without_names(&self) -> Self {
+        use Vector::*;
+        match self {
+            Double(x) => Double(x.without_names()),
+            Integer(x) => Integer(x.without_names()),
+            Logical(x) => Logical(x.without_names()),
+            Complex(x) => Complex(x.without_names()),
+            Character(x) => Character(x.without_names()),
+            Raw(x) => Raw(x.without_names()),
+        }
+    }
+
     pub fn try_get(&self, index: Obj) -> EvalResult {
         let err =
             Error::Other("Vector index cannot be coerced into a valid indexing type.".to_string());
@@ -161,9 +257,15 @@ impl Vector {
             (Vector::Logical(v), Obj::Vector(i)) => {
                 Ok(Obj::Vector(Vector::from(v.subset(i.try_into()?))))
             }
+            (Vector::Complex(v), Obj::Vector(i)) => {
+                Ok(Obj::Vector(Vector::from(v.subset(i.try_into()?))))
+            }
             (Vector::Character(v), Obj::Vector(i)) => {
                 Ok(Obj::Vector(Vector::from(v.subset(i.try_into()?))))
             }
+            (Vector::Raw(v), Obj::Vector(i)) => {
+                Ok(Obj::Vector(Vector::from(v.subset(i.try_into()?))))
+            }
             _ => Err(err.into()),
         }
     }
@@ -173,7 +275,9 @@ impl Vector {
             Vector::Double(x) => x.subset(subset).into(),
             Vector::Integer(x) => x.subset(subset).into(),
             Vector::Logical(x) => x.subset(subset).into(),
+            Vector::Complex(x) => x.subset(subset).into(),
             Vector::Character(x) => x.subset(subset).into(),
+            Vector::Raw(x) => x.subset(subset).into(),
         }
     }
 
@@ -190,22 +294,102 @@ impl Vector {
             (Vector::Logical(l), Obj::Vector(Vector::Logical(r))) => {
                 Ok(Obj::Vector(Vector::from(l.assign(r)?)))
             }
+            (Vector::Complex(l), Obj::Vector(Vector::Complex(r))) => {
+                Ok(Obj::Vector(Vector::from(l.assign(r)?)))
+            }
             (Vector::Character(l), Obj::Vector(Vector::Character(r))) => {
                 Ok(Obj::Vector(Vector::from(l.assign(r)?)))
             }
+            (Vector::Raw(l), Obj::Vector(Vector::Raw(r))) => {
+                Ok(Obj::Vector(Vector::from(l.assign(r)?)))
+            }
             _ => Err(err),
         }
     }
 
-    pub fn materialize(self) -> Self {
+    pub fn is_computed(&self) -> bool {
+        use Vector::*;
+        match self {
+            Double(x) => x.is_computed(),
+            Integer(x) => x.is_computed(),
+            Logical(x) => x.is_computed(),
+            Complex(x) => x.is_computed(),
+            Character(x) => x.is_computed(),
+            Raw(x) => x.is_computed(),
+        }
+    }
+
+    pub fn is_lazy(&self) -> bool {
+        use Vector::*;
+        match self {
+            Double(x) => x.is_lazy(),
+            Integer(x) => x.is_lazy(),
+            Logical(x) => x.is_lazy(),
+            Complex(x) => x.is_lazy(),
+            Character(x) => x.is_lazy(),
+            Raw(x) => x.is_lazy(),
+        }
+    }
+
+    pub fn is_unbounded(&self) -> bool {
+        use Vector::*;
+        match self {
+            Double(x) => x.is_unbounded(),
+            Integer(x) => x.is_unbounded(),
+            Logical(x) => x.is_unbounded(),
+            Complex(x) => x.is_unbounded(),
+            Character(x) => x.is_unbounded(),
+            Raw(x) => x.is_unbounded(),
+        }
+    }
+
+    pub fn materialize_computed(&mut self) -> Result<(), Signal> {
+        use Vector::*;
+        match self {
+            Double(x) => x.materialize_computed(),
+            Integer(x) => x.materialize_computed(),
+            Logical(x) => x.materialize_computed(),
+            Complex(x) => x.materialize_computed(),
+            Character(x) => x.materialize_computed(),
+            Raw(x) => x.materialize_computed(),
+        }
+    }
+
+    pub fn trace(&self) -> Option<usize> {
+        use Vector::*;
+        match self {
+            Double(x) => x.trace(),
+            Integer(x) => x.trace(),
+            Logical(x) => x.trace(),
+            Complex(x) => x.trace(),
+            Character(x) => x.trace(),
+            Raw(x) => x.trace(),
+        }
+    }
+
+    pub fn untrace(&self) {
+        use Vector::*;
         match self {
-            Vector::Double(x) => Vector::from(x.materialize()),
-            Vector::Integer(x) => Vector::from(x.materialize()),
-            Vector::Logical(x) => Vector::from(x.materialize()),
-            Vector::Character(x) => Vector::from(x.materialize()),
+            Double(x) => x.untrace(),
+            Integer(x) => x.untrace(),
+            Logical(x) => x.untrace(),
+            Complex(x) => x.untrace(),
+            Character(x) => x.untrace(),
+            Raw(x) => x.untrace(),
         }
     }
 
+    pub fn materialize(self) -> Result<Self, Signal> {
+        Ok(match self {
+            Vector::Double(x) => Vector::from(x.materialize()?),
+            Vector::Integer(x) => Vector::from(x.materialize()?),
+            Vector::Logical(x) => Vector::from(x.materialize()?),
+            Vector::Complex(x) => Vector::from(x.materialize()?),
+            Vector::Character(x) => Vector::from(x.materialize()?),
+            Vector::Raw(x) => Vector::from(x.materialize()?),
+        })
+    }
+
     pub fn vec_coerce<T, U>(v: &[OptionNA<T>]) -> Vec<OptionNA<U>>
     where
         T: CoercibleInto<U> + Clone,
@@ -219,7 +403,7 @@ impl Vector {
             .collect()
     }
 
-    pub fn vec_parse<U>(v: &[OptionNA<String>]) -> (bool, Vec<OptionNA<U>>)
+    pub fn vec_parse<U>(v: &[Character]) -> (bool, Vec<OptionNA<U>>)
     where
         U: std::str::FromStr,
     {
@@ -248,7 +432,9 @@ impl Vector {
             Double(v) => Integer(v.as_integer()),
             Integer(_) => self,
             Logical(v) => Integer(v.as_integer()),
+            Complex(v) => Integer(v.as_integer()),
             Character(v) => Integer(v.as_integer()),
+            Raw(v) => Integer(v.as_integer()),
         }
     }
 
@@ -258,7 +444,9 @@ impl Vector {
             Double(_) => self,
             Integer(v) => Double(v.as_double()),
             Logical(v) => Double(v.as_double()),
+            Complex(v) => Double(v.as_double()),
             Character(v) => Double(v.as_double()),
+            Raw(v) => Double(v.as_double()),
         }
     }
 
@@ -268,17 +456,53 @@ impl Vector {
             Double(v) => Logical(v.as_logical()),
             Integer(v) => Logical(v.as_logical()),
             Logical(_) => self,
+            Complex(v) => Logical(v.as_logical()),
             Character(v) => Logical(v.as_logical()),
+            Raw(v) => Logical(v.as_logical()),
         }
     }
 
     pub fn as_character(self) -> Vector {
         use Vector::*;
+
+        // factors are represented by their levels
+        if let (Some(levels), Integer(codes)) = (self.factor_levels(), &self) {
+            let codes: Vec<OptionNA<i32>> = codes.iter_values().collect();
+            return Vector::from(factor_labels(&codes, &levels));
+        }
+
         match self {
             Double(v) => Character(v.as_character()),
             Integer(v) => Character(v.as_character()),
             Logical(v) => Character(v.as_character()),
+            Complex(v) => Character(v.as_character()),
             Character(_) => self,
+            Raw(v) => Character(v.as_character()),
+        }
+    }
+
+    pub fn as_complex(self) -> Vector {
+        use Vector::*;
+        match self {
+            Double(v) => Complex(v.as_complex()),
+            Integer(v) => Complex(v.as_complex()),
+            Logical(v) => Complex(v.as_complex()),
+            Complex(_) => self,
+            Character(v) => Complex(v.as_complex()),
+            Raw(v) => Complex(v.as_complex()),
+        }
+    }
+
+    pub fn as_raw(self) -> Vector {
+        use Vector::*;
+        match self {
+            Double(v) => Raw(v.as_raw()),
+            Integer(v) => Raw(v.as_raw()),
+            Logical(v) => Raw(v.as_raw()),
+            Complex(v) => Raw(v.as_raw()),
+            // parsed as numbers, such that out of range values become 0
+            Character(v) => Raw(v.as_double().as_raw()),
+            Raw(_) => self,
         }
     }
 
@@ -288,6 +512,8 @@ impl Vector {
             Double(v) => v.len(),
             Integer(v) => v.len(),
             Logical(v) => v.len(),
+            Complex(v) => v.len(),
+            Raw(v) => v.len(),
             Character(v) => v.len(),
         }
     }
@@ -306,6 +532,8 @@ impl TryInto<bool> for Vector {
             Double(i) => i.try_into(),
             Integer(i) => i.try_into(),
             Logical(i) => i.try_into(),
+            Complex(i) => i.try_into(),
+            Raw(i) => i.try_into(),
             Character(i) => i.try_into(),
         }
     }
@@ -319,7 +547,8 @@ impl From<Vector> for Result<Vector, Signal> {
 
 impl From<CowObj<Vec<Character>>> for Vector {
     fn from(x: CowObj<Vec<Character>>) -> Self {
-        Vector::Character(x.into())
+        let values: Values<Character> = x.iter().collect();
+        Vector::Character(CowObj::from(values).into())
     }
 }
 
@@ -341,6 +570,18 @@ impl From<Rep<Logical>> for Vector {
     }
 }
 
+impl From<Rep<Complex>> for Vector {
+    fn from(x: Rep<Complex>) -> Self {
+        Vector::Complex(x)
+    }
+}
+
+impl From<Rep<Raw>> for Vector {
+    fn from(x: Rep<Raw>) -> Self {
+        Vector::Raw(x)
+    }
+}
+
 impl From<Rep<Character>> for Vector {
     fn from(x: Rep<Character>) -> Self {
         Vector::Character(x)
@@ -389,6 +630,30 @@ impl From<Vec<OptionNA<bool>>> for Vector {
     }
 }
 
+impl From<Vec<Complex64>> for Vector {
+    fn from(x: Vec<Complex64>) -> Self {
+        Vector::Complex(x.into())
+    }
+}
+
+impl From<Vec<OptionNA<Complex64>>> for Vector {
+    fn from(x: Vec<OptionNA<Complex64>>) -> Self {
+        Vector::Complex(x.into())
+    }
+}
+
+impl From<Vec<u8>> for Vector {
+    fn from(x: Vec<u8>) -> Self {
+        Vector::Raw(x.into())
+    }
+}
+
+impl From<Vec<OptionNA<u8>>> for Vector {
+    fn from(x: Vec<OptionNA<u8>>) -> Self {
+        Vector::Raw(x.into())
+    }
+}
+
 impl From<Vec<String>> for Vector {
     fn from(x: Vec<String>) -> Self {
         Vector::Character(x.into())
@@ -398,8 +663,8 @@ impl From<Vec<String>> for Vector {
 impl From<Vector> for String {
     fn from(val: Vector) -> Self {
         match val.as_character() {
-            Vector::Character(v) => match v.inner().clone().borrow().first() {
-                Some(OptionNA::Some(s)) => s.clone(),
+            Vector::Character(v) => match v.inner().borrow().get(0) {
+                Some(OptionNA::Some(s)) => s.into(),
                 Some(OptionNA::NA) => "NA".to_string(),
                 None => "".to_string(),
             },
@@ -423,8 +688,8 @@ impl From<Vector> for Vec<String> {
     }
 }
 
-impl From<Vec<OptionNA<String>>> for Vector {
-    fn from(x: Vec<OptionNA<String>>) -> Self {
+impl From<Vec<Character>> for Vector {
+    fn from(x: Vec<Character>) -> Self {
         Vector::Character(x.into())
     }
 }
@@ -433,6 +698,7 @@ pub trait DefaultDebug {}
 impl DefaultDebug for bool {}
 impl DefaultDebug for i32 {}
 impl DefaultDebug for f64 {}
+impl DefaultDebug for Complex64 {}
 
 impl<T> Debug for OptionNA<T>
 where
@@ -446,10 +712,19 @@ where
     }
 }
 
-impl Debug for OptionNA<String> {
+impl Debug for Character {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OptionNA::Some(x) => write!(f, "\"{}\"", escape(x)),
+            OptionNA::NA => write!(f, "NA"),
+        }
+    }
+}
+
+impl Debug for OptionNA<u8> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            OptionNA::Some(x) => write!(f, "\"{}\"", x),
+            OptionNA::Some(x) => write!(f, "{:02x}", x),
             OptionNA::NA => write!(f, "NA"),
         }
     }
@@ -473,6 +748,8 @@ impl Display for Vector {
             Vector::Double(x) => std::fmt::Display::fmt(&x, f),
             Vector::Integer(x) => std::fmt::Display::fmt(&x, f),
             Vector::Logical(x) => std::fmt::Display::fmt(&x, f),
+            Vector::Complex(x) => std::fmt::Display::fmt(&x, f),
+            Vector::Raw(x) => std::fmt::Display::fmt(&x, f),
             Vector::Character(x) => std::fmt::Display::fmt(&x, f),
         }
     }
@@ -578,6 +855,40 @@ where
     }
 }
 
+impl Pow<Complex64> for Complex64 {
+    type Output = Complex64;
+    fn power(self, rhs: Complex64) -> Self::Output {
+        if rhs == Complex64::default() {
+            return Complex64::new(1.0, 0.0);
+        }
+
+        if self == Complex64::default() {
+            return Complex64::default();
+        }
+
+        // integer powers are exact when computed by repeated multiplication
+        if rhs.im == 0.0 && rhs.re.fract() == 0.0 && rhs.re.abs() <= 65536.0 {
+            let (mut base, mut k) = (self, rhs.re.abs() as u32);
+            let mut result = Complex64::new(1.0, 0.0);
+            while k > 0 {
+                if k & 1 == 1 {
+                    result = result * base;
+                }
+                base = base * base;
+                k >>= 1;
+            }
+
+            return if rhs.re < 0.0 {
+                Complex64::new(1.0, 0.0) / result
+            } else {
+                result
+            };
+        }
+
+        (rhs * self.ln()).exp()
+    }
+}
+
 impl<T, U, O> Pow<OptionNA<U>> for OptionNA<T>
 where
     T: Pow<U, Output = O>,
@@ -643,6 +954,8 @@ impl std::ops::Neg for Vector {
             Double(x) => x.neg().map(|x| x.into()),
             Integer(x) => x.neg().map(|x| x.into()),
             Logical(x) => x.neg().map(|x| x.into()),
+            Complex(x) => x.neg().map(|x| x.into()),
+            Raw(_) => Err(Error::Other("invalid argument to unary operator".to_string()).into()),
             _ => todo!(),
         }
     }
@@ -654,6 +967,11 @@ impl std::ops::Not for Vector {
         use Vector::*;
         match self {
             Logical(x) => (!x).map(|x| x.into()),
+            Raw(x) => {
+                let values: Vec<super::types::Raw> =
+                    x.iter_values().map(|x| x.map(|x| !x)).collect();
+                Ok(Vector::from(values).with_attributes(x.attributes()))
+            }
             _ => todo!(),
         }
     }
@@ -673,6 +991,14 @@ impl std::ops::Add for Vector {
             (Logical(l), Double(r)) => (l + r).map(|x| x.into()),
             (Logical(l), Integer(r)) => (l + r).map(|x| x.into()),
             (Logical(l), Logical(r)) => (l + r).map(|x| x.into()),
+            (Double(l), Complex(r)) => (l + r).map(|x| x.into()),
+            (Integer(l), Complex(r)) => (l + r).map(|x| x.into()),
+            (Logical(l), Complex(r)) => (l + r).map(|x| x.into()),
+            (Complex(l), Double(r)) => (l + r).map(|x| x.into()),
+            (Complex(l), Integer(r)) => (l + r).map(|x| x.into()),
+            (Complex(l), Logical(r)) => (l + r).map(|x| x.into()),
+            (Complex(l), Complex(r)) => (l + r).map(|x| x.into()),
+            (Raw(_), _) | (_, Raw(_)) => Err(non_numeric_err()),
             // Add more combinations if necessary
             _ => todo!(),
         }
@@ -693,6 +1019,14 @@ impl std::ops::Sub for Vector {
             (Logical(l), Double(r)) => (l - r).map(|x| x.into()),
             (Logical(l), Integer(r)) => (l - r).map(|x| x.into()),
             (Logical(l), Logical(r)) => (l - r).map(|x| x.into()),
+            (Double(l), Complex(r)) => (l - r).map(|x| x.into()),
+            (Integer(l), Complex(r)) => (l - r).map(|x| x.into()),
+            (Logical(l), Complex(r)) => (l - r).map(|x| x.into()),
+            (Complex(l), Double(r)) => (l - r).map(|x| x.into()),
+            (Complex(l), Integer(r)) => (l - r).map(|x| x.into()),
+            (Complex(l), Logical(r)) => (l - r).map(|x| x.into()),
+            (Complex(l), Complex(r)) => (l - r).map(|x| x.into()),
+            (Raw(_), _) | (_, Raw(_)) => Err(non_numeric_err()),
             _ => todo!(),
         }
     }
@@ -712,6 +1046,14 @@ impl std::ops::Mul for Vector {
             (Logical(l), Double(r)) => (l * r).map(|x| x.into()),
             (Logical(l), Integer(r)) => (l * r).map(|x| x.into()),
             (Logical(l), Logical(r)) => (l * r).map(|x| x.into()),
+            (Double(l), Complex(r)) => (l * r).map(|x| x.into()),
+            (Integer(l), Complex(r)) => (l * r).map(|x| x.into()),
+            (Logical(l), Complex(r)) => (l * r).map(|x| x.into()),
+            (Complex(l), Double(r)) => (l * r).map(|x| x.into()),
+            (Complex(l), Integer(r)) => (l * r).map(|x| x.into()),
+            (Complex(l), Logical(r)) => (l * r).map(|x| x.into()),
+            (Complex(l), Complex(r)) => (l * r).map(|x| x.into()),
+            (Raw(_), _) | (_, Raw(_)) => Err(non_numeric_err()),
             _ => todo!(),
         }
     }
@@ -731,6 +1073,14 @@ impl std::ops::Div for Vector {
             (Logical(l), Double(r)) => (l / r).map(|x| x.into()),
             (Logical(l), Integer(r)) => (l / r).map(|x| x.into()),
             (Logical(l), Logical(r)) => (l / r).map(|x| x.into()),
+            (Double(l), Complex(r)) => (l / r).map(|x| x.into()),
+            (Integer(l), Complex(r)) => (l / r).map(|x| x.into()),
+            (Logical(l), Complex(r)) => (l / r).map(|x| x.into()),
+            (Complex(l), Double(r)) => (l / r).map(|x| x.into()),
+            (Complex(l), Integer(r)) => (l / r).map(|x| x.into()),
+            (Complex(l), Logical(r)) => (l / r).map(|x| x.into()),
+            (Complex(l), Complex(r)) => (l / r).map(|x| x.into()),
+            (Raw(_), _) | (_, Raw(_)) => Err(non_numeric_err()),
             _ => todo!(),
         }
     }
@@ -750,6 +1100,14 @@ impl Pow<Vector> for Vector {
             (Logical(l), Double(r)) => l.power(r).map(|x| x.into()),
             (Logical(l), Integer(r)) => l.power(r).map(|x| x.into()),
             (Logical(l), Logical(r)) => l.power(r).map(|x| x.into()),
+            (Double(l), Complex(r)) => l.power(r).map(|x| x.into()),
+            (Integer(l), Complex(r)) => l.power(r).map(|x| x.into()),
+            (Logical(l), Complex(r)) => l.power(r).map(|x| x.into()),
+            (Complex(l), Double(r)) => l.power(r).map(|x| x.into()),
+            (Complex(l), Integer(r)) => l.power(r).map(|x| x.into()),
+            (Complex(l), Logical(r)) => l.power(r).map(|x| x.into()),
+            (Complex(l), Complex(r)) => l.power(r).map(|x| x.into()),
+            (Raw(_), _) | (_, Raw(_)) => Err(non_numeric_err()),
             _ => todo!(),
         }
     }
@@ -765,11 +1123,43 @@ pub trait VecPartialCmp<Rhs> {
     fn vec_neq(self, rhs: Rhs) -> Self::Output;
 }
 
+fn non_numeric_err() -> Signal {
+    Error::Other("non-numeric argument to binary operator".to_string()).into()
+}
+
+fn raw_logical_err() -> Signal {
+    Error::Other("operations are possible only for numeric, logical or complex types".to_string())
+        .into()
+}
+
+/// Prepare raw vectors to be compared against other vectors
+///
+/// Raw values are compared by their byte value against numbers and by their
+/// hexadecimal representation against strings.
+fn raw_cmp_operands(lhs: Vector, rhs: Vector) -> (Vector, Vector) {
+    use Vector::*;
+    match (lhs, rhs) {
+        (l @ Raw(_), r @ Character(_)) => (l.as_character(), r),
+        (l @ Character(_), r @ Raw(_)) => (l, r.as_character()),
+        (l @ Raw(_), r) => (l.as_integer(), r),
+        (l, r) => (l, r.as_integer()),
+    }
+}
+
+fn complex_cmp_err() -> Signal {
+    Error::Other("invalid comparison with complex values".to_string()).into()
+}
+
 impl VecPartialCmp<Vector> for Vector {
     type Output = Result<Vector, Signal>;
     fn vec_gt(self, rhs: Self) -> Self::Output {
         use Vector::*;
         match (self, rhs) {
+            (Raw(l), Raw(r)) => l.vec_gt(r).map(|x| x.into()),
+            (l @ Raw(_), r) | (l, r @ Raw(_)) => {
+                let (l, r) = raw_cmp_operands(l, r);
+                l.vec_gt(r)
+            }
             (Double(l), Double(r)) => l.vec_gt(r).map(|x| x.into()),
             (Double(l), Integer(r)) => l.vec_gt(r).map(|x| x.into()),
             (Double(l), Logical(r)) => l.vec_gt(r).map(|x| x.into()),
@@ -786,12 +1176,18 @@ impl VecPartialCmp<Vector> for Vector {
             (Character(l), Integer(r)) => l.vec_gt(r).map(|x| x.into()),
             (Character(l), Logical(r)) => l.vec_gt(r).map(|x| x.into()),
             (Character(l), Character(r)) => l.vec_gt(r).map(|x| x.into()),
+            (Complex(_), _) | (_, Complex(_)) => Err(complex_cmp_err()),
         }
     }
 
     fn vec_gte(self, rhs: Self) -> Self::Output {
         use Vector::*;
         match (self, rhs) {
+            (Raw(l), Raw(r)) => l.vec_gte(r).map(|x| x.into()),
+            (l @ Raw(_), r) | (l, r @ Raw(_)) => {
+                let (l, r) = raw_cmp_operands(l, r);
+                l.vec_gte(r)
+            }
             (Double(l), Double(r)) => l.vec_gte(r).map(|x| x.into()),
             (Double(l), Integer(r)) => l.vec_gte(r).map(|x| x.into()),
             (Double(l), Logical(r)) => l.vec_gte(r).map(|x| x.into()),
@@ -808,12 +1204,18 @@ impl VecPartialCmp<Vector> for Vector {
             (Character(l), Integer(r)) => l.vec_gte(r).map(|x| x.into()),
             (Character(l), Logical(r)) => l.vec_gte(r).map(|x| x.into()),
             (Character(l), Character(r)) => l.vec_gte(r).map(|x| x.into()),
+            (Complex(_), _) | (_, Complex(_)) => Err(complex_cmp_err()),
         }
     }
 
     fn vec_lt(self, rhs: Self) -> Self::Output {
         use Vector::*;
         match (self, rhs) {
+            (Raw(l), Raw(r)) => l.vec_lt(r).map(|x| x.into()),
+            (l @ Raw(_), r) | (l, r @ Raw(_)) => {
+                let (l, r) = raw_cmp_operands(l, r);
+                l.vec_lt(r)
+            }
             (Double(l), Double(r)) => l.vec_lt(r).map(|x| x.into()),
             (Double(l), Integer(r)) => l.vec_lt(r).map(|x| x.into()),
             (Double(l), Logical(r)) => l.vec_lt(r).map(|x| x.into()),
@@ -830,12 +1232,18 @@ impl VecPartialCmp<Vector> for Vector {
             (Character(l), Integer(r)) => l.vec_lt(r).map(|x| x.into()),
             (Character(l), Logical(r)) => l.vec_lt(r).map(|x| x.into()),
             (Character(l), Character(r)) => l.vec_lt(r).map(|x| x.into()),
+            (Complex(_), _) | (_, Complex(_)) => Err(complex_cmp_err()),
         }
     }
 
     fn vec_lte(self, rhs: Self) -> Self::Output {
         use Vector::*;
         match (self, rhs) {
+            (Raw(l), Raw(r)) => l.vec_lte(r).map(|x| x.into()),
+            (l @ Raw(_), r) | (l, r @ Raw(_)) => {
+                let (l, r) = raw_cmp_operands(l, r);
+                l.vec_lte(r)
+            }
             (Double(l), Double(r)) => l.vec_lte(r).map(|x| x.into()),
             (Double(l), Integer(r)) => l.vec_lte(r).map(|x| x.into()),
             (Double(l), Logical(r)) => l.vec_lte(r).map(|x| x.into()),
@@ -852,12 +1260,18 @@ impl VecPartialCmp<Vector> for Vector {
             (Character(l), Integer(r)) => l.vec_lte(r).map(|x| x.into()),
             (Character(l), Logical(r)) => l.vec_lte(r).map(|x| x.into()),
             (Character(l), Character(r)) => l.vec_lte(r).map(|x| x.into()),
+            (Complex(_), _) | (_, Complex(_)) => Err(complex_cmp_err()),
         }
     }
 
     fn vec_eq(self, rhs: Self) -> Self::Output {
         use Vector::*;
         match (self, rhs) {
+            (Raw(l), Raw(r)) => l.vec_eq(r).map(|x| x.into()),
+            (l @ Raw(_), r) | (l, r @ Raw(_)) => {
+                let (l, r) = raw_cmp_operands(l, r);
+                l.vec_eq(r)
+            }
             (Double(l), Double(r)) => l.vec_eq(r).map(|x| x.into()),
             (Double(l), Integer(r)) => l.vec_eq(r).map(|x| x.into()),
             (Double(l), Logical(r)) => l.vec_eq(r).map(|x| x.into()),
@@ -874,12 +1288,26 @@ impl VecPartialCmp<Vector> for Vector {
             (Character(l), Integer(r)) => l.vec_eq(r).map(|x| x.into()),
             (Character(l), Logical(r)) => l.vec_eq(r).map(|x| x.into()),
             (Character(l), Character(r)) => l.vec_eq(r).map(|x| x.into()),
+            (Double(l), Complex(r)) => l.vec_eq(r).map(|x| x.into()),
+            (Integer(l), Complex(r)) => l.vec_eq(r).map(|x| x.into()),
+            (Logical(l), Complex(r)) => l.vec_eq(r).map(|x| x.into()),
+            (Complex(l), Double(r)) => l.vec_eq(r).map(|x| x.into()),
+            (Complex(l), Integer(r)) => l.vec_eq(r).map(|x| x.into()),
+            (Complex(l), Logical(r)) => l.vec_eq(r).map(|x| x.into()),
+            (Complex(l), Complex(r)) => l.vec_eq(r).map(|x| x.into()),
+            (Character(l), Complex(r)) => l.vec_eq(r).map(|x| x.into()),
+            (Complex(l), Character(r)) => l.vec_eq(r).map(|x| x.into()),
         }
     }
 
     fn vec_neq(self, rhs: Self) -> Self::Output {
         use Vector::*;
         match (self, rhs) {
+            (Raw(l), Raw(r)) => l.vec_neq(r).map(|x| x.into()),
+            (l @ Raw(_), r) | (l, r @ Raw(_)) => {
+                let (l, r) = raw_cmp_operands(l, r);
+                l.vec_neq(r)
+            }
             (Double(l), Double(r)) => l.vec_neq(r).map(|x| x.into()),
             (Double(l), Integer(r)) => l.vec_neq(r).map(|x| x.into()),
             (Double(l), Logical(r)) => l.vec_neq(r).map(|x| x.into()),
@@ -896,6 +1324,15 @@ impl VecPartialCmp<Vector> for Vector {
             (Character(l), Integer(r)) => l.vec_neq(r).map(|x| x.into()),
             (Character(l), Logical(r)) => l.vec_neq(r).map(|x| x.into()),
             (Character(l), Character(r)) => l.vec_neq(r).map(|x| x.into()),
+            (Double(l), Complex(r)) => l.vec_neq(r).map(|x| x.into()),
+            (Integer(l), Complex(r)) => l.vec_neq(r).map(|x| x.into()),
+            (Logical(l), Complex(r)) => l.vec_neq(r).map(|x| x.into()),
+            (Complex(l), Double(r)) => l.vec_neq(r).map(|x| x.into()),
+            (Complex(l), Integer(r)) => l.vec_neq(r).map(|x| x.into()),
+            (Complex(l), Logical(r)) => l.vec_neq(r).map(|x| x.into()),
+            (Complex(l), Complex(r)) => l.vec_neq(r).map(|x| x.into()),
+            (Character(l), Complex(r)) => l.vec_neq(r).map(|x| x.into()),
+            (Complex(l), Character(r)) => l.vec_neq(r).map(|x| x.into()),
         }
     }
 }
@@ -914,6 +1351,10 @@ impl std::ops::Rem for Vector {
             (Logical(l), Double(r)) => l.rem(r).map(|x| x.into()),
             (Logical(l), Integer(r)) => l.rem(r).map(|x| x.into()),
             (Logical(l), Logical(r)) => l.rem(r).map(|x| x.into()),
+            (Complex(_), _) | (_, Complex(_)) => {
+                Err(Error::Other("invalid operation on complex numbers".to_string()).into())
+            }
+            (Raw(_), _) | (_, Raw(_)) => Err(non_numeric_err()),
             _ => todo!(),
         }
     }
@@ -940,6 +1381,17 @@ impl std::ops::BitOr for Vector {
             (Character(l), Integer(r)) => l.bitor(r).map(|x| x.into()),
             (Character(l), Logical(r)) => l.bitor(r).map(|x| x.into()),
             (Character(l), Character(r)) => l.bitor(r).map(|x| x.into()),
+            (Double(l), Complex(r)) => l.bitor(r).map(|x| x.into()),
+            (Integer(l), Complex(r)) => l.bitor(r).map(|x| x.into()),
+            (Logical(l), Complex(r)) => l.bitor(r).map(|x| x.into()),
+            (Complex(l), Double(r)) => l.bitor(r).map(|x| x.into()),
+            (Complex(l), Integer(r)) => l.bitor(r).map(|x| x.into()),
+            (Complex(l), Logical(r)) => l.bitor(r).map(|x| x.into()),
+            (Complex(l), Complex(r)) => l.bitor(r).map(|x| x.into()),
+            (Character(l), Complex(r)) => l.bitor(r).map(|x| x.into()),
+            (Complex(l), Character(r)) => l.bitor(r).map(|x| x.into()),
+            (Raw(l), Raw(r)) => try_binary_raw_op(l, r, |x, y| x | y).map(|x| x.into()),
+            (Raw(_), _) | (_, Raw(_)) => Err(raw_logical_err()),
         }
     }
 }
@@ -965,6 +1417,17 @@ impl std::ops::BitAnd for Vector {
             (Character(l), Integer(r)) => l.bitand(r).map(|x| x.into()),
             (Character(l), Logical(r)) => l.bitand(r).map(|x| x.into()),
             (Character(l), Character(r)) => l.bitand(r).map(|x| x.into()),
+            (Double(l), Complex(r)) => l.bitand(r).map(|x| x.into()),
+            (Integer(l), Complex(r)) => l.bitand(r).map(|x| x.into()),
+            (Logical(l), Complex(r)) => l.bitand(r).map(|x| x.into()),
+            (Complex(l), Double(r)) => l.bitand(r).map(|x| x.into()),
+            (Complex(l), Integer(r)) => l.bitand(r).map(|x| x.into()),
+            (Complex(l), Logical(r)) => l.bitand(r).map(|x| x.into()),
+            (Complex(l), Complex(r)) => l.bitand(r).map(|x| x.into()),
+            (Character(l), Complex(r)) => l.bitand(r).map(|x| x.into()),
+            (Complex(l), Character(r)) => l.bitand(r).map(|x| x.into()),
+            (Raw(l), Raw(r)) => try_binary_raw_op(l, r, |x, y| x & y).map(|x| x.into()),
+            (Raw(_), _) | (_, Raw(_)) => Err(raw_logical_err()),
         }
     }
 }
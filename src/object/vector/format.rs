@@ -0,0 +1,343 @@
+use crate::error::Error;
+
+use super::rep::Rep;
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+    Center,
+}
+
+/// Format Specs
+///
+/// Describes how the values of a field of an interpolated string, such as
+/// `f"{x:>8.2f}"`, are formatted. Specs follow the mini-language of Python's
+/// format strings, `[[fill]align][sign][0][width][,][.precision][type]`,
+/// where `align` is one of `<`, `>` or `^`, `sign` is one of `+`, `-` or a
+/// space, `,` groups thousands and `type` is one of
+///
+/// * `f`, `e` or `%` for fixed, scientific or percentage notation
+/// * `d`, `x`, `X`, `o` or `b` for decimal, hexadecimal, octal or binary
+///   notation of whole numbers
+/// * `s` for strings
+///
+/// Without a type, values are formatted as they are printed and a precision
+/// gives the number of significant digits of numbers, or the maximum number
+/// of characters of strings.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatSpec {
+    spec: String,
+    fill: char,
+    align: Option<Align>,
+    sign: Option<char>,
+    zero: bool,
+    width: usize,
+    grouping: bool,
+    precision: Option<usize>,
+    kind: Option<char>,
+}
+
+impl Default for FormatSpec {
+    fn default() -> Self {
+        FormatSpec {
+            spec: String::new(),
+            fill: ' ',
+            align: None,
+            sign: None,
+            zero: false,
+            width: 0,
+            grouping: false,
+            precision: None,
+            kind: None,
+        }
+    }
+}
+
+impl FormatSpec {
+    pub fn parse(spec: &str) -> Result<FormatSpec, Error> {
+        let invalid = || Error::InvalidFormatSpec(spec.to_string());
+        let align = |c: Option<&char>| match c {
+            Some('<') => Some(Align::Left),
+            Some('>') => Some(Align::Right),
+            Some('^') => Some(Align::Center),
+            _ => None,
+        };
+
+        let chars: Vec<char> = spec.chars().collect();
+        let mut format = FormatSpec { spec: spec.to_string(), ..FormatSpec::default() };
+
+        let mut i = 0;
+        if let (Some(&fill), Some(a)) = (chars.first(), align(chars.get(1))) {
+            (format.fill, format.align, i) = (fill, Some(a), 2);
+        } else if let Some(a) = align(chars.first()) {
+            (format.align, i) = (Some(a), 1);
+        }
+
+        if let Some(&c @ ('+' | '-' | ' ')) = chars.get(i) {
+            (format.sign, i) = (Some(c), i + 1);
+        }
+
+        if let Some('0') = chars.get(i) {
+            (format.zero, i) = (true, i + 1);
+        }
+
+        let digits = |i: usize| chars[i..].iter().take_while(|c| c.is_ascii_digit()).count();
+        let n = digits(i);
+        if n > 0 {
+            let width: String = chars[i..i + n].iter().collect();
+            (format.width, i) = (width.parse().map_err(|_| invalid())?, i + n);
+        }
+
+        if let Some(',') = chars.get(i) {
+            (format.grouping, i) = (true, i + 1);
+        }
+
+        if let Some('.') = chars.get(i) {
+            let n = digits(i + 1);
+            let precision: String = chars[i + 1..i + 1 + n].iter().collect();
+            format.precision = Some(precision.parse().map_err(|_| invalid())?);
+            i += 1 + n;
+        }
+
+        if let Some(&c @ ('f' | 'e' | '%' | 'd' | 'x' | 'X' | 'o' | 'b' | 's')) = chars.get(i) {
+            (format.kind, i) = (Some(c), i + 1);
+        }
+
+        match i == chars.len() {
+            true => Ok(format),
+            false => Err(invalid()),
+        }
+    }
+
+    /// Split the field of an interpolated string into its code and format spec
+    ///
+    /// A spec follows the last `:` outside of any brackets or strings, so
+    /// long as what follows it is a valid spec. Otherwise the field is all
+    /// code, as in `{x[1:2]}`, `{base::sum(x)}` or `{1:n}`, while ranges of
+    /// literals like `{1:3}` must be wrapped in parentheses.
+    ///
+    pub fn split(field: &str) -> (&str, Option<&str>) {
+        let mut depth = 0;
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        let mut last_colon = None;
+
+        for (i, c) in field.char_indices() {
+            match (quote, c) {
+                (Some(_), _) if escaped => escaped = false,
+                (Some(_), '\\') => escaped = true,
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => (),
+                (None, '"' | '\'') => quote = Some(c),
+                (None, '(' | '[' | '{') => depth += 1,
+                (None, ')' | ']' | '}') => depth -= 1,
+                (None, ':') if depth == 0 => last_colon = Some(i),
+                _ => (),
+            }
+        }
+
+        let Some(i) = last_colon else {
+            return (field, None);
+        };
+
+        let (code, spec) = (&field[..i], &field[i + 1..]);
+        if code.ends_with(':') || spec.starts_with(':') || FormatSpec::parse(spec).is_err() {
+            return (field, None);
+        }
+
+        (code, Some(spec))
+    }
+
+    /// Format each element of a vector
+    pub fn format(&self, x: &Vector) -> Result<Vec<String>, Error> {
+        if x.is_unbounded() {
+            return Err(Error::UnboundedSequence);
+        }
+
+        match x {
+            Vector::Double(rep) => self.format_each(rep, |x| self.format_double(x)),
+            Vector::Integer(rep) => self.format_each(rep, |x| self.format_integer(x)),
+            Vector::Character(rep) => self.format_each(rep, |x| self.format_str(&x)),
+            Vector::Logical(rep) => self.format_each(rep, |x| self.format_other(x, "logical")),
+            Vector::Complex(rep) => self.format_each(rep, |x| self.format_other(x, "complex")),
+            Vector::Raw(rep) => self.format_each(rep, |x| self.format_other(x, "raw")),
+        }
+    }
+
+    fn format_each<T, U, F>(&self, rep: &Rep<T>, f: F) -> Result<Vec<String>, Error>
+    where
+        T: Element + Default + Into<OptionNA<U>>,
+        F: Fn(U) -> Result<String, Error>,
+    {
+        rep.iter_values()
+            .map(|x| match x.into() {
+                OptionNA::Some(x) => f(x),
+                OptionNA::NA => Ok(self.pad("", "NA", Align::Right)),
+            })
+            .collect()
+    }
+
+    /// Format a string, truncated to the precision
+    pub fn format_str(&self, x: &str) -> Result<String, Error> {
+        if !matches!(self.kind, None | Some('s')) {
+            return Err(self.incompatible("character"));
+        }
+
+        let x: String = match self.precision {
+            Some(n) => x.chars().take(n).collect(),
+            None => x.to_string(),
+        };
+
+        Ok(self.pad("", &x, Align::Left))
+    }
+
+    fn format_other<T>(&self, x: T, type_of: &str) -> Result<String, Error>
+    where
+        OptionNA<T>: std::fmt::Debug,
+    {
+        match self.kind {
+            None | Some('s') => Ok(self.pad("", &format!("{:?}", OptionNA::Some(x)), Align::Right)),
+            _ => Err(self.incompatible(type_of)),
+        }
+    }
+
+    fn format_integer(&self, x: i32) -> Result<String, Error> {
+        let digits = match (self.kind, self.precision) {
+            (None, None) | (Some('d'), _) => x.unsigned_abs().to_string(),
+            (Some('x'), _) => format!("{:x}", x.unsigned_abs()),
+            (Some('X'), _) => format!("{:X}", x.unsigned_abs()),
+            (Some('o'), _) => format!("{:o}", x.unsigned_abs()),
+            (Some('b'), _) => format!("{:b}", x.unsigned_abs()),
+            (Some('s'), _) => return Err(self.incompatible("integer")),
+            _ => return self.format_double(x as f64),
+        };
+
+        Ok(self.pad_number(x < 0, digits))
+    }
+
+    fn format_double(&self, x: f64) -> Result<String, Error> {
+        let negative = x.is_sign_negative() && x != 0.0;
+        let value = x.abs();
+        let whole = value.fract() == 0.0 && value <= i32::MAX as f64;
+
+        let digits = match (self.kind, self.precision) {
+            (None, None) => format!("{:?}", OptionNA::Some(value)),
+            (None, Some(n)) => {
+                let rounded = format!("{:.*e}", n.max(1) - 1, value);
+                format!(
+                    "{:?}",
+                    OptionNA::Some(rounded.parse::<f64>().unwrap_or(value))
+                )
+            }
+            (Some('f'), n) => format!("{:.*}", n.unwrap_or(6), value),
+            (Some('%'), n) => format!("{:.*}%", n.unwrap_or(6), value * 100.0),
+            (Some('e'), n) => scientific(value, n.unwrap_or(6)),
+            (Some('d' | 'x' | 'X' | 'o' | 'b'), _) if whole => {
+                let x = if negative { -value } else { value };
+                return self.format_integer(x as i32);
+            }
+            _ => return Err(self.incompatible("double")),
+        };
+
+        Ok(self.pad_number(negative, digits))
+    }
+
+    fn pad_number(&self, negative: bool, digits: String) -> String {
+        let sign = match (negative, self.sign) {
+            (true, _) => "-",
+            (false, Some('+')) => "+",
+            (false, Some(' ')) => " ",
+            _ => "",
+        };
+
+        let digits = match self.grouping {
+            true => group_thousands(&digits),
+            false => digits,
+        };
+
+        // zero-padding is placed between the sign and digits
+        let n = sign.chars().count() + digits.chars().count();
+        if self.zero && self.align.is_none() && n < self.width {
+            return format!("{sign}{}{digits}", "0".repeat(self.width - n));
+        }
+
+        self.pad(sign, &digits, Align::Right)
+    }
+
+    fn pad(&self, sign: &str, x: &str, align: Align) -> String {
+        let n = sign.chars().count() + x.chars().count();
+        let padding = self.width.saturating_sub(n);
+        let (left, right) = match self.align.unwrap_or(align) {
+            Align::Left => (0, padding),
+            Align::Right => (padding, 0),
+            Align::Center => (padding / 2, padding - padding / 2),
+        };
+
+        let fill = |n: usize| self.fill.to_string().repeat(n);
+        format!("{}{sign}{x}{}", fill(left), fill(right))
+    }
+
+    fn incompatible(&self, type_of: &str) -> Error {
+        Error::IncompatibleFormatSpec(self.spec.clone(), type_of.to_string())
+    }
+}
+
+/// Scientific notation with a signed exponent of at least two digits, as
+/// in `1.50e+03`
+fn scientific(x: f64, precision: usize) -> String {
+    let formatted = format!("{:.*e}", precision, x);
+    match formatted.split_once('e') {
+        Some((mantissa, exponent)) => {
+            let exponent: i32 = exponent.parse().unwrap_or_default();
+            let sign = if exponent < 0 { '-' } else { '+' };
+            format!("{mantissa}e{sign}{:02}", exponent.abs())
+        }
+        None => formatted,
+    }
+}
+
+/// Separate the thousands of the leading digits, as in `1,234,567.89`
+fn group_thousands(digits: &str) -> String {
+    let n = digits.chars().take_while(|c| c.is_ascii_digit()).count();
+    let (whole, rest) = digits.split_at(n);
+
+    let mut grouped = String::with_capacity(digits.len() + n / 3);
+    for (i, c) in whole.chars().enumerate() {
+        if i > 0 && (n - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+
+    grouped + rest
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn format(spec: &str, x: f64) -> String {
+        FormatSpec::parse(spec).unwrap().format_double(x).unwrap()
+    }
+
+    #[test]
+    fn numbers_are_formatted() {
+        assert_eq!(format(".2f", 3.14159), "3.14");
+        assert_eq!(format(".3", 3.14159), "3.14");
+        assert_eq!(format(".1e", 1500.0), "1.5e+03");
+        assert_eq!(format(".0%", 0.25), "25%");
+        assert_eq!(format(",", 1234567.0), "1,234,567");
+        assert_eq!(format("+08.2f", -1.5), "-0001.50");
+        assert_eq!(format("x", 255.0), "ff");
+    }
+
+    #[test]
+    fn invalid_specs() {
+        assert!(FormatSpec::parse("q").is_err());
+        assert!(FormatSpec::parse(".f").is_err());
+        assert!(FormatSpec::parse("d").unwrap().format_double(1.5).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use std::fmt::Debug;
+use std::rc::Rc;
+
+/// Lazy Elementwise Vectors
+///
+/// The result of an elementwise operation, like `x + y`, whose elements are
+/// only computed once they are needed. Because the operands may themselves be
+/// lazy, a chain of operations like `(x - m) ^ 2 / n` is fused into a single
+/// function of the element index, avoiding intermediate vectors.
+///
+/// The `depth` counts the operations fused into the vector, so that long
+/// chains (for example, built up in a loop) can be computed before they grow
+/// too deep.
+///
+pub struct Lazy<T> {
+    pub len: usize,
+    pub depth: usize,
+    elem: Rc<dyn Fn(usize) -> T>,
+}
+
+impl<T> Lazy<T> {
+    /// The deepest chain of operations that is fused into a single pass
+    pub const MAX_DEPTH: usize = 64;
+
+    pub fn new<F>(len: usize, depth: usize, elem: F) -> Self
+    where
+        F: Fn(usize) -> T + 'static,
+    {
+        Lazy { len, depth, elem: Rc::new(elem) }
+    }
+
+    /// Get the element at `index`, if it is within the vector
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index < self.len {
+            Some((self.elem)(index))
+        } else {
+            None
+        }
+    }
+
+    /// The function computing each element, for fusing into other operations
+    pub fn elem(&self) -> Rc<dyn Fn(usize) -> T> {
+        self.elem.clone()
+    }
+
+    /// Iterate over the computed elements
+    pub fn iter(&self) -> impl Iterator<Item = T> {
+        let elem = self.elem.clone();
+        (0..self.len).map(move |i| elem(i))
+    }
+}
+
+impl<T> Clone for Lazy<T> {
+    fn clone(&self) -> Self {
+        Lazy {
+            len: self.len,
+            depth: self.depth,
+            elem: self.elem.clone(),
+        }
+    }
+}
+
+impl<T> Debug for Lazy<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Lazy")
+            .field("len", &self.len)
+            .field("depth", &self.depth)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Lazy;
+
+    #[test]
+    fn lazy_elements() {
+        let x = Lazy::new(3, 1, |i| i * 2);
+        assert_eq!(x.get(2), Some(4));
+        assert_eq!(x.get(3), None);
+        assert_eq!(x.iter().collect::<Vec<_>>(), vec![0, 2, 4]);
+    }
+}
@@ -5,6 +5,7 @@
 /// R's vector types, but there is room for improvement.
 ///
 pub mod coercion;
+pub mod complex;
 pub mod iterators;
 pub mod rep;
 pub mod types;
@@ -15,5 +16,20 @@ pub use subsets::*;
 mod subset;
 pub use subset::*;
 
+mod sequence;
+pub use sequence::*;
+
+mod lazy;
+pub use lazy::*;
+
+mod values;
+pub use values::*;
+
+mod strings;
+pub use strings::*;
+
+mod format;
+pub use format::*;
+
 mod core;
 pub use core::*;
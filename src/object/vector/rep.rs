@@ -1,15 +1,19 @@
 use std::fmt::Debug;
 use std::fmt::Display;
-use std::iter::repeat;
 
 use super::coercion::{AtomicMode, CoercibleInto, CommonCmp, CommonNum, MinimallyNumeric};
+use super::complex::Complex64;
+use super::lazy::Lazy;
+use super::sequence::Sequence;
 use super::subset::Subset;
 use super::subsets::Subsets;
 use super::types::*;
+use super::values::{Element, Values};
+use super::Str;
 use super::{OptionNA, Pow, VecPartialCmp};
 use crate::error::Error;
 use crate::lang::Signal;
-use crate::object::{CowObj, ViewMut};
+use crate::object::{Attributes, CowObj, ViewMut};
 use hashbrown::HashMap;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -17,8 +21,8 @@ use std::rc::Rc;
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct Naming {
     // TODO: change this to usize and not Vec<usize> (after making names unique)
-    pub map: CowObj<HashMap<String, Vec<usize>>>,
-    pub names: CowObj<Vec<OptionNA<String>>>,
+    pub map: CowObj<HashMap<Str, Vec<usize>>>,
+    pub names: CowObj<Vec<Character>>,
 }
 
 impl Naming {
@@ -30,13 +34,13 @@ impl Naming {
     // Allocates a new Naming with a capacity for `capacity` elements.
     pub fn with_capacity(capacity: usize) -> Self {
         Self {
-            map: HashMap::<String, Vec<usize>>::with_capacity(capacity).into(),
+            map: HashMap::<Str, Vec<usize>>::with_capacity(capacity).into(),
             names: CowObj::from(Vec::<Character>::with_capacity(capacity)),
         }
     }
 
     /// Push a new name onto the `Naming`.
-    pub fn push(&mut self, name: OptionNA<String>) {
+    pub fn push(&mut self, name: Character) {
         self.names.with_inner_mut(|v| v.push(name.clone()));
         if let OptionNA::Some(name) = name {
             let n = self.names.len() - 1;
@@ -52,17 +56,17 @@ impl Naming {
     /// Get mutable access to the internal data (map and names vector) via the passed closure.
     pub fn with_inner_mut<F, R>(&self, f: F) -> R
     where
-        F: FnOnce(&mut HashMap<String, Vec<usize>>, &mut Vec<OptionNA<String>>) -> R,
+        F: FnOnce(&mut HashMap<Str, Vec<usize>>, &mut Vec<Character>) -> R,
     {
         self.map
             .with_inner_mut(|map| self.names.with_inner_mut(|names| f(map, names)))
     }
 }
 
-impl<T: Clone + Default> From<Vec<(Character, T)>> for Rep<T> {
+impl<T: Element + Default> From<Vec<(Character, T)>> for Rep<T> {
     fn from(value: Vec<(Character, T)>) -> Self {
         let mut names = Vec::with_capacity(value.len());
-        let mut values = Vec::with_capacity(value.len());
+        let mut values = Values::with_capacity(value.len());
         for (k, v) in value {
             names.push(k);
             values.push(v);
@@ -72,13 +76,14 @@ impl<T: Clone + Default> From<Vec<(Character, T)>> for Rep<T> {
             CowObj::new(Rc::new(RefCell::new(Rc::new(values)))),
             Subsets::default(),
             Option::Some(Naming::from(names)),
+            Option::None,
         )
     }
 }
 
 impl From<CowObj<Vec<Character>>> for Naming {
     fn from(value: CowObj<Vec<Character>>) -> Self {
-        let mut map: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut map: HashMap<Str, Vec<usize>> = HashMap::new();
 
         value.iter().enumerate().for_each(|(i, maybe_name)| {
             if let OptionNA::Some(name) = maybe_name {
@@ -94,29 +99,122 @@ impl From<CowObj<Vec<Character>>> for Naming {
 }
 
 /// Vector
-#[derive(Debug, PartialEq)]
-pub enum Rep<T: Clone> {
+#[derive(Debug)]
+pub enum Rep<T: Element> {
     // Vector::Subset encompasses a "raw" vector (no subsetting)
-    Subset(CowObj<Vec<T>>, Subsets, Option<Naming>),
-    // Iterator includes things like ranges 1:Inf, and lazily computed values
-    // Iter(Box<dyn Iterator<Item = &T>>)
+    Subset(
+        CowObj<Values<T>>,
+        Subsets,
+        Option<Naming>,
+        Option<Attributes>,
+    ),
+    // Seq is a compact arithmetic sequence, like the ranges 1:n and 1:Inf, which
+    // is only materialized once it is modified
+    Seq(Sequence<T>, Option<Attributes>),
+    // Lazy is the result of elementwise operations, whose elements are only
+    // computed when they are consumed
+    Lazy(Lazy<T>, Option<Attributes>),
 }
 
-impl<T: Clone> Clone for Rep<T> {
+impl<T: Element> Clone for Rep<T> {
     fn clone(&self) -> Self {
         match self {
-            Rep::Subset(v, s, n) => Rep::Subset(v.clone(), s.clone(), n.clone()),
+            Rep::Subset(v, s, n, a) => Rep::Subset(v.clone(), s.clone(), n.clone(), a.clone()),
+            Rep::Seq(seq, a) => Rep::Seq(*seq, a.clone()),
+            Rep::Lazy(lazy, a) => Rep::Lazy(lazy.clone(), a.clone()),
         }
     }
 }
 
-impl<T: Clone + Default> Default for Rep<T> {
+impl<T: Element + Default + PartialEq> PartialEq for Rep<T> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Rep::Subset(lv, ls, ln, la), Rep::Subset(rv, rs, rn, ra)) => {
+                lv == rv && ls == rs && ln == rn && la == ra
+            }
+            (Rep::Seq(l, la), Rep::Seq(r, ra)) if l == r => la == ra,
+            // sequences are equal to vectors of the same values
+            (l, r) if l.is_unbounded() || r.is_unbounded() => false,
+            (l, r) => l.materialize_bounded() == r.materialize_bounded(),
+        }
+    }
+}
+
+impl<T: Element> Rep<T> {
+    /// The attributes of the vector (excluding names) if there are any.
+    pub fn attributes(&self) -> Option<Attributes> {
+        match self {
+            Rep::Subset(.., attrs) | Rep::Seq(_, attrs) | Rep::Lazy(_, attrs) => attrs.clone(),
+        }
+    }
+
+    /// Replace the attributes of the vector (excluding names).
+    pub fn with_attributes(self, attrs: Option<Attributes>) -> Self {
+        let attrs = attrs.and_then(|a| a.into_option());
+        match self {
+            Rep::Subset(v, s, n, _) => Rep::Subset(v, s, n, attrs),
+            Rep::Seq(seq, _) => Rep::Seq(seq, attrs),
+            Rep::Lazy(lazy, _) => Rep::Lazy(lazy, attrs),
+        }
+    }
+
+    /// Whether the vector is a compact sequence, like `1:n`.
+    pub fn is_sequence(&self) -> bool {
+        matches!(self, Rep::Seq(..))
+    }
+
+    /// Whether the vector is the lazy result of an elementwise operation.
+    pub fn is_lazy(&self) -> bool {
+        matches!(self, Rep::Lazy(..))
+    }
+
+    /// Whether the vector's elements are computed when accessed, as is the
+    /// case for sequences and lazy vectors.
+    pub fn is_computed(&self) -> bool {
+        self.is_sequence() || self.is_lazy()
+    }
+
+    /// Whether the vector is a sequence without an end, like `1:Inf`.
+    pub fn is_unbounded(&self) -> bool {
+        matches!(self, Rep::Seq(seq, _) if seq.is_unbounded())
+    }
+
+    /// Trace copies of the vector's data, returning its address. Computed
+    /// vectors have no data to trace until they are materialized.
+    pub fn trace(&self) -> Option<usize> {
+        match self {
+            Rep::Subset(values, ..) => Some(values.trace()),
+            Rep::Seq(..) | Rep::Lazy(..) => None,
+        }
+    }
+
+    /// Stop tracing copies of the vector's data.
+    pub fn untrace(&self) {
+        if let Rep::Subset(values, ..) = self {
+            values.untrace()
+        }
+    }
+}
+
+impl<T: Element> From<Sequence<T>> for Rep<T> {
+    fn from(value: Sequence<T>) -> Self {
+        Rep::Seq(value, None)
+    }
+}
+
+impl<T: Element> From<Lazy<T>> for Rep<T> {
+    fn from(value: Lazy<T>) -> Self {
+        Rep::Lazy(value, None)
+    }
+}
+
+impl<T: Element + Default> Default for Rep<T> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<T: Clone + Default + ViewMut> Rep<T> {
+impl<T: Element<Dense = T> + Default + ViewMut> Rep<T> {
     /// Get a cloned version of the inner value.
     /// This is used for accessing inner values like `list(1)[[1]]`.
     pub fn try_get_inner(&self, subset: Subset) -> Result<T, Signal> {
@@ -127,7 +225,15 @@ impl<T: Clone + Default + ViewMut> Rep<T> {
     /// This is important for lists for things like `l$a[1:2] = c(10, 11)`
     pub fn try_get_inner_mut(&self, subset: Subset) -> Result<T, Signal> {
         let new_subset = self.subset(subset);
-        match new_subset {
+        match self {
+            // elements of sequences are computed rather than viewed
+            Rep::Seq(..) | Rep::Lazy(..) => match new_subset.len() {
+                0 => Error::Other("subset is empty".to_string()).into(),
+                1 => new_subset
+                    .get_inner(0)
+                    .ok_or_else(|| Error::Other("subset is empty".to_string()).into()),
+                _ => Error::Other("subset has length > 1".to_string()).into(),
+            },
             Rep::Subset(..) => {
                 let mut iter = new_subset.iter_subset_indices();
 
@@ -139,7 +245,7 @@ impl<T: Clone + Default + ViewMut> Rep<T> {
                     // TODO: subsetting with NA should not be possible.
                     let i = i.unwrap();
 
-                    Ok(self.with_inner_mut(|values| values[i].view_mut()))
+                    Ok(self.with_inner_mut(|values| values.as_slice()[i].view_mut()))
                 } else {
                     Error::Other("subset is empty".to_string()).into()
                 }
@@ -183,15 +289,16 @@ impl<'a> Iterator for IterableNames<'a> {
     }
 }
 
-pub struct IntoIterableRefValues<T: Clone> {
-    values: Rc<Vec<T>>,
+pub struct IntoIterableRefValues<T: Element> {
+    values: Rc<Values<T>>,
+    elements: Option<Vec<T>>,
     na_value: T,
     iter: Box<dyn Iterator<Item = Option<usize>>>,
 }
 
-impl<T: Clone + Default> IntoIterableRefValues<T> {
+impl<T: Element + Default> IntoIterableRefValues<T> {
     pub fn iter(&mut self) -> IterableRefValues<'_, T> {
-        let values = &self.values[..];
+        let values = elements_ref(&self.values, &self.elements);
 
         IterableRefValues {
             values,
@@ -201,17 +308,18 @@ impl<T: Clone + Default> IntoIterableRefValues<T> {
     }
 }
 
-pub struct IntoIterableRefPairs<T: Clone> {
-    values: Rc<Vec<T>>,
+pub struct IntoIterableRefPairs<T: Element> {
+    values: Rc<Values<T>>,
+    elements: Option<Vec<T>>,
     names: Option<Rc<Vec<Character>>>,
     na_value: T,
     na_name: Character,
     iter: Box<dyn Iterator<Item = Option<usize>>>,
 }
 
-impl<T: Clone + Default> IntoIterableRefPairs<T> {
+impl<T: Element + Default> IntoIterableRefPairs<T> {
     pub fn iter(&mut self) -> IterableRefPairs<'_, T> {
-        let values = &self.values[..];
+        let values = elements_ref(&self.values, &self.elements);
 
         let names = self.names.as_ref().map(|names| &names[..]);
 
@@ -225,13 +333,29 @@ impl<T: Clone + Default> IntoIterableRefPairs<T> {
     }
 }
 
-pub struct IterableRefValues<'a, T: Clone> {
+/// Elements which are stored densely, like those of atomic vectors, have to
+/// be collected before references to them can be handed out.
+fn collect_elements<T: Element>(values: &Values<T>) -> Option<Vec<T>> {
+    match T::as_elements(values.dense()) {
+        Some(_) => None,
+        None => Some(values.iter().collect()),
+    }
+}
+
+fn elements_ref<'a, T: Element>(values: &'a Values<T>, elements: &'a Option<Vec<T>>) -> &'a [T] {
+    match elements {
+        Some(elements) => elements,
+        None => T::as_elements(values.dense()).expect("elements are stored as they are"),
+    }
+}
+
+pub struct IterableRefValues<'a, T: Element> {
     values: &'a [T],
     na_value: &'a T,
     iter: &'a mut Box<dyn Iterator<Item = Option<usize>>>,
 }
 
-pub struct IterableRefPairs<'a, T: Clone> {
+pub struct IterableRefPairs<'a, T: Element> {
     values: &'a [T],
     names: Option<&'a [Character]>,
     na_value: &'a T,
@@ -239,7 +363,7 @@ pub struct IterableRefPairs<'a, T: Clone> {
     iter: &'a mut Box<dyn Iterator<Item = Option<usize>>>,
 }
 
-impl<'a, T: Clone> Iterator for IterableRefPairs<'a, T> {
+impl<'a, T: Element> Iterator for IterableRefPairs<'a, T> {
     type Item = (&'a Character, &'a T);
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -255,7 +379,7 @@ impl<'a, T: Clone> Iterator for IterableRefPairs<'a, T> {
     }
 }
 
-impl<'a, T: Clone> Iterator for IterableRefValues<'a, T> {
+impl<'a, T: Element> Iterator for IterableRefValues<'a, T> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -267,40 +391,59 @@ impl<'a, T: Clone> Iterator for IterableRefValues<'a, T> {
     }
 }
 
-impl<T: Clone> ViewMut for Rep<T> {
+impl<T: Element> ViewMut for Rep<T> {
     fn view_mut(&self) -> Self {
         match self {
-            Rep::Subset(v, s, n) => Rep::Subset(v.view_mut(), s.clone(), n.clone()),
+            Rep::Subset(v, s, n, a) => Rep::Subset(v.view_mut(), s.clone(), n.clone(), a.clone()),
+            Rep::Seq(..) | Rep::Lazy(..) => self.clone(),
         }
     }
 }
 
-pub struct IterableValues<T: Clone> {
-    values: Rc<Vec<T>>,
+enum Elements<T: Element> {
+    Values(Rc<Values<T>>),
+    Vec(Rc<Vec<T>>),
+    Seq(Sequence<T>),
+    Lazy(Lazy<T>),
+}
+
+pub struct IterableValues<T: Element> {
+    elements: Elements<T>,
     iter: Box<dyn Iterator<Item = Option<usize>>>,
 }
 
-impl<T: Clone> Iterator for IterableValues<T> {
+impl<T: Element> Iterator for IterableValues<T> {
     type Item = T;
     fn next(&mut self) -> Option<Self::Item> {
-        // FIXME: Already assumes no indexing with NA
-        let i = self.iter.next()?.unwrap();
-        Some(self.values[i].clone())
+        // indexing with NA produces a missing element
+        let Some(i) = self.iter.next()? else {
+            return Some(T::from_dense(None));
+        };
+
+        match &self.elements {
+            Elements::Values(values) => values.get(i),
+            Elements::Vec(values) => values.get(i).cloned(),
+            Elements::Seq(seq) => seq.get(i),
+            Elements::Lazy(lazy) => lazy.get(i),
+        }
     }
 }
 
-pub struct IterablePairs<T> {
-    values: Rc<Vec<T>>,
+pub struct IterablePairs<T: Element> {
+    values: Rc<Values<T>>,
     names: Option<Rc<Vec<Character>>>,
     iter: Box<dyn Iterator<Item = Option<usize>>>,
 }
 
-impl<T: Clone> Iterator for IterablePairs<T> {
+impl<T: Element> Iterator for IterablePairs<T> {
     type Item = (Character, T);
     fn next(&mut self) -> Option<Self::Item> {
-        // FIXME: Already assumes no indexing with NA
-        let i = self.iter.next()?.unwrap();
-        let value = self.values[i].clone();
+        // indexing with NA produces a missing, unnamed element
+        let Some(i) = self.iter.next()? else {
+            return Some((Character::NA, T::from_dense(None)));
+        };
+
+        let value = self.values.get(i)?;
         let name = if let Some(names) = &self.names {
             names[i].clone()
         } else {
@@ -310,7 +453,7 @@ impl<T: Clone> Iterator for IterablePairs<T> {
     }
 }
 
-impl<T: Clone + Default> Rep<T> {
+impl<T: Element + Default> Rep<T> {
     /// Create an empty vector
     ///
     /// The primary use case for this function is to support testing, and there
@@ -331,21 +474,23 @@ impl<T: Clone + Default> Rep<T> {
     ///
     pub fn new() -> Self {
         Rep::Subset(
-            Vec::new().into(),
+            Values::new().into(),
             Subsets(Vec::new()),
             Some(Naming::default()),
+            None,
         )
     }
 
     /// Whether the vector representation has names.
     pub fn is_named(&self) -> bool {
-        matches!(self, Rep::Subset(.., Some(_)))
+        matches!(self, Rep::Subset(_, _, Some(_), _))
     }
 
     /// Return the names of the vector if there are any.
     pub fn names(&self) -> Option<CowObj<Vec<Character>>> {
         match self.clone() {
-            Rep::Subset(_, s, n) => {
+            Rep::Seq(..) | Rep::Lazy(..) => None,
+            Rep::Subset(_, s, n, _) => {
                 if s.is_empty() {
                     n.map(|n| n.clone().names)
                 } else if n.is_some() {
@@ -365,7 +510,9 @@ impl<T: Clone + Default> Rep<T> {
     /// Change a value at the location given by `subset` to the provided `value`.
     /// If the `subset` does not have length `1`, an error is returned.
     pub fn set_subset(&mut self, subset: Subset, value: T) -> Result<T, Signal> {
+        self.materialize_computed()?;
         match &self {
+            Rep::Seq(..) | Rep::Lazy(..) => unreachable!(),
             Rep::Subset(..) => {
                 let err = Error::Other("subset must have length 1".to_string());
 
@@ -384,7 +531,7 @@ impl<T: Clone + Default> Rep<T> {
                 }
                 .unwrap();
 
-                self.with_inner_mut(|v| v[i] = value.clone());
+                self.with_inner_mut(|v| v.set(i, value.clone()));
                 Ok(value.clone())
             }
         }
@@ -396,11 +543,13 @@ impl<T: Clone + Default> Rep<T> {
     /// Directly getting an iterator is not possible due to lifetime issues.
     pub fn values_ref(&self) -> IntoIterableRefValues<T> {
         match self.clone() {
+            Rep::Seq(..) | Rep::Lazy(..) => self.materialize_bounded().values_ref(),
             Rep::Subset(values, ..) => {
                 let iter = Box::new(self.iter_subset_indices());
                 let values = values.inner_rc();
+                let elements = collect_elements(&values);
 
-                IntoIterableRefValues { values, na_value: T::default(), iter }
+                IntoIterableRefValues { values, elements, na_value: T::default(), iter }
             }
         }
     }
@@ -412,7 +561,8 @@ impl<T: Clone + Default> Rep<T> {
     /// Directly getting an iterator is not possible due to lifetime issues.
     pub fn names_ref(&self) -> Option<IntoIterableRefNames> {
         match self.clone() {
-            Rep::Subset(.., naming) => {
+            Rep::Seq(..) | Rep::Lazy(..) => None,
+            Rep::Subset(_, _, naming, _) => {
                 let iter = Box::new(self.iter_subset_indices());
                 let naming = naming?;
                 let names = naming.names.inner_rc();
@@ -428,13 +578,16 @@ impl<T: Clone + Default> Rep<T> {
     /// Directly getting an iterator is not possible due to lifetime issues.
     pub fn pairs_ref(&self) -> IntoIterableRefPairs<T> {
         match self.clone() {
-            Rep::Subset(values, _, maybe_naming) => {
+            Rep::Seq(..) | Rep::Lazy(..) => self.materialize_bounded().pairs_ref(),
+            Rep::Subset(values, _, maybe_naming, _) => {
                 let iter = Box::new(self.iter_subset_indices());
                 let values = values.inner_rc();
+                let elements = collect_elements(&values);
                 let names = maybe_naming.map(|x| x.names.inner_rc());
 
                 IntoIterableRefPairs {
                     values,
+                    elements,
                     names,
                     na_value: T::default(),
                     na_name: Character::NA,
@@ -447,7 +600,8 @@ impl<T: Clone + Default> Rep<T> {
     /// Iterate over (owned) pairs of names and values (`(String, T)`).
     pub fn iter_pairs(&self) -> IterablePairs<T> {
         match self.clone() {
-            Rep::Subset(values, _, maybe_naming) => {
+            Rep::Seq(..) | Rep::Lazy(..) => self.materialize_bounded().iter_pairs(),
+            Rep::Subset(values, _, maybe_naming, _) => {
                 let iter = Box::new(self.iter_subset_indices());
                 let values = values.inner_rc();
                 let names = maybe_naming.map(|x| x.names.inner_rc());
@@ -462,7 +616,18 @@ impl<T: Clone + Default> Rep<T> {
         match self.clone() {
             Rep::Subset(values, ..) => {
                 let iter = Box::new(self.iter_subset_indices());
-                IterableValues { values: values.inner_rc(), iter }
+                IterableValues {
+                    elements: Elements::Values(values.inner_rc()),
+                    iter,
+                }
+            }
+            Rep::Seq(seq, _) => {
+                let iter = Box::new(self.iter_subset_indices());
+                IterableValues { elements: Elements::Seq(seq), iter }
+            }
+            Rep::Lazy(lazy, _) => {
+                let iter = Box::new(self.iter_subset_indices());
+                IterableValues { elements: Elements::Lazy(lazy), iter }
             }
         }
     }
@@ -470,11 +635,12 @@ impl<T: Clone + Default> Rep<T> {
     /// Iterate over the names of the vector (if they exist).
     pub fn iter_names(&self) -> Option<IterableValues<Character>> {
         match self.clone() {
-            Rep::Subset(.., maybe_naming) => {
+            Rep::Seq(..) | Rep::Lazy(..) => None,
+            Rep::Subset(_, _, maybe_naming, _) => {
                 let iter = Box::new(self.iter_subset_indices());
                 let names = maybe_naming.map(|x| x.names.inner_rc())?;
 
-                Some(IterableValues { values: names, iter })
+                Some(IterableValues { elements: Elements::Vec(names), iter })
             }
         }
     }
@@ -484,9 +650,14 @@ impl<T: Clone + Default> Rep<T> {
     }
 
     /// Push a named `value` with a given `name` onto the `Rep<T>`.
-    pub fn push_named(&mut self, name: OptionNA<String>, value: T) {
+    pub fn push_named(&mut self, name: Character, value: T) {
+        if self.is_computed() {
+            *self = self.materialize_bounded();
+        }
+
         match self {
-            Rep::Subset(values, Subsets(subsets), maybe_naming) => match subsets.as_slice() {
+            Rep::Seq(..) | Rep::Lazy(..) => unreachable!(),
+            Rep::Subset(values, Subsets(subsets), maybe_naming, _) => match subsets.as_slice() {
                 [] => {
                     values.with_inner_mut(|values| values.push(value));
                     if let Some(naming) = maybe_naming {
@@ -508,9 +679,14 @@ impl<T: Clone + Default> Rep<T> {
 
     pub fn iter_subset_indices(&self) -> Box<dyn Iterator<Item = Option<usize>>> {
         match self.clone() {
-            Rep::Subset(vals, subsets, maybe_naming) => {
+            Rep::Seq(seq, _) => match seq.len {
+                Some(len) => Box::new((0_usize..len).map(Some)),
+                None => Box::new((0_usize..).map(Some)),
+            },
+            Rep::Lazy(lazy, _) => Box::new((0_usize..lazy.len).map(Some)),
+            Rep::Subset(vals, subsets, maybe_naming, _) => {
                 if subsets.is_empty() {
-                    return Box::new((0_usize..vals.len()).map(Some));
+                    return Box::new((0_usize..vals.borrow().len()).map(Some));
                 }
 
                 if let Some(naming) = maybe_naming {
@@ -524,7 +700,7 @@ impl<T: Clone + Default> Rep<T> {
 
     /// Reindex the mapping from names to indices.
     pub fn reindex(&mut self) {
-        if let Rep::Subset(.., Some(naming)) = self {
+        if let Rep::Subset(_, _, Some(naming), _) = self {
             naming.map.with_inner_mut(|map| {
                 map.drain();
 
@@ -549,15 +725,16 @@ impl<T: Clone + Default> Rep<T> {
             None
         };
         Rep::Subset(
-            CowObj::from(Vec::with_capacity(capacity)),
+            CowObj::from(Values::with_capacity(capacity)),
             Subsets::default(),
             naming,
+            None,
         )
     }
 
     pub fn dedup_last(self) -> Self {
         match self {
-            Rep::Subset(values, subsets, Some(naming)) => {
+            Rep::Subset(values, subsets, Some(naming), attrs) => {
                 naming.with_inner_mut(|map, names| {
                     let mut dups: Vec<usize> = map
                         .iter()
@@ -581,32 +758,81 @@ impl<T: Clone + Default> Rep<T> {
                         indices.drain(0..(indices.len()));
                     }
                 });
-                Rep::Subset(values, subsets, Some(naming))
+                Rep::Subset(values, subsets, Some(naming), attrs)
             }
-            Rep::Subset(.., None) => self,
+            Rep::Subset(_, _, None, _) | Rep::Seq(..) | Rep::Lazy(..) => self,
         }
     }
 
     pub fn set_names(&self, names: CowObj<Vec<Character>>) -> Self {
         match self {
-            Rep::Subset(v, s, _) => Rep::Subset(v.clone(), s.clone(), Option::Some(names.into())),
+            Rep::Seq(..) | Rep::Lazy(..) => self.materialize_bounded().set_names(names),
+            Rep::Subset(v, s, _, a) => {
+                Rep::Subset(v.clone(), s.clone(), Option::Some(names.into()), a.clone())
+            }
+        }
+    }
+
+    /// Remove the names of the vector.
+    pub fn without_names(&self) -> Self {
+        if self.is_computed() {
+            return self.clone();
+        }
+
+        match self.materialize_bounded() {
+            Rep::Subset(v, s, _, a) => Rep::Subset(v, s, None, a),
+            Rep::Seq(..) | Rep::Lazy(..) => unreachable!(),
         }
     }
 
     /// Access a lazy copy of the internal vector data
-    pub fn inner(&self) -> CowObj<Vec<T>> {
-        match self.materialize() {
+    pub fn inner(&self) -> CowObj<Values<T>> {
+        match self.materialize_bounded() {
             Rep::Subset(v, ..) => v.clone(),
+            Rep::Seq(..) | Rep::Lazy(..) => unreachable!(),
+        }
+    }
+
+    /// A function computing the element at an index, used to fuse the vector
+    /// into lazy elementwise operations. Values are captured as they are now,
+    /// such that later modifications don't affect the lazy result.
+    ///
+    /// Returns the function along with the number of operations it fuses.
+    /// Lazy vectors at the maximum depth are computed rather than fused.
+    fn elements(&self) -> (Rc<dyn Fn(usize) -> T>, usize)
+    where
+        T: 'static,
+    {
+        match self {
+            Rep::Lazy(lazy, _) if lazy.depth < Lazy::<T>::MAX_DEPTH => (lazy.elem(), lazy.depth),
+            Rep::Seq(seq, _) => {
+                let seq = *seq;
+                (Rc::new(move |i| seq.get(i).unwrap_or_default()), 0)
+            }
+            Rep::Subset(..) | Rep::Lazy(..) => {
+                let values = self.inner().inner_rc();
+                if values.has_na() {
+                    (Rc::new(move |i| values.get(i).unwrap_or_default()), 0)
+                } else {
+                    // without missing values, elements are read straight from the dense values
+                    (
+                        Rc::new(move |i| T::from_dense(Some(values.dense()[i].clone()))),
+                        0,
+                    )
+                }
+            }
         }
     }
 
     /// Get mutable access to the internal vector through the passed closure.
     pub fn with_inner_mut<F, R>(&self, f: F) -> R
     where
-        F: FnOnce(&mut Vec<T>) -> R,
+        F: FnOnce(&mut Values<T>) -> R,
     {
         match self {
             Rep::Subset(v, ..) => v.with_inner_mut(f),
+            // computed vectors need to be materialized before they can be modified in place
+            Rep::Seq(..) | Rep::Lazy(..) => self.materialize_bounded().with_inner_mut(f),
         }
     }
 
@@ -615,21 +841,36 @@ impl<T: Clone + Default> Rep<T> {
     /// Introduce a new subset into the aggregate list of subset indices.
     pub fn subset(&self, subset: Subset) -> Self {
         match self {
-            Rep::Subset(v, Subsets(subsets), n) => {
+            Rep::Subset(v, Subsets(subsets), n, _) => {
                 let mut subsets = subsets.clone();
                 subsets.push(subset);
-                Rep::Subset(v.view_mut(), Subsets(subsets), n.clone())
+                Rep::Subset(v.view_mut(), Subsets(subsets), n.clone(), None)
             }
+            Rep::Seq(seq, _) => match subset {
+                // ranges of sequences are themselves sequences
+                Subset::Range(range) => Rep::Seq(seq.slice(range), None),
+                Subset::Names(_) => self.materialize_bounded().subset(subset),
+                // otherwise, only the selected elements are computed
+                _ => subset_computed(subset, self.len(), |i| seq.get(i)),
+            },
+            Rep::Lazy(lazy, _) => match subset {
+                Subset::Names(_) => self.materialize_bounded().subset(subset),
+                _ => subset_computed(subset, lazy.len, |i| lazy.get(i)),
+            },
         }
     }
 
     /// The length of the vector.
+    ///
+    /// Unbounded sequences have a length of `usize::MAX`.
     pub fn len(&self) -> usize {
         match self {
-            Rep::Subset(v, Subsets(s), _) => match s.as_slice() {
+            Rep::Subset(v, Subsets(s), ..) => match s.as_slice() {
                 [] => v.borrow().len(),
-                _ => self.values_ref().iter().count(),
+                _ => self.iter_subset_indices().count(),
             },
+            Rep::Seq(seq, _) => seq.len.unwrap_or(usize::MAX),
+            Rep::Lazy(lazy, _) => lazy.len,
         }
     }
 
@@ -645,19 +886,45 @@ impl<T: Clone + Default> Rep<T> {
     ///
     pub fn get(&self, index: usize) -> Option<Rep<T>>
     where
-        T: Clone,
+        T: Element,
     {
         match self {
-            Rep::Subset(v, subsets, _) => {
+            Rep::Subset(v, subsets, ..) => {
                 let vb = v.borrow();
                 let index = subsets.get_index_at(index)?;
                 let elem = vb.get(index)?;
                 Some(Rep::Subset(
-                    vec![elem.clone()].into(),
+                    Values::from(vec![elem]).into(),
                     Subsets::new(),
-                    Option::Some(Naming::new()),
+                    Option::None,
+                    Option::None,
                 ))
             }
+            Rep::Seq(seq, _) => Some(Rep::Subset(
+                Values::from(vec![seq.get(index)?]).into(),
+                Subsets::new(),
+                Option::None,
+                Option::None,
+            )),
+            Rep::Lazy(lazy, _) => Some(Rep::Subset(
+                Values::from(vec![lazy.get(index)?]).into(),
+                Subsets::new(),
+                Option::None,
+                Option::None,
+            )),
+        }
+    }
+
+    /// Replace a sequence or lazy vector with its materialized values, such
+    /// that it can be modified in place. Unbounded sequences can't be
+    /// materialized.
+    pub fn materialize_computed(&mut self) -> Result<(), Signal> {
+        match self {
+            Rep::Seq(..) | Rep::Lazy(..) => {
+                *self = self.materialize()?;
+                Ok(())
+            }
+            Rep::Subset(..) => Ok(()),
         }
     }
 
@@ -668,9 +935,13 @@ impl<T: Clone + Default> Rep<T> {
     ///
     pub fn assign<R>(&mut self, value: Rep<R>) -> Result<Self, Signal>
     where
-        T: Clone + Default + From<R>,
-        R: Default + Clone,
+        T: Element + Default + From<R>,
+        R: Default + Element,
     {
+        self.materialize_computed()?;
+        let mut value = value;
+        value.materialize_computed()?;
+
         let l_indices = self.iter_subset_indices_exact();
         let mut r_indices = value.iter_subset_indices_exact();
 
@@ -686,13 +957,16 @@ impl<T: Clone + Default> Rep<T> {
                 .expect("No NA for subsetting");
             let elem = value.get_inner(index).expect("element should exist");
             match (self, value) {
-                (Rep::Subset(lv, ls, ln), Rep::Subset(..)) => {
+                (Rep::Seq(..) | Rep::Lazy(..), _) | (_, Rep::Seq(..) | Rep::Lazy(..)) => {
+                    unreachable!()
+                }
+                (Rep::Subset(lv, ls, ln, la), Rep::Subset(..)) => {
                     lv.with_inner_mut(|lvb| {
                         for li in l_indices {
-                            lvb[li.unwrap()] = elem.clone().into();
+                            lvb.set(li.unwrap(), elem.clone().into());
                         }
                     });
-                    return Ok(Rep::Subset(lv.clone(), ls.clone(), ln.clone()));
+                    return Ok(Rep::Subset(lv.clone(), ls.clone(), ln.clone(), la.clone()));
                 }
             }
         }
@@ -705,32 +979,44 @@ impl<T: Clone + Default> Rep<T> {
         }
 
         match (self, value) {
-            (Rep::Subset(lv, ls, ln), Rep::Subset(rv, ..)) => {
+            (Rep::Seq(..) | Rep::Lazy(..), _) | (_, Rep::Seq(..) | Rep::Lazy(..)) => {
+                unreachable!()
+            }
+            (Rep::Subset(lv, ls, ln, la), Rep::Subset(rv, ..)) => {
                 lv.with_inner_mut(|lvb| {
                     let rvc = rv.clone();
                     let rvb = rvc.borrow();
 
                     for (li, ri) in l_indices.zip(r_indices) {
                         match (li, ri) {
-                            (Some(li), None) => lvb[li] = T::default(),
-                            (Some(li), Some(ri)) => lvb[li] = rvb[ri % rvb.len()].clone().into(),
+                            (Some(li), None) => lvb.set(li, T::default()),
+                            (Some(li), Some(ri)) => {
+                                let value = rvb.get(ri % rvb.len()).expect("index is recycled");
+                                lvb.set(li, value.into())
+                            }
                             _ => (),
                         }
                     }
                 });
 
-                Ok(Rep::Subset(lv.clone(), ls.clone(), ln.clone()))
+                Ok(Rep::Subset(lv.clone(), ls.clone(), ln.clone(), la.clone()))
             }
         }
     }
 
     /// Return the only value if the vector has length 1.
     pub fn as_scalar(&self) -> Option<T> {
-        let mut into_iter = self.values_ref();
-        let mut iter = into_iter.iter();
+        match self {
+            Rep::Seq(seq, _) => return seq.len.filter(|&len| len == 1).and_then(|_| seq.get(0)),
+            Rep::Lazy(lazy, _) if lazy.len == 1 => return lazy.get(0),
+            Rep::Lazy(..) => return None,
+            Rep::Subset(..) => (),
+        }
+
+        let mut iter = self.iter_values();
         if let Some(x) = iter.next() {
             if iter.next().is_none() {
-                return Some(x.clone());
+                return Some(x);
             }
         };
         None
@@ -738,13 +1024,30 @@ impl<T: Clone + Default> Rep<T> {
 
     /// Materialize a Vector
     ///
-    /// Apply subsets and clone values into a new vector.
-    pub fn materialize(&self) -> Self
-    where
-        T: Clone,
-    {
+    /// Apply subsets and clone values into a new vector. Unbounded sequences
+    /// can't be materialized.
+    pub fn materialize(&self) -> Result<Self, Signal> {
+        match self {
+            Rep::Seq(seq, _) if seq.is_unbounded() => Error::UnboundedSequence.into(),
+            _ => Ok(self.materialize_bounded()),
+        }
+    }
+
+    /// Materialize a vector within an operation that can't fail
+    ///
+    /// Builtins reject unbounded sequences before they reach operations that
+    /// need the values of a vector, such that only bounded vectors get here.
+    fn materialize_bounded(&self) -> Self {
         match self {
-            Rep::Subset(v, subsets, naming) => {
+            Rep::Seq(seq, attrs) => {
+                let values: Values<T> = seq.iter().collect();
+                Rep::Subset(values.into(), Subsets(vec![]), Option::None, attrs.clone())
+            }
+            Rep::Lazy(lazy, attrs) => {
+                let values: Values<T> = lazy.iter().collect();
+                Rep::Subset(values.into(), Subsets(vec![]), Option::None, attrs.clone())
+            }
+            Rep::Subset(v, subsets, naming, attrs) => {
                 // early exit when there is nothing to do
                 match subsets {
                     Subsets(s) => {
@@ -759,17 +1062,22 @@ impl<T: Clone + Default> Rep<T> {
                     let vb = &**vc.borrow();
                     let iter = self.iter_subset_indices();
                     // TODO(performance): use size hints
-                    let mut values: Vec<T> = Vec::new();
+                    let mut values: Values<T> = Values::new();
                     let names = &**naming.names.borrow();
                     let mut new_naming = Naming::new();
                     for i in iter {
-                        values.push(vb[i.unwrap()].clone());
+                        values.push(vb.get(i.unwrap()).expect("index is within values"));
                         new_naming.push(names[i.unwrap()].clone())
                     }
-                    Rep::Subset(values.into(), Subsets(vec![]), Some(new_naming))
+                    Rep::Subset(
+                        values.into(),
+                        Subsets(vec![]),
+                        Some(new_naming),
+                        attrs.clone(),
+                    )
                 } else {
-                    let values: Vec<T> = self.iter_values().collect();
-                    Rep::Subset(values.into(), Subsets(vec![]), Option::None)
+                    let values: Values<T> = self.iter_values().collect();
+                    Rep::Subset(values.into(), Subsets(vec![]), Option::None, attrs.clone())
                 }
             }
         }
@@ -803,6 +1111,22 @@ impl<T: Clone + Default> Rep<T> {
         T::is_integer()
     }
 
+    /// See [Self::is_double] for more information
+    pub fn is_complex(&self) -> bool
+    where
+        T: AtomicMode,
+    {
+        T::is_complex()
+    }
+
+    /// See [Self::is_double] for more information
+    pub fn is_raw(&self) -> bool
+    where
+        T: AtomicMode,
+    {
+        T::is_raw()
+    }
+
     /// See [Self::is_double] for more information
     pub fn is_character(&self) -> bool
     where
@@ -838,16 +1162,22 @@ impl<T: Clone + Default> Rep<T> {
     pub fn as_mode<Mode>(&self) -> Rep<Mode>
     where
         T: CoercibleInto<Mode>,
-        Mode: Clone,
+        Mode: Element,
     {
         match self {
-            Rep::Subset(v, subsets, naming) => {
+            Rep::Seq(..) | Rep::Lazy(..) => self.materialize_bounded().as_mode(),
+            Rep::Subset(v, subsets, naming, attrs) => {
                 let vc = v.clone();
                 let vb = vc.borrow();
 
-                let num_vec: Vec<Mode> = vb.iter().map(|i| (*i).clone().coerce_into()).collect();
+                let num_vec: Values<Mode> = vb.iter().map(|i| i.coerce_into()).collect();
 
-                Rep::Subset(num_vec.into(), subsets.clone(), naming.clone())
+                Rep::Subset(
+                    num_vec.into(),
+                    subsets.clone(),
+                    naming.clone(),
+                    attrs.clone(),
+                )
             }
         }
     }
@@ -876,6 +1206,22 @@ impl<T: Clone + Default> Rep<T> {
         self.as_mode::<Double>()
     }
 
+    /// See [Self::as_mode] for more information
+    pub fn as_complex(&self) -> Rep<Complex>
+    where
+        T: CoercibleInto<Complex>,
+    {
+        self.as_mode::<Complex>()
+    }
+
+    /// See [Self::as_mode] for more information
+    pub fn as_raw(&self) -> Rep<Raw>
+    where
+        T: CoercibleInto<Raw>,
+    {
+        self.as_mode::<Raw>()
+    }
+
     /// See [Self::as_mode] for more information
     pub fn as_character(&self) -> Rep<Character>
     where
@@ -886,14 +1232,16 @@ impl<T: Clone + Default> Rep<T> {
 
     pub fn get_inner(&self, index: usize) -> Option<T> {
         match self {
-            Rep::Subset(v, subsets, maybe_naming) => {
+            Rep::Seq(seq, _) => seq.get(index),
+            Rep::Lazy(lazy, _) => lazy.get(index),
+            Rep::Subset(v, subsets, maybe_naming, _) => {
                 if maybe_naming.is_some() {
                     // TODO(NOW)
                     unimplemented!()
                 }
                 let vb = v.borrow();
                 let index = subsets.get_index_at(index)?;
-                vb.get(index).cloned()
+                vb.get(index)
             }
         }
     }
@@ -919,7 +1267,8 @@ impl Iterator for ExactIterSubsetIndices {
 
 impl<T> TryInto<bool> for Rep<OptionNA<T>>
 where
-    OptionNA<T>: AtomicMode + Clone + CoercibleInto<OptionNA<bool>>,
+    T: Clone + Default + 'static,
+    OptionNA<T>: AtomicMode + CoercibleInto<OptionNA<bool>>,
 {
     type Error = ();
     fn try_into(self) -> Result<bool, Self::Error> {
@@ -943,96 +1292,188 @@ impl From<Vec<Character>> for Naming {
     }
 }
 
-impl<T: Clone> From<CowObj<Vec<T>>> for Rep<T> {
-    fn from(value: CowObj<Vec<T>>) -> Self {
-        Rep::Subset(value, Subsets::default(), Option::None)
+impl<T: Element> From<CowObj<Values<T>>> for Rep<T> {
+    fn from(value: CowObj<Values<T>>) -> Self {
+        Rep::Subset(value, Subsets::default(), Option::None, Option::None)
     }
 }
 
-impl<T: Clone> From<Vec<(Option<String>, T)>> for Rep<T> {
+impl<T: Element> From<Vec<(Option<String>, T)>> for Rep<T> {
     fn from(value: Vec<(Option<String>, T)>) -> Self {
         let mut names = Vec::with_capacity(value.len());
-        let mut values = Vec::with_capacity(value.len());
+        let mut values = Values::with_capacity(value.len());
         for (k, v) in value.into_iter() {
-            names.push(k.map_or(Character::NA, Character::Some));
+            names.push(k.map_or(Character::NA, |k| Character::Some(k.into())));
             values.push(v)
         }
         let naming = Naming::from(names);
-        Rep::Subset(values.into(), Subsets::default(), Some(naming))
+        Rep::Subset(values.into(), Subsets::default(), Some(naming), None)
     }
 }
 
 impl From<Vec<OptionNA<f64>>> for Rep<Double> {
     fn from(value: Vec<OptionNA<f64>>) -> Self {
-        let value: Vec<_> = value.into_iter().map(|i| i.coerce_into()).collect();
-        Rep::Subset(value.into(), Subsets(Vec::new()), Option::None)
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
     }
 }
 
 impl From<Vec<f64>> for Rep<Double> {
     fn from(value: Vec<f64>) -> Self {
-        let value: Vec<_> = value.into_iter().map(|i| i.coerce_into()).collect();
-        Rep::Subset(value.into(), Subsets(Vec::new()), Option::None)
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
     }
 }
 
 impl From<Vec<OptionNA<i32>>> for Rep<Integer> {
     fn from(value: Vec<OptionNA<i32>>) -> Self {
-        let value: Vec<_> = value.into_iter().map(|i| i.coerce_into()).collect();
-        Rep::Subset(value.into(), Subsets(Vec::new()), Option::None)
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
     }
 }
 
 impl From<Vec<i32>> for Rep<Integer> {
     fn from(value: Vec<i32>) -> Self {
-        let value: Vec<_> = value.into_iter().map(|i| i.coerce_into()).collect();
-        Rep::Subset(value.into(), Subsets(Vec::new()), Option::None)
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
     }
 }
 
 impl From<Vec<OptionNA<bool>>> for Rep<Logical> {
     fn from(value: Vec<OptionNA<bool>>) -> Self {
-        let value: Vec<_> = value.into_iter().map(|i| i.coerce_into()).collect();
-        Rep::Subset(value.into(), Subsets(Vec::new()), Option::None)
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
     }
 }
 
 impl From<Vec<bool>> for Rep<Logical> {
     fn from(value: Vec<bool>) -> Self {
-        let value: Vec<_> = value.into_iter().map(|i| i.coerce_into()).collect();
-        Rep::Subset(value.into(), Subsets(Vec::new()), Option::None)
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
+    }
+}
+
+impl From<Vec<OptionNA<Complex64>>> for Rep<Complex> {
+    fn from(value: Vec<OptionNA<Complex64>>) -> Self {
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
+    }
+}
+
+impl From<Vec<Complex64>> for Rep<Complex> {
+    fn from(value: Vec<Complex64>) -> Self {
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
+    }
+}
+
+impl From<Vec<OptionNA<u8>>> for Rep<Raw> {
+    fn from(value: Vec<OptionNA<u8>>) -> Self {
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
+    }
+}
+
+impl From<Vec<u8>> for Rep<Raw> {
+    fn from(value: Vec<u8>) -> Self {
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
     }
 }
 
-impl From<Vec<OptionNA<String>>> for Rep<Character> {
-    fn from(value: Vec<OptionNA<String>>) -> Self {
-        let value: Vec<_> = value.into_iter().map(|i| i.coerce_into()).collect();
-        Rep::Subset(value.into(), Subsets(Vec::new()), Option::None)
+impl From<Vec<Character>> for Rep<Character> {
+    fn from(value: Vec<Character>) -> Self {
+        let value: Values<_> = value.into_iter().map(|i| i.coerce_into()).collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
     }
 }
 
 impl From<Vec<String>> for Rep<Character> {
     fn from(value: Vec<String>) -> Self {
-        let value: Vec<_> = value.into_iter().map(|i| i.coerce_into()).collect();
-        Rep::Subset(value.into(), Subsets(Vec::new()), Option::None)
+        let value: Values<_> = value
+            .into_iter()
+            .map(|i| Character::Some(i.into()))
+            .collect();
+        Rep::Subset(
+            value.into(),
+            Subsets(Vec::new()),
+            Option::None,
+            Option::None,
+        )
     }
 }
 
 impl<F, T> From<(Vec<F>, Subsets)> for Rep<T>
 where
     Rep<T>: From<Vec<F>>,
-    T: Clone,
+    T: Element,
 {
     fn from(value: (Vec<F>, Subsets)) -> Self {
         match Self::from(value.0) {
-            Rep::Subset(v, ..) => Rep::Subset(v, value.1, Option::None),
+            Rep::Subset(v, ..) => Rep::Subset(v, value.1, Option::None, Option::None),
+            Rep::Seq(..) | Rep::Lazy(..) => unreachable!(),
         }
     }
 }
 
 impl<T> Display for Rep<T>
 where
-    T: AtomicMode + Debug + Default + Clone,
+    T: AtomicMode + Debug + Default + Element,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let n = self.len();
@@ -1046,11 +1487,21 @@ where
             if self.is_logical() {
                 return write!(f, "logical(0)");
             }
+            if self.is_complex() {
+                return write!(f, "complex(0)");
+            }
+            if self.is_raw() {
+                return write!(f, "raw(0)");
+            }
             if self.is_character() {
                 return write!(f, "character(0)");
             }
         }
-        let nlen = format!("{}", n).len();
+        // unbounded sequences are displayed by as many elements as fit on screen
+        let nlen = match self.is_unbounded() {
+            true => format!("{}", 20 * 80).len(),
+            false => format!("{}", n).len(),
+        };
         // calculate how many characters are printed per value.
         // The iteraror yields the characters needed for a specific item.
         fn element_width(iter: impl Iterator<Item = usize>) -> usize {
@@ -1065,11 +1516,8 @@ where
         }
 
         if !self.is_named() {
-            let elt_width =
-                element_width(self.values_ref().iter().map(|x| format!("{:?}", x).len()));
-
-            let mut values_ref = self.values_ref();
-            let x_strs = values_ref.iter().map(|xi| format!("{:?}", xi));
+            let elt_width = element_width(self.iter_values().map(|x| format!("{:?}", x).len()));
+            let x_strs = self.iter_values().map(|xi| format!("{:?}", xi));
 
             let mut col = 0;
             let gutterlen = 2 + nlen + 1;
@@ -1108,7 +1556,9 @@ where
                     }
                 })?;
 
-            if n > maxprint {
+            if self.is_unbounded() {
+                write!(f, "\n[ omitting infinitely many entries ]")?;
+            } else if n > maxprint {
                 write!(f, "\n[ omitting {} entries ]", n - maxprint)?;
             }
         } else {
@@ -1154,29 +1604,35 @@ where
 
 impl<L, LNum, O> std::ops::Neg for Rep<L>
 where
-    L: AtomicMode + Default + Clone + MinimallyNumeric<As = LNum> + CoercibleInto<LNum>,
+    L: AtomicMode + Default + Element + MinimallyNumeric<As = LNum> + CoercibleInto<LNum>,
     LNum: std::ops::Neg<Output = O>,
     Rep<O>: From<Vec<O>>,
-    O: Clone,
+    O: Element,
 {
     type Output = Result<Rep<O>, Signal>;
     fn neg(self) -> Self::Output {
+        if self.is_unbounded() {
+            return Error::UnboundedSequence.into();
+        }
+
         let result: Vec<O> = self
             .iter_values()
             .map(|x| -(CoercibleInto::<LNum>::coerce_into(x)))
             .collect();
-        Ok(result.into())
+        Ok(Rep::from(result).with_attributes(self.attributes()))
     }
 }
 
 impl<L, R, C, O, LNum, RNum> std::ops::Add<Rep<R>> for Rep<L>
 where
-    L: AtomicMode + Default + Clone + MinimallyNumeric<As = LNum> + CoercibleInto<LNum>,
-    R: AtomicMode + Default + Clone + MinimallyNumeric<As = RNum> + CoercibleInto<RNum>,
+    L: AtomicMode + Default + Element + MinimallyNumeric<As = LNum> + CoercibleInto<LNum> + 'static,
+    R: AtomicMode + Default + Element + MinimallyNumeric<As = RNum> + CoercibleInto<RNum> + 'static,
     (LNum, RNum): CommonNum<Common = C>,
-    C: Clone + std::ops::Add<Output = O> + Default,
+    C: Element + std::ops::Add<Output = O> + Default + From<O> + 'static,
     Rep<C>: From<Vec<O>>,
-    O: Clone + Default,
+    O: Clone + Default + 'static,
+    LNum: 'static,
+    RNum: 'static,
 {
     type Output = Result<Rep<C>, Signal>;
     fn add(self, rhs: Rep<R>) -> Self::Output {
@@ -1186,12 +1642,14 @@ where
 
 impl<L, R, C, O, LNum, RNum> std::ops::Sub<Rep<R>> for Rep<L>
 where
-    L: AtomicMode + Default + Clone + MinimallyNumeric<As = LNum> + CoercibleInto<LNum>,
-    R: AtomicMode + Default + Clone + MinimallyNumeric<As = RNum> + CoercibleInto<RNum>,
+    L: AtomicMode + Default + Element + MinimallyNumeric<As = LNum> + CoercibleInto<LNum> + 'static,
+    R: AtomicMode + Default + Element + MinimallyNumeric<As = RNum> + CoercibleInto<RNum> + 'static,
     (LNum, RNum): CommonNum<Common = C>,
-    C: Clone + std::ops::Sub<Output = O> + Default,
+    C: Element + std::ops::Sub<Output = O> + Default + From<O> + 'static,
     Rep<C>: From<Vec<O>>,
-    O: Clone + Default,
+    O: Clone + Default + 'static,
+    LNum: 'static,
+    RNum: 'static,
 {
     type Output = Result<Rep<C>, Signal>;
     fn sub(self, rhs: Rep<R>) -> Self::Output {
@@ -1201,12 +1659,14 @@ where
 
 impl<L, R, C, O, LNum, RNum> std::ops::Mul<Rep<R>> for Rep<L>
 where
-    L: AtomicMode + Default + Clone + MinimallyNumeric<As = LNum> + CoercibleInto<LNum>,
-    R: AtomicMode + Default + Clone + MinimallyNumeric<As = RNum> + CoercibleInto<RNum>,
+    L: AtomicMode + Default + Element + MinimallyNumeric<As = LNum> + CoercibleInto<LNum> + 'static,
+    R: AtomicMode + Default + Element + MinimallyNumeric<As = RNum> + CoercibleInto<RNum> + 'static,
     (LNum, RNum): CommonNum<Common = C>,
-    C: Clone + std::ops::Mul<Output = O> + Default,
+    C: Element + std::ops::Mul<Output = O> + Default + From<O> + 'static,
     Rep<C>: From<Vec<O>>,
-    O: Clone + Default,
+    O: Clone + Default + 'static,
+    LNum: 'static,
+    RNum: 'static,
 {
     type Output = Result<Rep<C>, Signal>;
     fn mul(self, rhs: Rep<R>) -> Self::Output {
@@ -1216,12 +1676,14 @@ where
 
 impl<L, R, C, O, LNum, RNum> std::ops::Div<Rep<R>> for Rep<L>
 where
-    L: AtomicMode + Default + Clone + MinimallyNumeric<As = LNum> + CoercibleInto<LNum>,
-    R: AtomicMode + Default + Clone + MinimallyNumeric<As = RNum> + CoercibleInto<RNum>,
+    L: AtomicMode + Default + Element + MinimallyNumeric<As = LNum> + CoercibleInto<LNum> + 'static,
+    R: AtomicMode + Default + Element + MinimallyNumeric<As = RNum> + CoercibleInto<RNum> + 'static,
     (LNum, RNum): CommonNum<Common = C>,
-    C: Clone + std::ops::Div<Output = O> + Default,
+    C: Element + std::ops::Div<Output = O> + Default + From<O> + 'static,
     Rep<C>: From<Vec<O>>,
-    O: Clone + Default,
+    O: Clone + Default + 'static,
+    LNum: 'static,
+    RNum: 'static,
 {
     type Output = Result<Rep<C>, Signal>;
     fn div(self, rhs: Rep<R>) -> Self::Output {
@@ -1231,12 +1693,14 @@ where
 
 impl<L, R, C, O, LNum, RNum> std::ops::Rem<Rep<R>> for Rep<L>
 where
-    L: AtomicMode + Default + Clone + MinimallyNumeric<As = LNum> + CoercibleInto<LNum>,
-    R: AtomicMode + Default + Clone + MinimallyNumeric<As = RNum> + CoercibleInto<RNum>,
+    L: AtomicMode + Default + Element + MinimallyNumeric<As = LNum> + CoercibleInto<LNum> + 'static,
+    R: AtomicMode + Default + Element + MinimallyNumeric<As = RNum> + CoercibleInto<RNum> + 'static,
     (LNum, RNum): CommonNum<Common = C>,
-    C: Clone + std::ops::Rem<Output = O> + Default,
+    C: Element + std::ops::Rem<Output = O> + Default + From<O> + 'static,
     Rep<C>: From<Vec<O>>,
-    O: Clone + Default,
+    O: Clone + Default + 'static,
+    LNum: 'static,
+    RNum: 'static,
 {
     type Output = Result<Rep<C>, Signal>;
     fn rem(self, rhs: Rep<R>) -> Self::Output {
@@ -1246,15 +1710,17 @@ where
 
 impl<L, R, O, LNum, RNum> Pow<Rep<R>> for Rep<L>
 where
-    L: AtomicMode + Default + Clone + MinimallyNumeric<As = LNum> + CoercibleInto<LNum>,
-    R: AtomicMode + Default + Clone + MinimallyNumeric<As = RNum> + CoercibleInto<RNum>,
+    L: AtomicMode + Default + Element + MinimallyNumeric<As = LNum> + CoercibleInto<LNum> + 'static,
+    R: AtomicMode + Default + Element + MinimallyNumeric<As = RNum> + CoercibleInto<RNum> + 'static,
     (LNum, RNum): CommonNum<Common = O>,
     O: Pow<O, Output = O>,
     Rep<O>: From<Vec<O>>,
     O: Default,
-    L: Clone,
-    R: Clone,
-    O: Clone,
+    L: Element,
+    R: Element,
+    O: Element + 'static,
+    LNum: 'static,
+    RNum: 'static,
 {
     type Output = Result<Rep<O>, Signal>;
     fn power(self, rhs: Rep<R>) -> Self::Output {
@@ -1264,8 +1730,8 @@ where
 
 impl<L, R> std::ops::BitOr<Rep<R>> for Rep<L>
 where
-    L: AtomicMode + Default + Clone + CoercibleInto<Logical>,
-    R: AtomicMode + Default + Clone + CoercibleInto<Logical>,
+    L: AtomicMode + Default + Element + CoercibleInto<Logical> + 'static,
+    R: AtomicMode + Default + Element + CoercibleInto<Logical> + 'static,
 {
     type Output = Result<Rep<Logical>, Signal>;
     fn bitor(self, rhs: Rep<R>) -> Self::Output {
@@ -1275,8 +1741,8 @@ where
 
 impl<L, R> std::ops::BitAnd<Rep<R>> for Rep<L>
 where
-    L: AtomicMode + Default + Clone + CoercibleInto<Logical>,
-    R: AtomicMode + Default + Clone + CoercibleInto<Logical>,
+    L: AtomicMode + Default + Element + CoercibleInto<Logical> + 'static,
+    R: AtomicMode + Default + Element + CoercibleInto<Logical> + 'static,
 {
     type Output = Result<Rep<Logical>, Signal>;
     fn bitand(self, rhs: Rep<R>) -> Self::Output {
@@ -1286,24 +1752,28 @@ where
 
 impl<L> std::ops::Not for Rep<L>
 where
-    L: AtomicMode + Default + Clone + CoercibleInto<Logical>,
+    L: AtomicMode + Default + Element + CoercibleInto<Logical>,
 {
     type Output = Result<Rep<Logical>, Signal>;
     fn not(self) -> Self::Output {
+        if self.is_unbounded() {
+            return Error::UnboundedSequence.into();
+        }
+
         let result: Vec<Logical> = self
             .iter_values()
             .map(|x| !(CoercibleInto::<Logical>::coerce_into(x)))
             .collect();
-        Ok(result.into())
+        Ok(Rep::from(result).with_attributes(self.attributes()))
     }
 }
 
 impl<L, R, C> VecPartialCmp<Rep<R>> for Rep<L>
 where
-    L: AtomicMode + Default + Clone + CoercibleInto<C> + Clone,
-    R: AtomicMode + Default + Clone + CoercibleInto<C> + Clone,
+    L: AtomicMode + Default + Element + CoercibleInto<C> + 'static,
+    R: AtomicMode + Default + Element + CoercibleInto<C> + 'static,
     (L, R): CommonCmp<Common = C>,
-    C: PartialOrd + Clone + Default,
+    C: PartialOrd + Element + Default + 'static,
 {
     type Output = Result<Rep<Logical>, Signal>;
 
@@ -1362,71 +1832,104 @@ where
     }
 }
 
+/// Compute only the elements selected by `subset` from a vector of length
+/// `len`, whose elements are computed on access by `get`.
+fn subset_computed<T, F>(subset: Subset, len: usize, get: F) -> Rep<T>
+where
+    T: Element + Default,
+    F: Fn(usize) -> Option<T>,
+{
+    let values: Values<T> = match subset {
+        Subset::Indices(indices) => indices
+            .iter()
+            .map(|i| match i {
+                OptionNA::Some(i) if i >= 0 => get(i as usize).unwrap_or_default(),
+                _ => T::default(),
+            })
+            .collect(),
+        _ => {
+            let indices = Box::new((0_usize..len).map(|i| (i, Some(i))));
+            subset
+                .filter(indices)
+                .map(|(_, i)| i.and_then(&get).unwrap_or_default())
+                .collect()
+        }
+    };
+
+    Rep::from(CowObj::from(values))
+}
+
 /// This function applies a function `g` to pairs from lhs and rhs.
 /// The function returns an error when the lengths are not compatible.
+///
+/// Attributes are copied from operands with the same length as the result,
+/// with those of `lhs` taking precedence.
 fn try_recycle_then<L, R, O, F, A>(lhs: Rep<L>, rhs: Rep<R>, g: F) -> Result<Rep<A>, Signal>
 where
-    L: Clone + Default,
-    R: Clone + Default,
+    L: Element + Default + 'static,
+    R: Element + Default + 'static,
     Rep<A>: From<Vec<O>>,
     O: Clone + Default,
-    A: Clone,
-    F: Fn(L, R) -> O,
+    A: Element + Default + From<O>,
+    F: Fn(L, R) -> O + 'static,
 {
-    match (lhs.as_scalar(), rhs.as_scalar()) {
-        (Some(l), Some(r)) => {
-            let result: Vec<O> = vec![g(l, r)];
-            Ok(Rep::from(result))
-        }
-        (Some(l), None) => {
-            let result: Vec<O> = repeat(l)
-                .zip(rhs.iter_values())
-                .map(|(l, r)| g(l, r))
-                .collect();
-            if result.is_empty() {
-                return Err(Signal::Error(Error::NonRecyclableLengths(1, 0)));
-            }
-            Ok(Rep::from(result))
-        }
-        (None, Some(r)) => {
-            let result: Vec<O> = lhs
-                .iter_values()
-                .zip(repeat(r))
-                .map(|(l, r)| g(l, r))
-                .collect();
-            if result.is_empty() {
-                return Err(Signal::Error(Error::NonRecyclableLengths(0, 1)));
-            }
-            Ok(Rep::from(result))
-        }
-        (None, None) => {
-            let mut lc = lhs.iter_values();
-            let mut rc = rhs.iter_values();
-
-            let max_size = std::cmp::max(lc.size_hint().0, rc.size_hint().0);
-
-            let mut result: Vec<O> = Vec::with_capacity(max_size);
-
-            loop {
-                match (lc.next(), rc.next()) {
-                    (Some(l), Some(r)) => result.push(g(l, r)),
-                    (Some(_), None) => {
-                        return Err(Signal::Error(Error::NonRecyclableLengths(
-                            result.len() + 1 + lc.count(),
-                            result.len(),
-                        )));
-                    }
-                    (None, Some(_)) => {
-                        return Err(Signal::Error(Error::NonRecyclableLengths(
-                            result.len(),
-                            result.len() + 1 + rc.count(),
-                        )));
-                    }
-                    (None, None) => return Ok(Rep::from(result)),
-                }
-            }
-        }
+    let (lattrs, rattrs) = (lhs.attributes(), rhs.attributes());
+    if lattrs.is_none() && rattrs.is_none() {
+        return recycle_then(lhs, rhs, g);
+    }
+
+    let (llen, rlen) = (lhs.len(), rhs.len());
+    let result = recycle_then(lhs, rhs, g)?;
+    let n = result.len();
+
+    let mut attrs = Attributes::new();
+    if let (Some(lattrs), true) = (lattrs, llen == n) {
+        attrs.merge(&lattrs);
     }
+    if let (Some(rattrs), true) = (rattrs, rlen == n) {
+        attrs.merge(&rattrs);
+    }
+
+    Ok(result.with_attributes(Some(attrs)))
+}
+
+fn recycle_then<L, R, O, F, A>(lhs: Rep<L>, rhs: Rep<R>, g: F) -> Result<Rep<A>, Signal>
+where
+    L: Element + Default + 'static,
+    R: Element + Default + 'static,
+    Rep<A>: From<Vec<O>>,
+    O: Clone + Default,
+    A: Element + From<O>,
+    F: Fn(L, R) -> O + 'static,
+{
+    if lhs.is_unbounded() || rhs.is_unbounded() {
+        return Error::UnboundedSequence.into();
+    }
+
+    // scalar operations are cheap enough to be computed immediately
+    if let (Some(l), Some(r)) = (lhs.as_scalar(), rhs.as_scalar()) {
+        let result: Vec<O> = vec![g(l, r)];
+        return Ok(Rep::from(result));
+    }
+
+    let (llen, rlen) = (lhs.len(), rhs.len());
+    match (llen, rlen) {
+        (1, n) | (n, 1) if n > 0 => (),
+        (l, r) if l == r => (),
+        (l, r) => return Err(Signal::Error(Error::NonRecyclableLengths(l, r))),
+    }
+
+    // elements are only computed once consumed, fusing with lazy operands
+    let ((l, ldepth), (r, rdepth)) = (lhs.elements(), rhs.elements());
+    let (lrecycled, rrecycled) = (llen == 1, rlen == 1);
+    let elem = move |i| {
+        let li = if lrecycled { 0 } else { i };
+        let ri = if rrecycled { 0 } else { i };
+        A::from(g(l(li), r(ri)))
+    };
+
+    let (len, depth) = (std::cmp::max(llen, rlen), std::cmp::max(ldepth, rdepth) + 1);
+    Ok(Rep::Lazy(Lazy::new(len, depth, elem), None))
 }
 
 fn try_binary_num_op<L, R, C, O, LNum, RNum, F>(
@@ -1435,16 +1938,17 @@ fn try_binary_num_op<L, R, C, O, LNum, RNum, F>(
     f: F,
 ) -> Result<Rep<C>, Signal>
 where
-    L: Default + Clone + MinimallyNumeric<As = LNum> + CoercibleInto<LNum>,
-    R: Default + Clone + MinimallyNumeric<As = RNum> + CoercibleInto<RNum>,
-    C: Default + Clone,
+    L: Default + Element + MinimallyNumeric<As = LNum> + CoercibleInto<LNum> + 'static,
+    R: Default + Element + MinimallyNumeric<As = RNum> + CoercibleInto<RNum> + 'static,
+    C: Default + Element + From<O> + 'static,
     (LNum, RNum): CommonNum<Common = C>,
     Rep<C>: From<Vec<O>>,
-    O: Clone + Default,
-    F: Fn(C, C) -> O,
-    C: Clone + Default,
+    O: Clone + Default + 'static,
+    F: Fn(C, C) -> O + 'static,
+    LNum: 'static,
+    RNum: 'static,
 {
-    try_recycle_then(lhs, rhs, |x, y| {
+    try_recycle_then(lhs, rhs, move |x, y| {
         let (c1, c2) = (
             CoercibleInto::<LNum>::coerce_into(x),
             CoercibleInto::<RNum>::coerce_into(y),
@@ -1454,16 +1958,27 @@ where
     })
 }
 
+/// Apply a bytewise operation to two raw vectors
+pub fn try_binary_raw_op<F>(lhs: Rep<Raw>, rhs: Rep<Raw>, f: F) -> Result<Rep<Raw>, Signal>
+where
+    F: Fn(u8, u8) -> u8 + 'static,
+{
+    try_recycle_then(lhs, rhs, move |x, y| match (x, y) {
+        (OptionNA::Some(x), OptionNA::Some(y)) => OptionNA::Some(f(x, y)),
+        _ => OptionNA::Some(0),
+    })
+}
+
 // FIXME(performance): equality with references for characters
 fn try_binary_cmp_op<L, R, C, F>(lhs: Rep<L>, rhs: Rep<R>, f: F) -> Result<Rep<Logical>, Signal>
 where
-    L: AtomicMode + Default + Clone + CoercibleInto<C> + Clone,
-    R: AtomicMode + Default + Clone + CoercibleInto<C> + Clone,
+    L: AtomicMode + Default + Element + CoercibleInto<C> + 'static,
+    R: AtomicMode + Default + Element + CoercibleInto<C> + 'static,
     (L, R): CommonCmp<Common = C>,
-    C: PartialOrd + Clone + Default,
-    F: Fn(Option<std::cmp::Ordering>) -> Logical,
+    C: PartialOrd + Element + Default + 'static,
+    F: Fn(Option<std::cmp::Ordering>) -> Logical + 'static,
 {
-    try_recycle_then(lhs, rhs, |x, y| {
+    try_recycle_then(lhs, rhs, move |x, y| {
         let c1: C = x.coerce_into();
         let c2: C = y.coerce_into();
         let ordering = c1.partial_cmp(&c2);
@@ -1473,11 +1988,11 @@ where
 
 pub fn try_binary_lgl_op<L, R, F>(lhs: Rep<L>, rhs: Rep<R>, f: F) -> Result<Rep<Logical>, Signal>
 where
-    L: AtomicMode + Default + Clone + CoercibleInto<Logical>,
-    R: AtomicMode + Default + Clone + CoercibleInto<Logical>,
-    F: Fn(Logical, Logical) -> Logical,
+    L: AtomicMode + Default + Element + CoercibleInto<Logical> + 'static,
+    R: AtomicMode + Default + Element + CoercibleInto<Logical> + 'static,
+    F: Fn(Logical, Logical) -> Logical + 'static,
 {
-    try_recycle_then(lhs, rhs, |x, y| {
+    try_recycle_then(lhs, rhs, move |x, y| {
         let (c1, c2) = (
             CoercibleInto::<Logical>::coerce_into(x),
             CoercibleInto::<Logical>::coerce_into(y),
@@ -1485,14 +2000,51 @@ where
         f(c1, c2)
     })
 }
+
 #[cfg(test)]
 mod test {
     use super::OptionNA::*;
     use crate::object::rep::Rep;
-    use crate::object::{types::*, OptionNA, VecPartialCmp};
+    use crate::object::{types::*, OptionNA, Sequence, VecPartialCmp};
     use crate::r;
     use crate::utils::SameType;
 
+    #[test]
+    fn arithmetic_is_lazy() {
+        let x = Rep::<Double>::from(vec![1.0, 2.0, 3.0]);
+        let y = ((x.clone() - Rep::<Double>::from(vec![2.0])).unwrap() * x).unwrap();
+        assert!(y.is_lazy());
+        assert_eq!(y, Rep::<Double>::from(vec![-1.0, 0.0, 3.0]));
+    }
+
+    #[test]
+    fn lazy_results_ignore_later_modification() {
+        let x = Rep::<Double>::from(vec![1.0, 2.0]);
+        let y = (x.clone() + Rep::<Double>::from(vec![1.0])).unwrap();
+        x.with_inner_mut(|v| v.set(0, Some(10.0)));
+        assert_eq!(y, Rep::<Double>::from(vec![2.0, 3.0]));
+    }
+
+    #[test]
+    fn long_lazy_chains_are_computed() {
+        let mut x = Rep::<Double>::from(vec![0.0, 1.0]);
+        for _ in 0..1000 {
+            x = (x + Rep::<Double>::from(vec![1.0])).unwrap();
+        }
+        assert!(
+            matches!(x, Rep::Lazy(ref lazy, _) if lazy.depth <= super::Lazy::<Double>::MAX_DEPTH)
+        );
+        assert_eq!(x, Rep::<Double>::from(vec![1000.0, 1001.0]));
+    }
+
+    #[test]
+    fn sequence_compares_to_vector() {
+        let x = Rep::from(Sequence::new(1.0, 1.0, Option::Some(3)));
+        assert_eq!(x, Rep::<Double>::from(vec![1.0, 2.0, 3.0]));
+        assert_eq!(x.subset((1..3).into()), Rep::<Double>::from(vec![2.0, 3.0]));
+        assert!(x.subset((1..3).into()).is_sequence());
+    }
+
     #[test]
     fn vector_add() {
         let x = Rep::<Integer>::from((1..=5).collect::<Vec<_>>());
@@ -1590,11 +2142,11 @@ mod test {
     fn test_iter_names() {
         // Create values with names
         let values_with_names = vec![
-            (Character::Some(String::from("a")), 1),
-            (Character::Some(String::from("b")), 2),
-            (Character::NA, 3),
-            (Character::Some(String::from("d")), 4),
-            (Character::NA, 5),
+            (Character::Some("a".into()), Some(1)),
+            (Character::Some("b".into()), Some(2)),
+            (Character::NA, Some(3)),
+            (Character::Some("d".into()), Some(4)),
+            (Character::NA, Some(5)),
         ];
 
         // Create Rep<Integer> from values with names
@@ -1636,7 +2188,7 @@ mod test {
 
         assert_eq!(
             x.next().unwrap(),
-            (Character::Some("a".to_string()), Double::Some(1.0))
+            (Character::Some("a".into()), Double::Some(1.0))
         );
         assert_eq!(x.next().unwrap(), (Character::NA, Double::Some(2.0)));
         assert_eq!(x.next(), None);
@@ -1696,7 +2248,7 @@ mod test {
         };
 
         assert_eq!(x.next().unwrap(), Character::NA);
-        assert_eq!(x.next().unwrap(), Character::Some("b".to_string()));
+        assert_eq!(x.next().unwrap(), Character::Some("b".into()));
         assert_eq!(x.next(), None);
     }
 
@@ -1713,7 +2265,7 @@ mod test {
         let mut x = x.iter();
 
         assert_eq!(x.next().unwrap(), &Character::NA);
-        assert_eq!(x.next().unwrap(), &Character::Some("b".to_string()));
+        assert_eq!(x.next().unwrap(), &Character::Some("b".into()));
         assert_eq!(x.next(), None);
     }
 
@@ -1761,7 +2313,7 @@ mod test {
         assert_eq!(x.next().unwrap(), (&Character::NA, &Double::Some(1.0)));
         assert_eq!(
             x.next().unwrap(),
-            (&Character::Some("b".to_string()), &Double::Some(2.0))
+            (&Character::Some("b".into()), &Double::Some(2.0))
         );
         assert_eq!(x.next(), None);
     }
@@ -1769,6 +2321,20 @@ mod test {
     use crate::error::Error;
     use crate::lang::Signal;
 
+    #[test]
+    fn unbounded_sequences_cant_be_materialized() {
+        use crate::object::Sequence;
+
+        let x = Rep::from(Sequence::new(1.0, 1.0, None));
+        assert_eq!(
+            x.materialize().unwrap_err(),
+            Signal::Error(Error::UnboundedSequence)
+        );
+
+        let x = Rep::from(Sequence::new(1.0, 1.0, Option::Some(3)));
+        assert_eq!(x.materialize().unwrap(), Rep::from(vec![1.0, 2.0, 3.0]));
+    }
+
     #[test]
     fn assign_recycle_incompatible() {
         let mut x = Rep::<Integer>::from(vec![1, 2, 3]);
@@ -1852,7 +2418,7 @@ mod test {
     #[test]
     fn materialize_after_subset() {
         let x = Rep::<Integer>::from(vec![10, 20, 30]);
-        let x1 = x.subset(vec![0, 2].into()).materialize();
+        let x1 = x.subset(vec![0, 2].into()).materialize().unwrap();
         let x2 = Rep::<Integer>::from(vec![10, 30]);
         assert_eq!(x1, x2);
     }
@@ -1861,21 +2427,15 @@ mod test {
         let x = Rep::<Integer>::from(vec![10, 20, 30]);
         x.set_names(
             vec![
-                Character::Some("a".to_string()),
-                Character::Some("b".to_string()),
-                Character::Some("c".to_string()),
+                Character::Some("a".into()),
+                Character::Some("b".into()),
+                Character::Some("c".into()),
             ]
             .into(),
         );
-        let x1 = x.subset(vec![0, 2].into()).materialize();
+        let x1 = x.subset(vec![0, 2].into()).materialize().unwrap();
         let x2 = Rep::<Integer>::from(vec![10, 30]);
-        x.set_names(
-            vec![
-                Character::Some("a".to_string()),
-                Character::Some("c".to_string()),
-            ]
-            .into(),
-        );
+        x.set_names(vec![Character::Some("a".into()), Character::Some("c".into())].into());
         assert_eq!(x1, x2);
     }
 }
@@ -0,0 +1,105 @@
+use std::ops::Range;
+
+use super::types::Double;
+use super::OptionNA;
+
+/// Arithmetic Sequences
+///
+/// A compact representation of the values `start + i * by` for `i` in
+/// `0..len`, such that ranges like `1:1e9` don't need to allocate their
+/// elements. Sequences without a length are unbounded, as in `1:Inf`.
+///
+#[derive(Debug)]
+pub struct Sequence<T> {
+    pub start: f64,
+    pub by: f64,
+    pub len: Option<usize>,
+    elem: fn(f64) -> T,
+}
+
+impl Sequence<Double> {
+    pub fn new(start: f64, by: f64, len: Option<usize>) -> Self {
+        Sequence { start, by, len, elem: OptionNA::Some }
+    }
+
+    /// The sum of the sequence's elements, without iterating over them, or
+    /// `None` if the sequence is unbounded
+    pub fn sum(&self) -> Option<f64> {
+        let n = self.len? as f64;
+        Some(n * self.start + self.by * n * (n - 1.0) / 2.0)
+    }
+}
+
+impl<T> Sequence<T> {
+    /// Whether the sequence has no end
+    pub fn is_unbounded(&self) -> bool {
+        self.len.is_none()
+    }
+
+    /// Get the element at `index`, if it is within the sequence
+    pub fn get(&self, index: usize) -> Option<T> {
+        match self.len {
+            Some(len) if index >= len => None,
+            _ => Some((self.elem)(self.start + index as f64 * self.by)),
+        }
+    }
+
+    /// Iterate over the elements of the sequence, indefinitely if unbounded
+    pub fn iter(&self) -> impl Iterator<Item = T> {
+        let seq = *self;
+        (0..self.len.unwrap_or(usize::MAX)).map(move |i| (seq.elem)(seq.start + i as f64 * seq.by))
+    }
+
+    /// A sub-sequence of the elements within `range`
+    ///
+    /// Ranges ending at `usize::MAX` keep unbounded sequences unbounded.
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        let len = match self.len {
+            Some(len) => Some(range.end.min(len).saturating_sub(range.start)),
+            None if range.end == usize::MAX => None,
+            None => Some(range.end.saturating_sub(range.start)),
+        };
+
+        Sequence {
+            start: self.start + range.start as f64 * self.by,
+            len,
+            ..*self
+        }
+    }
+}
+
+impl<T> Clone for Sequence<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Sequence<T> {}
+
+impl<T> PartialEq for Sequence<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start && self.by == other.by && self.len == other.len
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Sequence;
+    use crate::object::OptionNA;
+
+    #[test]
+    fn sequence_elements() {
+        let x = Sequence::new(1.0, 2.0, Some(3));
+        assert_eq!(x.get(2), Some(OptionNA::Some(5.0)));
+        assert_eq!(x.get(3), None);
+        assert_eq!(x.iter().collect::<Vec<_>>().len(), 3);
+    }
+
+    #[test]
+    fn unbounded_sequences() {
+        let x = Sequence::new(1.0, 1.0, None);
+        assert_eq!(x.get(1_000_000_000), Some(OptionNA::Some(1e9 + 1.0)));
+        assert_eq!(x.slice(10..20), Sequence::new(11.0, 1.0, Some(10)));
+        assert!(x.slice(10..usize::MAX).is_unbounded());
+    }
+}
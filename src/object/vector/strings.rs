@@ -0,0 +1,220 @@
+use core::fmt;
+use hashbrown::HashSet;
+use std::cell::RefCell;
+use std::convert::Infallible;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use crate::object::Symbol;
+
+thread_local! {
+    static STRINGS: RefCell<HashSet<Rc<str>>> = Default::default();
+}
+
+/// Shared Strings
+///
+/// The values of character vectors are stored in a global string pool, much
+/// like R's cache of `CHARSXP`s, such that each distinct string is stored
+/// once, no matter how many vectors hold it. Cloning a string only bumps its
+/// reference count and, because equal strings share their storage, strings
+/// are compared and hashed by address. Strings are removed from the pool
+/// once the last value referencing them is dropped.
+///
+pub struct Str(Rc<str>);
+
+impl Str {
+    pub fn new(value: &str) -> Str {
+        STRINGS.with_borrow_mut(|strings| {
+            if let Some(interned) = strings.get(value) {
+                return Str(interned.clone());
+            }
+
+            let interned: Rc<str> = Rc::from(value);
+            strings.insert(interned.clone());
+            Str(interned)
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The number of distinct strings in the pool
+    pub fn pool_size() -> usize {
+        STRINGS.with_borrow(|strings| strings.len())
+    }
+}
+
+impl Drop for Str {
+    fn drop(&mut self) {
+        // the pool holds the only other reference to the string
+        if Rc::strong_count(&self.0) == 2 {
+            let _ = STRINGS.try_with(|strings| {
+                if let Ok(mut strings) = strings.try_borrow_mut() {
+                    strings.remove(&*self.0);
+                }
+            });
+        }
+    }
+}
+
+impl Clone for Str {
+    fn clone(&self) -> Self {
+        Str(self.0.clone())
+    }
+}
+
+impl Default for Str {
+    fn default() -> Self {
+        Str::new("")
+    }
+}
+
+impl PartialEq for Str {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for Str {}
+
+impl Hash for Str {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_ptr().hash(state)
+    }
+}
+
+impl PartialOrd for Str {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Str {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Deref for Str {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Str {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Str {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Str {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for Str {
+    fn eq(&self, other: &String) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl FromStr for Str {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Str::new(s))
+    }
+}
+
+impl From<&str> for Str {
+    fn from(value: &str) -> Self {
+        Str::new(value)
+    }
+}
+
+impl From<&String> for Str {
+    fn from(value: &String) -> Self {
+        Str::new(value)
+    }
+}
+
+impl From<String> for Str {
+    fn from(value: String) -> Self {
+        Str::new(&value)
+    }
+}
+
+impl From<Symbol> for Str {
+    fn from(value: Symbol) -> Self {
+        Str::new(value.as_str())
+    }
+}
+
+impl From<Str> for String {
+    fn from(value: Str) -> Self {
+        value.as_str().to_string()
+    }
+}
+
+impl From<&Str> for Symbol {
+    fn from(value: &Str) -> Self {
+        Symbol::new(value)
+    }
+}
+
+impl From<Str> for Symbol {
+    fn from(value: Str) -> Self {
+        Symbol::new(&value)
+    }
+}
+
+impl fmt::Debug for Str {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.as_str())
+    }
+}
+
+impl fmt::Display for Str {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Str;
+
+    #[test]
+    fn strings_are_shared() {
+        let x = Str::new("shared string");
+        let y = Str::from(String::from("shared string"));
+        assert_eq!(x, y);
+        assert!(std::ptr::eq(x.as_str(), y.as_str()));
+        assert_ne!(x, Str::new("other string"));
+        assert_eq!(x, "shared string");
+    }
+
+    #[test]
+    fn strings_are_released() {
+        let before = Str::pool_size();
+        let x = Str::new("a string that is released");
+        let y = x.clone();
+        assert_eq!(Str::pool_size(), before + 1);
+        drop(x);
+        assert_eq!(Str::pool_size(), before + 1);
+        drop(y);
+        assert_eq!(Str::pool_size(), before);
+    }
+}
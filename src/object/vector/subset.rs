@@ -2,6 +2,7 @@ use std::cell::RefCell;
 use std::ops::Range;
 use std::rc::Rc;
 
+use super::rep::Rep;
 use super::{types::*, OptionNA, Vector};
 use crate::error::Error;
 use crate::lang::Signal;
@@ -28,7 +29,7 @@ impl Subset {
                 OptionNA::NA => None,
             }),
             Subset::Range(range) => {
-                if range.start <= index && index < range.end {
+                if index < range.end - range.start {
                     Some(range.start + index)
                 } else {
                     None
@@ -220,6 +221,17 @@ impl TryFrom<Vector> for Subset {
     type Error = Signal;
     fn try_from(value: Vector) -> Result<Self, Self::Error> {
         match value {
+            // sequences of consecutive indices don't need to be materialized
+            Vector::Double(Rep::Seq(seq, _))
+                if seq.by == 1.0 && seq.start >= 1.0 && seq.start.fract() == 0.0 =>
+            {
+                let start = seq.start as usize - 1;
+                match seq.len {
+                    Some(len) => Ok(Subset::Range(start..start + len)),
+                    None => Err(Error::UnboundedSequence.into()),
+                }
+            }
+            value if value.is_unbounded() => Err(Error::UnboundedSequence.into()),
             value @ Vector::Double(_) => Subset::try_from(value.as_integer()),
             Vector::Integer(v) => {
                 let y = v
@@ -240,16 +252,20 @@ impl TryFrom<Vector> for Subset {
                     .clone()
                     .borrow()
                     .iter()
-                    .all(|i| i == &OptionNA::Some(false));
+                    .all(|i| i == OptionNA::Some(false));
 
                 // special case when all are false, treat it as no indices
                 if all_false {
                     Ok(Subset::Indices(Vec::new().into()))
                 } else {
-                    Ok(Subset::Mask(v.inner()))
+                    Ok(Subset::Mask(v.iter_values().collect::<Vec<_>>().into()))
                 }
             }
-            Vector::Character(v) => Ok(Subset::Names(v.inner())),
+            Vector::Character(v) => Ok(Subset::Names(v.iter_values().collect::<Vec<_>>().into())),
+            Vector::Complex(_) => {
+                Err(Error::Other("invalid subscript type 'complex'".to_string()).into())
+            }
+            Vector::Raw(_) => Err(Error::Other("invalid subscript type 'raw'".to_string()).into()),
         }
     }
 }
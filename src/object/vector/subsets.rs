@@ -1,6 +1,7 @@
 use crate::object::CowObj;
 use hashbrown::HashMap;
 
+use super::Str;
 use super::Subset;
 
 #[derive(Debug, Clone, PartialEq, Default)]
@@ -8,7 +9,7 @@ pub struct Subsets(pub Vec<Subset>);
 
 pub struct NamedSubsets {
     subsets: Subsets,
-    names: CowObj<HashMap<String, Vec<usize>>>,
+    names: CowObj<HashMap<Str, Vec<usize>>>,
 }
 
 impl Subsets {
@@ -48,7 +49,7 @@ impl Subsets {
         self.0.push(subset.into());
     }
 
-    pub fn bind_names(self, names: CowObj<HashMap<String, Vec<usize>>>) -> NamedSubsets {
+    pub fn bind_names(self, names: CowObj<HashMap<Str, Vec<usize>>>) -> NamedSubsets {
         NamedSubsets { subsets: self, names }
     }
 }
@@ -152,7 +153,7 @@ mod test {
     #[test]
     fn subset_range() {
         let x: Vector = (1..=10).collect::<Vec<_>>().into();
-        let result = x.subset((2..6).into()).materialize();
+        let result = x.subset((2..6).into()).materialize().unwrap();
         let expect = Vector::from(vec![3, 4, 5, 6]);
         assert_eq!(result, expect)
     }
@@ -160,7 +161,7 @@ mod test {
     #[test]
     fn subset_sequential_indices() {
         let x: Vector = (1..=10).collect::<Vec<_>>().into();
-        let result = x.subset(vec![2, 3, 4, 5].into()).materialize();
+        let result = x.subset(vec![2, 3, 4, 5].into()).materialize().unwrap();
         let expect = Vector::from(vec![3, 4, 5, 6]);
         assert_eq!(result, expect)
     }
@@ -168,7 +169,10 @@ mod test {
     #[test]
     fn subset_sequential_repeating_indices() {
         let x: Vector = (1..=10).collect::<Vec<_>>().into();
-        let result = x.subset(vec![2, 3, 3, 3, 5, 5].into()).materialize();
+        let result = x
+            .subset(vec![2, 3, 3, 3, 5, 5].into())
+            .materialize()
+            .unwrap();
         let expect = Vector::from(vec![3, 4, 4, 4, 6, 6]);
         assert_eq!(result, expect)
     }
@@ -176,7 +180,7 @@ mod test {
     #[test]
     fn subset_indices_with_gap() {
         let x: Vector = (1..=10).collect::<Vec<_>>().into();
-        let result = x.subset(vec![2, 8].into()).materialize();
+        let result = x.subset(vec![2, 8].into()).materialize().unwrap();
         let expect = Vector::from(vec![3, 9]);
         assert_eq!(result, expect);
     }
@@ -184,7 +188,7 @@ mod test {
     #[test]
     fn subset_empty_indices() {
         let x: Vector = (1..=10).collect::<Vec<_>>().into();
-        let result = x.subset(vec![].into()).materialize();
+        let result = x.subset(vec![].into()).materialize().unwrap();
         let expect = Vector::from(Vec::new() as Vec<i32>);
         assert_eq!(result, expect);
     }
@@ -192,7 +196,7 @@ mod test {
     #[test]
     fn subset_single_index() {
         let x: Vector = (1..=10).collect::<Vec<_>>().into();
-        let result = x.subset(vec![6].into()).materialize();
+        let result = x.subset(vec![6].into()).materialize().unwrap();
         let expect = Vector::from(vec![7]);
         assert_eq!(result, expect);
     }
@@ -200,7 +204,7 @@ mod test {
     #[test]
     fn subset_unsorted_indices() {
         let x: Vector = (1..=10).collect::<Vec<_>>().into();
-        let result = x.subset(vec![6, 2, 1, 4].into()).materialize();
+        let result = x.subset(vec![6, 2, 1, 4].into()).materialize().unwrap();
         let expect = Vector::from(vec![7, 3, 2, 5]);
         assert_eq!(result, expect);
     }
@@ -208,7 +212,7 @@ mod test {
     #[test]
     fn subset_repeated_indices() {
         let x: Vector = (1..=10).collect::<Vec<_>>().into();
-        let result = x.subset(vec![6, 2, 6, 6].into()).materialize();
+        let result = x.subset(vec![6, 2, 6, 6].into()).materialize().unwrap();
         let expect = Vector::from(vec![7, 3, 7, 7]);
         assert_eq!(result, expect);
     }
@@ -216,7 +220,7 @@ mod test {
     #[test]
     fn subset_by_range() {
         let x: Vector = (1..=10).collect::<Vec<_>>().into();
-        let result = x.subset((3..6).into()).materialize();
+        let result = x.subset((3..6).into()).materialize().unwrap();
         let expect = Vector::from(vec![4, 5, 6]);
         assert_eq!(result, expect);
     }
@@ -227,7 +231,8 @@ mod test {
         let result = x
             .subset((3..6).into())
             .subset(vec![2, 1].into())
-            .materialize();
+            .materialize()
+            .unwrap();
         let expect = Vector::from(vec![6, 5]);
         assert_eq!(result, expect);
     }
@@ -1,7 +1,10 @@
 use super::coercion::AtomicMode;
+pub use super::complex::Complex64;
 use super::OptionNA;
+use super::Str;
 use crate::error::Error;
 use crate::object::Obj;
+use crate::object::Symbol;
 use crate::object::Vector;
 
 pub type Double = OptionNA<f64>;
@@ -25,7 +28,21 @@ impl AtomicMode for Logical {
     }
 }
 
-pub type Character = OptionNA<String>;
+pub type Complex = OptionNA<Complex64>;
+impl AtomicMode for Complex {
+    fn is_complex() -> bool {
+        true
+    }
+}
+
+pub type Raw = OptionNA<u8>;
+impl AtomicMode for Raw {
+    fn is_raw() -> bool {
+        true
+    }
+}
+
+pub type Character = OptionNA<Str>;
 impl AtomicMode for Character {
     fn is_character() -> bool {
         true
@@ -50,11 +67,20 @@ impl From<Option<bool>> for OptionNA<bool> {
     }
 }
 
-impl From<Option<String>> for OptionNA<String> {
+impl From<Option<String>> for OptionNA<Str> {
     fn from(value: Option<String>) -> Self {
         match value {
             None => Self::NA,
-            Some(x) => Self::Some(x),
+            Some(x) => Self::Some(x.into()),
+        }
+    }
+}
+
+impl From<Option<Symbol>> for OptionNA<Str> {
+    fn from(value: Option<Symbol>) -> Self {
+        match value {
+            None => Self::NA,
+            Some(x) => Self::Some(x.into()),
         }
     }
 }
@@ -68,6 +94,24 @@ impl From<Option<f64>> for OptionNA<f64> {
     }
 }
 
+impl From<Option<Complex64>> for OptionNA<Complex64> {
+    fn from(value: Option<Complex64>) -> Self {
+        match value {
+            None => Self::NA,
+            Some(x) => Self::Some(x),
+        }
+    }
+}
+
+impl From<Option<u8>> for OptionNA<u8> {
+    fn from(value: Option<u8>) -> Self {
+        match value {
+            None => Self::NA,
+            Some(x) => Self::Some(x),
+        }
+    }
+}
+
 impl<T> OptionNA<T> {
     pub fn is_na(&self) -> bool {
         matches!(self, OptionNA::NA)
@@ -110,6 +154,42 @@ impl TryFrom<Obj> for Integer {
     }
 }
 
+impl TryFrom<Obj> for Complex {
+    type Error = Error;
+    fn try_from(value: Obj) -> Result<Self, Self::Error> {
+        let err = Err(Error::Other(
+            "Cannot convert object to scalar complex.".to_string(),
+        ));
+        if let Obj::Vector(Vector::Complex(v)) = value {
+            if v.len() == 1 {
+                Ok(v.iter_values().next().expect("length is one"))
+            } else {
+                err
+            }
+        } else {
+            err
+        }
+    }
+}
+
+impl TryFrom<Obj> for Raw {
+    type Error = Error;
+    fn try_from(value: Obj) -> Result<Self, Self::Error> {
+        let err = Err(Error::Other(
+            "Cannot convert object to scalar raw.".to_string(),
+        ));
+        if let Obj::Vector(Vector::Raw(v)) = value {
+            if v.len() == 1 {
+                Ok(v.iter_values().next().expect("length is one"))
+            } else {
+                err
+            }
+        } else {
+            err
+        }
+    }
+}
+
 impl TryFrom<Obj> for Character {
     type Error = Error;
     fn try_from(value: Obj) -> Result<Self, Self::Error> {
@@ -172,3 +252,15 @@ impl From<Integer> for Vector {
         Vector::Integer(vec![value].into())
     }
 }
+
+impl From<Complex> for Vector {
+    fn from(value: Complex) -> Self {
+        Vector::Complex(vec![value].into())
+    }
+}
+
+impl From<Raw> for Vector {
+    fn from(value: Raw) -> Self {
+        Vector::Raw(vec![value].into())
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt::Debug;
+
+use super::OptionNA;
+use crate::object::Obj;
+
+/// Vector Elements
+///
+/// Elements are stored as a dense value along with whether they are missing,
+/// such that atomic vectors can hold their values in a contiguous slice of
+/// primitives. Elements without a missing value, like those of lists, are
+/// stored as they are.
+///
+pub trait Element: Clone + 'static {
+    type Dense: Clone + Default;
+
+    /// Split an element into its dense value, or `None` if it is missing
+    fn into_dense(self) -> Option<Self::Dense>;
+
+    /// Build an element from its dense value, or `None` if it is missing
+    fn from_dense(value: Option<Self::Dense>) -> Self;
+
+    /// View dense values as elements, for elements which are stored as they are
+    fn as_elements(_dense: &[Self::Dense]) -> Option<&[Self]> {
+        None
+    }
+}
+
+impl<T: Clone + Default + 'static> Element for OptionNA<T> {
+    type Dense = T;
+
+    fn into_dense(self) -> Option<T> {
+        match self {
+            OptionNA::Some(x) => Some(x),
+            OptionNA::NA => None,
+        }
+    }
+
+    fn from_dense(value: Option<T>) -> Self {
+        match value {
+            Some(x) => OptionNA::Some(x),
+            None => OptionNA::NA,
+        }
+    }
+}
+
+impl Element for Obj {
+    type Dense = Obj;
+
+    fn into_dense(self) -> Option<Obj> {
+        Some(self)
+    }
+
+    fn from_dense(value: Option<Obj>) -> Self {
+        value.unwrap_or_default()
+    }
+
+    fn as_elements(dense: &[Obj]) -> Option<&[Obj]> {
+        Some(dense)
+    }
+}
+
+const BITS: usize = u64::BITS as usize;
+
+/// Vector Values
+///
+/// The values of a vector, stored as a dense vector of primitive values and,
+/// once any value is missing, a bitmask flagging the missing elements. Missing
+/// elements hold a default dense value.
+///
+/// Elements are read and written as their `OptionNA` view, while operations
+/// which can work on contiguous values directly can use [Values::dense].
+///
+pub struct Values<T: Element> {
+    data: Vec<T::Dense>,
+    na: Option<Vec<u64>>,
+}
+
+impl<T: Element> Values<T> {
+    pub fn new() -> Self {
+        Values { data: Vec::new(), na: None }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Values { data: Vec::with_capacity(capacity), na: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// The dense values, where missing elements hold a default value.
+    pub fn dense(&self) -> &[T::Dense] {
+        &self.data
+    }
+
+    /// Whether the element at `index` is missing
+    pub fn is_na(&self, index: usize) -> bool {
+        self.na
+            .as_ref()
+            .is_some_and(|na| na[index / BITS] & (1 << (index % BITS)) != 0)
+    }
+
+    /// Whether any element is missing
+    pub fn has_na(&self) -> bool {
+        self.na
+            .as_ref()
+            .is_some_and(|na| na.iter().any(|&bits| bits != 0))
+    }
+
+    pub fn get(&self, index: usize) -> Option<T> {
+        let value = self.data.get(index)?;
+        if self.is_na(index) {
+            Some(T::from_dense(None))
+        } else {
+            Some(T::from_dense(Some(value.clone())))
+        }
+    }
+
+    pub fn set(&mut self, index: usize, value: T) {
+        let value = value.into_dense();
+        self.set_na(index, value.is_none());
+        self.data[index] = value.unwrap_or_default();
+    }
+
+    pub fn push(&mut self, value: T) {
+        let value = value.into_dense();
+        let is_na = value.is_none();
+        self.data.push(value.unwrap_or_default());
+        if let Some(na) = &mut self.na {
+            na.resize(self.data.len().div_ceil(BITS), 0);
+        }
+        self.set_na(self.data.len() - 1, is_na);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.data.remove(index);
+        if let Some(na) = &self.na {
+            let is_na = |i: usize| na[i / BITS] & (1 << (i % BITS)) != 0;
+            let mut shifted = vec![0; self.data.len().div_ceil(BITS)];
+            for i in (0..self.data.len()).filter(|&i| is_na(if i < index { i } else { i + 1 })) {
+                shifted[i / BITS] |= 1 << (i % BITS);
+            }
+            self.na = Some(shifted);
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        (0..self.len()).map(|i| self.get(i).expect("index is within values"))
+    }
+
+    fn set_na(&mut self, index: usize, is_na: bool) {
+        if !is_na && self.na.is_none() {
+            return;
+        }
+
+        let len = self.data.len().div_ceil(BITS);
+        let na = self.na.get_or_insert_with(|| vec![0; len]);
+        if is_na {
+            na[index / BITS] |= 1 << (index % BITS);
+        } else {
+            na[index / BITS] &= !(1 << (index % BITS));
+        }
+    }
+}
+
+impl<T: Element<Dense = T>> Values<T> {
+    /// The values themselves, for elements without missing values
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+}
+
+impl<T: Element> Default for Values<T> {
+    fn default() -> Self {
+        Values::new()
+    }
+}
+
+impl<T: Element> Clone for Values<T> {
+    fn clone(&self) -> Self {
+        Values { data: self.data.clone(), na: self.na.clone() }
+    }
+}
+
+impl<T: Element + PartialEq> PartialEq for Values<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: Element + Debug> Debug for Values<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: Element> FromIterator<T> for Values<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let iter = iter.into_iter();
+        let mut values = Values::with_capacity(iter.size_hint().0);
+        for value in iter {
+            values.push(value);
+        }
+        values
+    }
+}
+
+impl<T: Element> From<Vec<T>> for Values<T> {
+    fn from(value: Vec<T>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Values;
+    use crate::object::OptionNA;
+
+    #[test]
+    fn values_are_dense() {
+        let x = Values::from(vec![OptionNA::Some(1), OptionNA::NA, OptionNA::Some(3)]);
+        assert_eq!(x.dense(), &[1, 0, 3]);
+        assert!(x.has_na());
+        assert_eq!(x.get(1), Some(OptionNA::NA));
+        assert_eq!(x.get(3), None);
+    }
+
+    #[test]
+    fn values_without_na_have_no_mask() {
+        let mut x = Values::from(vec![OptionNA::Some(1.0), OptionNA::Some(2.0)]);
+        assert!(x.na.is_none());
+        x.set(0, OptionNA::NA);
+        assert!(x.has_na());
+        x.set(0, OptionNA::Some(5.0));
+        assert!(!x.has_na());
+        assert_eq!(
+            x.iter().collect::<Vec<_>>(),
+            vec![OptionNA::Some(5.0), OptionNA::Some(2.0)]
+        );
+    }
+
+    #[test]
+    fn values_mask_spans_words() {
+        let mut x: Values<OptionNA<i32>> = (0..100).map(OptionNA::Some).collect();
+        x.push(OptionNA::NA);
+        x.set(70, OptionNA::NA);
+        x.remove(3);
+        assert_eq!(x.len(), 100);
+        assert!(x.is_na(69) && x.is_na(99));
+        assert_eq!(x.iter().filter(|i| *i == OptionNA::NA).count(), 2);
+    }
+}
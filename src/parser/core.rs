@@ -11,12 +11,13 @@ use crate::cli::Experiment;
 use crate::error::Error;
 use crate::internal_err;
 use crate::lang::Signal;
-use crate::object::{Expr, ExprList};
+use crate::object::{Expr, ExprList, FormatSpec, Symbol};
 use crate::parser::*;
 use crate::session::SessionParserConfig;
 use pest::iterators::{Pair, Pairs};
 use pest::pratt_parser::PrattParser;
 use pest::{Parser, RuleType};
+use std::rc::Rc;
 
 pub type ParseResult = Result<Expr, Signal>;
 pub type ParseListResult = Result<ExprList, Signal>;
@@ -45,6 +46,7 @@ where
                 en::Rule::power => Box::new(InfixPow),
                 en::Rule::colon => Box::new(InfixColon),
                 en::Rule::modulo => Box::new(InfixMod),
+                en::Rule::matmul => Box::new(InfixMatMul),
                 en::Rule::assign => Box::new(InfixAssign),
                 en::Rule::or => Box::new(InfixOr),
                 en::Rule::and => Box::new(InfixAnd),
@@ -57,13 +59,18 @@ where
                 en::Rule::eq => Box::new(InfixEqual),
                 en::Rule::neq => Box::new(InfixNotEqual),
                 en::Rule::pipe => Box::new(InfixPipe),
+                // user-defined operators call the function of the same name
+                en::Rule::special => {
+                    let what = Expr::Symbol(Symbol::new(op.as_str()));
+                    return Ok(Expr::Call(Rc::new(what), Rc::new(args)));
+                }
                 rule => {
                     let span = (op.as_span().start(), op.as_span().end());
                     return Err(Error::ParseUnexpected(rule, span).into());
                 }
             };
 
-            Ok(Expr::Call(Box::new(Expr::Primitive(op)), args))
+            Ok(Expr::Call(Rc::new(Expr::Primitive(op)), Rc::new(args)))
         })
         .parse(pairs)
 }
@@ -104,25 +111,24 @@ where
         en::Rule::val_null => Ok(Expr::Null),
         en::Rule::val_na => Ok(Expr::NA),
         en::Rule::val_inf => Ok(Expr::Inf),
+        en::Rule::val_nan => Ok(Expr::NaN),
 
         // reserved symbols
         en::Rule::more => Ok(Expr::More),
 
+        // omitted indices, as in `x[, 1]`
+        en::Rule::index_missing => Ok(Expr::Missing),
+
         // atomic values
-        en::Rule::number => Ok(Expr::Number(
-            pair.as_str()
-                .replace('_', "")
-                .parse::<f64>()
-                .map_or(internal_err!(), Ok)?,
-        )),
-        en::Rule::integer => Ok(Expr::Integer(
-            pair.as_str()
-                .replace('_', "")
-                .parse::<i32>()
-                .map_or(internal_err!(), Ok)?,
-        )),
-        en::Rule::single_quoted_string => Ok(Expr::String(String::from(pair.as_str()))),
-        en::Rule::double_quoted_string => Ok(Expr::String(String::from(pair.as_str()))),
+        en::Rule::number => Ok(Expr::Number(parse_number(pair.as_str())?)),
+        en::Rule::integer => parse_integer(pair.as_str()),
+        en::Rule::imaginary => Ok(Expr::Imaginary(parse_number(pair.as_str())?)),
+        en::Rule::single_quoted_string => Ok(Expr::String(unescape(pair.as_str())?)),
+        en::Rule::double_quoted_string => Ok(Expr::String(unescape(pair.as_str())?)),
+        en::Rule::raw_string_paren | en::Rule::raw_string_bracket | en::Rule::raw_string_brace => {
+            Ok(Expr::String(String::from(pair.as_str())))
+        }
+        en::Rule::fstring => parse_fstring(config, parser, pair),
 
         // structured values
         en::Rule::vec => parse_vec(config, parser, pratt, pair),
@@ -131,7 +137,8 @@ where
         // calls and symbols
         en::Rule::call => parse_call(config, parser, pratt, pair),
         en::Rule::symbol_ident => parse_symbol(config, parser, pratt, pair),
-        en::Rule::symbol_backticked => Ok(Expr::Symbol(String::from(pair.as_str()))),
+        en::Rule::symbol_backticked => Ok(Expr::Symbol(Symbol::new(pair.as_str()))),
+        en::Rule::namespaced => parse_namespaced(config, parser, pratt, pair),
 
         // otherwise fail
         rule => {
@@ -141,6 +148,46 @@ where
     }
 }
 
+/// Parse a numeric literal, which may be written in scientific notation or
+/// in hexadecimal
+fn parse_number(literal: &str) -> Result<f64, Signal> {
+    let literal = literal.replace('_', "");
+    let value = match literal.strip_prefix("0x").or(literal.strip_prefix("0X")) {
+        Some(hex) => parse_hex(hex),
+        None => literal.parse::<f64>().ok(),
+    };
+
+    value.map_or(internal_err!(), Ok)
+}
+
+/// Parse hexadecimal digits, with an optional fraction and binary exponent
+/// as in `1.8p3`
+fn parse_hex(hex: &str) -> Option<f64> {
+    let (mantissa, exponent) = match hex.split_once(['p', 'P']) {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().ok()?),
+        None => (hex, 0),
+    };
+
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = whole.chars().chain(fraction.chars());
+    let value = digits.fold(0.0, |value, digit| {
+        value * 16.0 + digit.to_digit(16).unwrap_or_default() as f64
+    });
+
+    Some(value * 2f64.powi(exponent - 4 * fraction.len() as i32))
+}
+
+/// Parse an integer literal, like `1e5L`. As in R, literals that aren't whole
+/// numbers or that exceed the range of integers are parsed as doubles.
+fn parse_integer(literal: &str) -> ParseResult {
+    let value = parse_number(literal)?;
+    if value.fract() == 0.0 && value <= i32::MAX as f64 {
+        Ok(Expr::Integer(value as i32))
+    } else {
+        Ok(Expr::Number(value))
+    }
+}
+
 fn parse_paren<P, R>(
     config: &SessionParserConfig,
     parser: &P,
@@ -186,13 +233,13 @@ fn parse_named<P, R>(
     parser: &P,
     pratt: &PrattParser<R>,
     pair: Pair<R>,
-) -> Result<(Option<String>, Expr), Signal>
+) -> Result<(Option<Symbol>, Expr), Signal>
 where
     P: Parser<R> + LocalizedParser,
     R: RuleType + Into<en::Rule>,
 {
     let mut inner = pair.into_inner();
-    let name = String::from(inner.next().unwrap().as_str());
+    let name = Symbol::new(inner.next().unwrap().as_str());
     Ok((Some(name), parse_expr(config, parser, pratt, inner)?))
 }
 
@@ -237,8 +284,11 @@ where
     )?;
 
     match name {
-        "list" => Ok(Expr::List(pairs)),
-        name => Ok(Expr::Call(Box::new(Expr::String(name.to_string())), pairs)),
+        "list" => Ok(Expr::List(Rc::new(pairs))),
+        name => Ok(Expr::Call(
+            Rc::new(Expr::Symbol(Symbol::new(name))),
+            Rc::new(pairs),
+        )),
     }
 }
 
@@ -261,7 +311,7 @@ where
     )?
     .as_formals();
     let body = parse_expr(config, parser, pratt, inner)?;
-    Ok(Expr::Function(params, Box::new(body)))
+    Ok(Expr::Function(Rc::new(params), Rc::new(body)))
 }
 
 fn parse_if_else<P, R>(
@@ -319,7 +369,73 @@ where
     P: Parser<R> + LocalizedParser,
     R: RuleType + Into<en::Rule>,
 {
-    Ok(Expr::Symbol(String::from(pair.as_str())))
+    Ok(Expr::Symbol(Symbol::new(pair.as_str())))
+}
+
+/// Parse an interpolated string, such as `f"{x:.2f} and {{braces}}"`
+///
+/// The code of each field is parsed on its own, after splitting off its
+/// format spec (see [`FormatSpec::split`]).
+///
+fn parse_fstring<P, R>(config: &SessionParserConfig, parser: &P, pair: Pair<R>) -> ParseResult
+where
+    P: Parser<R> + LocalizedParser,
+    R: RuleType + Into<en::Rule>,
+{
+    let mut parts: Vec<(Option<Symbol>, Expr)> = vec![];
+    for part in pair.into_inner() {
+        match part.as_rule().into() {
+            en::Rule::fstring_text => {
+                let text = part.as_str().replace("{{", "{").replace("}}", "}");
+                parts.push((None, Expr::String(unescape(&text)?)));
+            }
+            en::Rule::fstring_field => {
+                let mut inner = part.into_inner();
+                let field = inner.next().map_or(internal_err!(), Ok)?;
+                let (code, spec) = FormatSpec::split(field.as_str());
+                let expr = parser.parse_input_with(code, config)?;
+                parts.push((spec.map(Symbol::new), expr));
+            }
+            rule => {
+                let span = (part.as_span().start(), part.as_span().end());
+                return Err(Error::ParseUnexpected(rule, span).into());
+            }
+        }
+    }
+
+    Ok(Expr::new_primitive_call(KeywordFString, parts.into()))
+}
+
+fn parse_namespaced<P, R>(
+    config: &SessionParserConfig,
+    parser: &P,
+    pratt: &PrattParser<R>,
+    pair: Pair<R>,
+) -> ParseResult
+where
+    P: Parser<R> + LocalizedParser,
+    R: RuleType + Into<en::Rule>,
+{
+    let mut inner = pair.into_inner();
+    let namespace = parse_primary(
+        config,
+        parser,
+        pratt,
+        inner.next().map_or(internal_err!(), Ok)?,
+    )?;
+    let op = inner.next().map_or(internal_err!(), Ok)?;
+    let name = parse_primary(
+        config,
+        parser,
+        pratt,
+        inner.next().map_or(internal_err!(), Ok)?,
+    )?;
+
+    let args = ExprList::from(vec![namespace, name]);
+    match op.as_rule().into() {
+        en::Rule::triplecolon => Ok(Expr::new_primitive_call(InfixTripleColon, args)),
+        _ => Ok(Expr::new_primitive_call(InfixDoubleColon, args)),
+    }
 }
 
 fn parse_for<P, R>(
@@ -413,7 +529,7 @@ where
             let val = pair.as_str();
             let is_ellipsis = val == ".";
             if config.experiments.contains(&Experiment::RestArgs) {
-                Ok((Expr::Ellipsis(Some(val.to_string())), ExprList::new()))
+                Ok((Expr::Ellipsis(Some(Symbol::new(val))), ExprList::new()))
             } else if is_ellipsis {
                 Ok((Expr::Ellipsis(None), ExprList::new()))
             } else {
@@ -447,12 +563,12 @@ where
         result = match what {
             // Null used here has a magic value to dispatch on `x(...)` calls
             // if postfix is parenthesized pairlist, it's a call to result
-            Expr::Null => Expr::Call(Box::new(result), args),
+            Expr::Null => Expr::Call(Rc::new(result), Rc::new(args)),
 
             // otherwise call to a postfix operator with result as the first arg
             _ => {
                 args.insert(0, result);
-                Expr::Call(Box::new(what), args)
+                Expr::Call(Rc::new(what), Rc::new(args))
             }
         };
     }
@@ -490,7 +606,7 @@ where
             en::Rule::more => {
                 let is_ellipsis = result.to_string() == ".";
                 if config.experiments.contains(&Experiment::RestArgs) {
-                    Expr::Ellipsis(Some(result.to_string()))
+                    Expr::Ellipsis(Some(Symbol::new(&result.to_string())))
                 } else if is_ellipsis {
                     Expr::Ellipsis(None)
                 } else {
@@ -538,7 +654,10 @@ where
 
 #[cfg(test)]
 mod test {
-    use crate::r;
+    use crate::error::Error;
+    use crate::lang::Signal;
+    use crate::parser::{Localization, LocalizedParser, Style};
+    use crate::{r, r_expect};
 
     #[test]
     fn prefix_with_space() {
@@ -585,4 +704,209 @@ mod test {
             r! {{"0.000123"}}
         }
     }
+
+    #[test]
+    fn special_operators() {
+        r_expect! {{"
+            `%m%` <- function(a, b) a - b
+            5 %m% 3 == 2
+        "}}
+    }
+
+    #[test]
+    fn special_operator_precedence() {
+        r_expect! {{"
+            `%m%` <- function(a, b) a - b
+            1 + 10 %m% 3 * 4 == 29
+        "}}
+    }
+
+    #[test]
+    fn special_operators_are_highlighted() {
+        let styles = Localization::En.parse_highlight("x %in% y").unwrap();
+        assert!(styles.contains(&("%in%".to_string(), Style::Infix)));
+    }
+
+    #[test]
+    fn namespaced_calls() {
+        assert_eq!(r!(base::sum(1, 2)), r!(3));
+        assert_eq!(r!(base:::sum(1, 2)), r!(3));
+        assert_eq!(r!(quote(base::sum(1))).unwrap().to_string(), "base::sum(1)");
+    }
+
+    #[test]
+    fn scientific_notation() {
+        assert_eq!(r!(1e6), r!(1000000));
+        assert_eq!(r! {{"2.5e-3"}}, r!(0.0025));
+        assert_eq!(r! {{".5E+3"}}, r!(500));
+    }
+
+    #[test]
+    fn hexadecimal_numbers() {
+        assert_eq!(r! {{"0xFF"}}, r!(255));
+        assert_eq!(r! {{"0x1p-3"}}, r!(0.125));
+        assert_eq!(r! {{"0x1.8p3"}}, r!(12));
+    }
+
+    #[test]
+    fn integer_exponents() {
+        assert_eq!(r! {{"1e5L"}}, r! {{"100000L"}});
+        assert_eq!(r! {{"0xFFL"}}, r! {{"255L"}});
+        assert_eq!(r! {{"1e-3L"}}, r!(0.001));
+    }
+
+    #[test]
+    fn integer_fractions() {
+        assert_eq!(r! {{"1.5L"}}, r!(1.5));
+        assert_eq!(r! {{".5L"}}, r!(0.5));
+        assert_eq!(r! {{"2.0L"}}, r! {{"2L"}});
+    }
+
+    #[test]
+    fn nan_values() {
+        r_expect! {{r#"typeof(NaN) == "double""#}}
+        assert_eq!(r!(NaN + 1).unwrap().to_string(), "[1] NaN");
+    }
+
+    #[test]
+    fn numbers_are_highlighted() {
+        let styles = Localization::En.parse_highlight("1e5L + NaN").unwrap();
+        assert!(styles.contains(&("1e5L".to_string(), Style::Number)));
+        assert!(styles.contains(&("NaN".to_string(), Style::Value)));
+    }
+
+    #[test]
+    fn raw_strings() {
+        assert_eq!(
+            r! {{r#"r"(C:\path\to "file")""#}},
+            r! {{r#""C:\\path\\to \"file\"""#}}
+        );
+        assert_eq!(r! {{r#"R'[a)"b]'"#}}, r! {{r#""a)\"b""#}});
+        assert_eq!(r! {{r#"r"--{a}-"}--""#}}, r! {{r#""a}-\"""#}});
+        assert_eq!(r! {{"r'(multi\nline)'"}}, r! {{r#""multi\nline""#}});
+    }
+
+    #[test]
+    fn string_escapes() {
+        r_expect! {{r#""\x41\101\u00e9\u{e9}\U{1F600}" == "AAéé😀""#}}
+        r_expect! {{r#""a\tb" != "a\\tb""#}}
+        assert!(r! {{r#""\q""#}}.is_err());
+        assert!(r! {{r#""\x""#}}.is_err());
+        assert!(r! {{r#""\U{110000}""#}}.is_err());
+    }
+
+    #[test]
+    fn string_escape_errors() {
+        let error = |e: Error| Err(Signal::Error(e));
+        assert_eq!(r! {{r#""\q""#}}, error(Error::InvalidEscape("\\q".into())));
+        assert_eq!(
+            r! {{r#""\xZZ""#}},
+            error(Error::EscapeWithoutHexDigits("\\x".into()))
+        );
+        assert_eq!(
+            r! {{r#""\u{zz}""#}},
+            error(Error::EscapeWithoutHexDigits("\\u".into()))
+        );
+        assert_eq!(
+            r! {{r#""\u{e9""#}},
+            error(Error::InvalidUnicodeEscape("\\u{e9".into()))
+        );
+        assert_eq!(
+            r! {{r#""\U{110000}""#}},
+            error(Error::InvalidUnicodeEscape("\\U{110000}".into()))
+        );
+        assert_eq!(r! {{r#""\x00""#}}, error(Error::NulEscape("\\x00".into())));
+        assert_eq!(
+            Error::EscapeWithoutHexDigits("\\x".into()).to_string(),
+            "Error: '\\x' used without hex digits in character string"
+        );
+    }
+
+    #[test]
+    fn strings_are_deparsed() {
+        let code = r#"f("a\"b\n", "\\d+", "\001")"#;
+        let expr = Localization::En.parse_input(code).unwrap();
+        assert_eq!(expr.to_string(), code);
+        assert_eq!(
+            r! {{r#"quote(r"(\d+)")"#}}.unwrap().to_string(),
+            r#""\\d+""#
+        );
+    }
+
+    #[test]
+    fn raw_strings_are_highlighted() {
+        let styles = Localization::En
+            .parse_highlight(r#"r"-(a)-" + r"[b"#)
+            .unwrap();
+        assert!(styles.contains(&(r#"r"-(a)-""#.to_string(), Style::String)));
+        assert!(styles.contains(&(r#"r"[b"#.to_string(), Style::String)));
+    }
+
+    #[test]
+    fn fstrings() {
+        r_expect! {{r#"
+            x <- 2
+            f"value is {x + 1}" == "value is 3"
+        "#}}
+        r_expect! {{r#"f'{{braces}} {"a"}\n' == "{braces} a\n""#}}
+        r_expect! {{r#"f"{base::sum(1, 2)} {f'{1}'}" == "3 1""#}}
+    }
+
+    #[test]
+    fn fstrings_are_vectorized() {
+        assert_eq!(
+            r! {{r#"f"{(1:3)}{c('a', 'b', 'c')}""#}},
+            r! {{r#"c("1a", "2b", "3c")"#}}
+        );
+        r_expect! {{r#"length(f"{NULL}") == 0"#}}
+    }
+
+    #[test]
+    fn fstring_format_specs() {
+        r_expect! {{r#"f"{1 / 3:.2f}|{1:>3}|{1:<3}|{'a':*^5}" == "0.33|  1|1  |**a**""#}}
+        r_expect! {{r#"f"{1:3}" == "  1""#}}
+        assert!(r! {{r#"f"{1:q}""#}}.is_err());
+        assert!(r! {{r#"f"{'a':.2f}""#}}.is_err());
+    }
+
+    #[test]
+    fn fstring_fields_keep_colons() {
+        assert_eq!(r! {{r#"f"{(1:3)}""#}}, r! { ["1", "2", "3"] });
+        assert_eq!(r! {{r#"f"{1:3:02}""#}}, r! { ["01", "02", "03"] });
+        assert_eq!(r! {{r#"n <- 2; f"{1:n}""#}}, r! { ["1", "2"] });
+        assert_eq!(r! {{r#"f"{base::sum(1:2)}""#}}, r! { "3" });
+        assert_eq!(
+            r! {{r#"
+                x <- [10, 20, 30]
+                f"{x[1:2]}"
+            "#}},
+            r! { ["10", "20"] }
+        );
+    }
+
+    #[test]
+    fn fstrings_are_deparsed() {
+        let code = r#"f"a{x + 1:.2f}\n{{b}}""#;
+        let expr = Localization::En.parse_input(code).unwrap();
+        assert_eq!(expr.to_string(), code);
+
+        // ranges are kept apart from specs when deparsed
+        let code = r#"f"{1 : 3:02}{(1 : 3)}""#;
+        let expr = Localization::En.parse_input(code).unwrap();
+        assert_eq!(expr.to_string(), code);
+    }
+
+    #[test]
+    fn fstrings_are_highlighted() {
+        let styles = Localization::En.parse_highlight(r#"f"a {x:.2f}""#).unwrap();
+        assert!(styles.contains(&("a ".to_string(), Style::String)));
+        assert!(styles.contains(&("x".to_string(), Style::Symbol)));
+        assert!(styles.contains(&(":.2f".to_string(), Style::String)));
+
+        let styles = Localization::En
+            .parse_highlight(r#"f"{base::sum(1:2)}""#)
+            .unwrap();
+        assert!(styles.contains(&("sum".to_string(), Style::Call)));
+        assert!(styles.contains(&("2".to_string(), Style::Number)));
+    }
 }
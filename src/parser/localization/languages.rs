@@ -13,12 +13,16 @@ pub mod en {
                 Rule::hl_callname => Self::Call,
                 Rule::hl_value => Self::Value,
                 Rule::hl_num => Self::Number,
-                Rule::hl_str => Self::String,
+                Rule::hl_str
+                | Rule::hl_fstr_open
+                | Rule::hl_fstr_close
+                | Rule::hl_fstr_text
+                | Rule::hl_fstr_spec => Self::String,
                 Rule::hl_comment => Self::Comment,
                 Rule::hl_function => Self::Function,
                 Rule::hl_signal => Self::Signal,
                 Rule::hl_control => Self::ControlFlow,
-                Rule::hl_open | Rule::hl_brackets => Self::Brackets,
+                Rule::hl_open | Rule::hl_brackets | Rule::hl_fstr_brace => Self::Brackets,
                 Rule::hl_ops => Self::Operators,
                 Rule::hl_infix => Self::Infix,
                 _ => Self::None,
@@ -4,5 +4,11 @@ pub use core::*;
 mod localization;
 pub use localization::*;
 
+mod optimize;
+pub use optimize::*;
+
 mod style;
 pub use style::*;
+
+mod strings;
+pub use strings::*;
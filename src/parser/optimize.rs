@@ -0,0 +1,243 @@
+use crate::bytecode::BinaryOp;
+use crate::callable::core::Builtin;
+use crate::callable::keywords::KeywordParen;
+use crate::callable::operators::{PrefixNot, PrefixSub};
+use crate::callable::primitive::PrimitiveC;
+use crate::object::types::*;
+use crate::object::*;
+use std::rc::Rc;
+
+/// Optimize a parsed expression
+///
+/// Operators applied to literal operands are folded into a single literal,
+/// such that `2 * 3 + x` is evaluated as `6 + x`. Only code that is
+/// evaluated as written is optimized, so that `quote()`, `substitute()` and
+/// deparsing still see the original code:
+///
+/// * Arguments of calls by name are left untouched, as they may be captured
+///   by the called function, as in `quote(1 + 2)` or `g(2 * 3)`.
+/// * Function bodies are left untouched, and constants within them are
+///   instead computed once when the function is compiled.
+///
+pub fn optimize(expr: Expr) -> Expr {
+    match expr {
+        Expr::Call(what, args) => {
+            if !matches!(what.as_ref(), Expr::Primitive(_)) {
+                return Expr::Call(what, args);
+            }
+
+            let args: ExprList = Rc::unwrap_or_clone(args)
+                .into_iter()
+                .map(|(key, value)| (key, optimize(value)))
+                .collect();
+
+            let call = Expr::Call(what, Rc::new(args));
+            match value(&call).as_ref().and_then(as_literal) {
+                Some(literal) => literal,
+                None => call,
+            }
+        }
+        Expr::List(args) => Expr::List(Rc::new(
+            Rc::unwrap_or_clone(args)
+                .into_iter()
+                .map(|(key, value)| (key, optimize(value)))
+                .collect(),
+        )),
+        expr => expr,
+    }
+}
+
+/// The value of a literal expression
+pub fn literal(expr: &Expr) -> Option<Obj> {
+    let value = match expr {
+        Expr::Null => return Some(Obj::Null),
+        Expr::NA => Vector::from(vec![OptionNA::NA as Logical]),
+        Expr::Inf => Vector::from(vec![OptionNA::Some(f64::INFINITY)]),
+        Expr::NaN => Vector::from(vec![OptionNA::Some(f64::NAN)]),
+        Expr::Number(x) => Vector::from(vec![*x]),
+        Expr::Integer(x) => Vector::from(vec![*x]),
+        Expr::Imaginary(x) => Vector::from(vec![Complex64::new(0.0, *x)]),
+        Expr::Bool(x) => Vector::from(vec![OptionNA::Some(*x)]),
+        Expr::String(x) => Vector::from(vec![OptionNA::Some(Str::new(x))]),
+        _ => return None,
+    };
+
+    Some(Obj::Vector(value))
+}
+
+/// The value of an expression that can be computed before it is evaluated
+///
+/// These are literals and operators applied to numeric or logical constants.
+/// Calls by name, such as `c(1, 2)`, are never computed ahead of time, as
+/// the name may refer to a function other than the builtin when evaluated
+/// (see [`combined`]).
+///
+pub fn value(expr: &Expr) -> Option<Obj> {
+    let Expr::Call(what, args) = expr else {
+        return literal(expr);
+    };
+
+    let mut values = args
+        .values
+        .iter()
+        .map(value)
+        .collect::<Option<Vec<Obj>>>()?;
+
+    match what.as_ref() {
+        Expr::Primitive(f) if f.as_any().is::<KeywordParen>() && values.len() == 1 => values.pop(),
+        Expr::Primitive(f) => fold(f.as_ref(), values),
+        _ => None,
+    }
+}
+
+/// The vector built by calling `c()` with constant arguments
+///
+/// Unlike [`value`], this doesn't guarantee that `c` refers to the builtin,
+/// which must be checked when the call would be evaluated.
+///
+pub fn combined(args: &ExprList) -> Option<Obj> {
+    let pairs = args
+        .keys
+        .iter()
+        .zip(args.values.iter())
+        .map(|(key, arg)| Some((Character::from(*key), value(arg)?)))
+        .collect::<Option<Vec<(Character, Obj)>>>()?;
+
+    PrimitiveC::combine(List::from(pairs)).ok()
+}
+
+/// Apply a primitive operator to constant operands
+fn fold(f: &dyn Builtin, mut values: Vec<Obj>) -> Option<Obj> {
+    // only fold operators which are well defined for their operands
+    let foldable = values.iter().all(|value| match value {
+        Obj::Vector(v) if v.attributes().is_none() => matches!(
+            v,
+            Vector::Double(_) | Vector::Integer(_) | Vector::Logical(_) | Vector::Complex(_)
+        ),
+        _ => false,
+    });
+
+    if !foldable {
+        return None;
+    }
+
+    let result = match (values.len(), BinaryOp::from_builtin(f)) {
+        (2, Some(op)) => {
+            let rhs = values.pop()?;
+            let lhs = values.pop()?;
+            op.apply(lhs, rhs)
+        }
+        (1, _) if f.as_any().is::<PrefixSub>() => -values.pop()?,
+        (1, _) if f.as_any().is::<PrefixNot>() => !values.pop()?,
+        _ => return None,
+    };
+
+    match result {
+        Ok(Obj::Vector(mut v)) => {
+            v.materialize_computed().ok()?;
+            Some(Obj::Vector(v))
+        }
+        _ => None,
+    }
+}
+
+/// The literal expression for a value, if it can be written as one
+fn as_literal(value: &Obj) -> Option<Expr> {
+    let Obj::Vector(v) = value else {
+        return None;
+    };
+
+    if v.len() != 1 || v.attributes().is_some() {
+        return None;
+    }
+
+    match v {
+        Vector::Double(x) => match x.get_inner(0)? {
+            OptionNA::Some(x) if x.is_finite() => Some(Expr::Number(x)),
+            OptionNA::Some(x) if x == f64::INFINITY => Some(Expr::Inf),
+            _ => None,
+        },
+        Vector::Integer(x) => match x.get_inner(0)? {
+            OptionNA::Some(x) => Some(Expr::Integer(x)),
+            OptionNA::NA => None,
+        },
+        Vector::Logical(x) => match x.get_inner(0)? {
+            OptionNA::Some(x) => Some(Expr::Bool(x)),
+            OptionNA::NA => Some(Expr::NA),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::optimize;
+    use crate::cli::Experiment;
+    use crate::lang::{CallStack, EvalResult};
+    use crate::object::Expr;
+    use crate::session::Session;
+    use crate::{r, r_expect};
+
+    fn parse(code: &str) -> Expr {
+        CallStack::default().parse(code).unwrap()
+    }
+
+    fn eval_optimized(code: &str) -> EvalResult {
+        let session = Session {
+            experiments: vec![Experiment::Optimize],
+            ..Session::default()
+        };
+
+        CallStack::from(session).parse_and_eval(code)
+    }
+
+    #[test]
+    fn constant_subexpressions_are_folded() {
+        assert_eq!(optimize(parse("2 * 3 + x")), parse("6 + x"));
+        assert_eq!(optimize(parse("-1L")), Expr::Integer(-1));
+        assert_eq!(optimize(parse("!(1 > 2)")), Expr::Bool(true));
+        assert_eq!(optimize(parse("1 / 0")), Expr::Inf);
+    }
+
+    #[test]
+    fn only_well_defined_operations_are_folded() {
+        assert_eq!(optimize(parse("1 + 'a'")), parse("1 + 'a'"));
+        assert_eq!(optimize(parse("0 / 0")), parse("0 / 0"));
+        assert_eq!(optimize(parse("c(1, 2)")), parse("c(1, 2)"));
+    }
+
+    #[test]
+    fn quoted_expressions_are_not_optimized() {
+        assert_eq!(optimize(parse("quote(1 + 2)")), parse("quote(1 + 2)"));
+        assert_eq!(eval_optimized("quote(1 + 2)"), r! { quote(1 + 2) });
+    }
+
+    #[test]
+    fn optimized_code_is_evaluated_the_same() {
+        assert_eq!(
+            eval_optimized("f <- function(x) { for (i in 1:3) x <- x + 2 * 3; x }; f(1)"),
+            r! { 19 }
+        );
+    }
+
+    #[test]
+    fn captured_code_is_not_optimized() {
+        assert_eq!(optimize(parse("f(2 * 3)")), parse("f(2 * 3)"));
+        assert_eq!(
+            optimize(parse("function(x) 2 * 3 + x")),
+            parse("function(x) 2 * 3 + x")
+        );
+        assert_eq!(
+            eval_optimized("g <- function(x) substitute(x); g(2 * 3)"),
+            r! { quote(2 * 3) }
+        );
+    }
+
+    #[test]
+    fn literal_arguments_are_substituted() {
+        r_expect! {{"
+            f <- function(x) substitute(x)
+            f(1) == 1
+        "}}
+    }
+}
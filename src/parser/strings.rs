@@ -0,0 +1,116 @@
+use crate::error::Error;
+
+/// Replace the escapes of a string literal with the characters they stand for
+///
+/// Supports the escapes of R, such as `\n`, octal escapes like `\101`, and
+/// hexadecimal escapes like `\x41`, `\u00e9`, `\u{e9}` or `\U{1F600}`.
+/// Unknown escapes, hexadecimal escapes without digits, escapes of invalid
+/// code points and escaped nul characters are reported as an error.
+///
+pub fn unescape(literal: &str) -> Result<String, Error> {
+    let mut unescaped = String::with_capacity(literal.len());
+    let mut chars = literal.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        let Some(escape) = chars.next() else {
+            return Err(Error::InvalidEscape("\\".to_string()));
+        };
+
+        let c = match escape {
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'b' => '\x08',
+            'a' => '\x07',
+            'f' => '\x0c',
+            'v' => '\x0b',
+            '\\' | '"' | '\'' | '`' | '/' | ' ' | '\n' => escape,
+            '0'..='7' => {
+                let mut digits = escape.to_string();
+                while digits.len() < 3 && chars.peek().is_some_and(|c| c.is_digit(8)) {
+                    digits.extend(chars.next());
+                }
+
+                // as in R, octal escapes are limited to a single byte
+                let code = u32::from_str_radix(&digits, 8).unwrap_or_default() & 0xff;
+                match char::from_u32(code) {
+                    Some(c) if c != '\0' => c,
+                    _ => return Err(Error::NulEscape(format!("\\{digits}"))),
+                }
+            }
+            'x' => unescape_hex(&mut chars, escape, 2, false)?,
+            'u' => unescape_hex(&mut chars, escape, 4, true)?,
+            'U' => unescape_hex(&mut chars, escape, 8, true)?,
+            _ => return Err(Error::InvalidEscape(format!("\\{escape}"))),
+        };
+
+        unescaped.push(c);
+    }
+
+    Ok(unescaped)
+}
+
+/// Read the hexadecimal digits of an escape, as in `\x41` or `\U{1F600}`
+fn unescape_hex<I>(
+    chars: &mut std::iter::Peekable<I>,
+    escape: char,
+    max_digits: usize,
+    braced: bool,
+) -> Result<char, Error>
+where
+    I: Iterator<Item = char>,
+{
+    let braced = braced && chars.next_if_eq(&'{').is_some();
+    let mut digits = String::new();
+    while digits.len() < max_digits && chars.peek().is_some_and(|c| c.is_ascii_hexdigit()) {
+        digits.extend(chars.next());
+    }
+
+    if digits.is_empty() {
+        return Err(Error::EscapeWithoutHexDigits(format!("\\{escape}")));
+    }
+
+    let escaped = match braced {
+        true if chars.next_if_eq(&'}').is_none() => {
+            return Err(Error::InvalidUnicodeEscape(format!("\\{escape}{{{digits}")));
+        }
+        true => format!("\\{escape}{{{digits}}}"),
+        false => format!("\\{escape}{digits}"),
+    };
+
+    match u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+    {
+        Some('\0') => Err(Error::NulEscape(escaped)),
+        Some(c) => Ok(c),
+        None => Err(Error::InvalidUnicodeEscape(escaped)),
+    }
+}
+
+/// Escape a string, such that it can be parsed back from within double quotes
+pub fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\x08' => escaped.push_str("\\b"),
+            '\x07' => escaped.push_str("\\a"),
+            '\x0c' => escaped.push_str("\\f"),
+            '\x0b' => escaped.push_str("\\v"),
+            c if c.is_ascii_control() => escaped.push_str(&format!("\\{:03o}", c as u32)),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:04x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,6 +1,5 @@
 use reedline::{FileBackedHistory, Reedline};
 use std::io::Write;
-use std::rc::Rc;
 
 use super::prompt::Prompt;
 use super::release::*;
@@ -12,10 +11,7 @@ use crate::session::{Session, SessionParserConfig};
 
 pub fn repl(mut session: Session) -> Result<(), Signal> {
     writeln!(session.output, "{}", session_header(&session)).ok();
-    let global_env = Rc::new(Environment {
-        parent: Some(Environment::from_builtins()),
-        ..Default::default()
-    });
+    let global_env = Environment::new(Some(Environment::from_builtins()));
 
     let history = session
         .history
@@ -63,7 +59,9 @@ pub fn repl(mut session: Session) -> Result<(), Signal> {
                                 write!(session.output, "backtrace:\n{stack}").ok();
                             }
                             Ok(val) => {
-                                writeln!(session.output, "{val}").ok();
+                                if let Err(e) = stack.print_value(val) {
+                                    write!(session.output, "{e}").ok();
+                                }
                             }
                         }
                     }
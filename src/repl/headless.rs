@@ -57,10 +57,7 @@ pub fn wasm_runtime(args: JsValue) -> JsValue {
     log(&format!("Launching runtime with args: {args:?}"));
 
     // build our global environment
-    let global_env = Rc::new(Environment {
-        parent: Some(Environment::from_builtins()),
-        ..Default::default()
-    });
+    let global_env = Environment::new(Some(Environment::from_builtins()));
 
     // build a callback to evaluate with a enclosed environment, allows
     // for a callback to be provided to handle stdout
@@ -133,6 +130,7 @@ pub fn wasm_eval_in(args: &Session, env: &Rc<Environment>, input: &str) -> Optio
             let mut stack = CallStack::from(args.clone()).with_global_env(env.clone());
             match stack.eval_and_finalize(expr) {
                 Err(Signal::Condition(Cond::Terminate)) => None,
+                Ok(val) if val.is_object() => stack.print_value(val).err().map(|e| format!("{e}")),
                 Ok(val) => Some(format!("{val}")),
                 Err(e) => Some(format!("{e}")),
             }
@@ -40,24 +40,27 @@ macro_rules! formals {
     ( $what:ident, $args:literal ) => {
         impl CallableFormals for $what {
             fn formals(&self) -> ExprList {
-                static FORMALS: std::sync::LazyLock<ExprList> = std::sync::LazyLock::new(|| {
-                    use $crate::object::{Expr, ExprList};
+                thread_local! {
+                    static FORMALS: ExprList = {
+                        use $crate::object::{Expr, ExprList};
 
-                    let signature = $crate::r_parse! {{ $args }};
-                    let Ok($crate::object::Expr::Call(_, signature)) = signature else {
-                        panic!("unexpected formal definition")
-                    };
+                        let signature = $crate::r_parse! {{ $args }};
+                        let Ok($crate::object::Expr::Call(_, signature)) = signature else {
+                            panic!("unexpected formal definition")
+                        };
 
-                    signature
-                        .into_iter()
-                        .map(|pair| match pair {
-                            (None, Expr::Symbol(name)) => (Some(name), Expr::Missing),
-                            pair => pair,
-                        })
-                        .collect::<ExprList>()
-                });
+                        (*signature)
+                            .clone()
+                            .into_iter()
+                            .map(|pair| match pair {
+                                (None, Expr::Symbol(name)) => (Some(name), Expr::Missing),
+                                pair => pair,
+                            })
+                            .collect::<ExprList>()
+                    };
+                }
 
-                (*FORMALS).clone()
+                FORMALS.with(|formals| formals.clone())
             }
         }
     };